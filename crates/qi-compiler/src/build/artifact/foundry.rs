@@ -0,0 +1,49 @@
+//! The Foundry artifact.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::LinkReferences;
+
+/// The Foundry artifact, written to `out/<File>.sol/<Contract>.json`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+    /// The contract ABI.
+    pub abi: serde_json::Value,
+    /// The deploy bytecode.
+    pub bytecode: Bytecode,
+    /// The runtime bytecode.
+    pub deployed_bytecode: Bytecode,
+    /// The method identifiers.
+    pub method_identifiers: BTreeMap<String, String>,
+    /// The metadata JSON serialized to string.
+    pub raw_metadata: String,
+    /// The metadata JSON.
+    pub metadata: serde_json::Value,
+}
+
+/// The Foundry artifact bytecode.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Bytecode {
+    /// The `0x`-prefixed hexadecimal bytecode, empty while the QiVM code generator is unavailable.
+    pub object: String,
+    /// The source map, which is not produced for the QiVM code.
+    pub source_map: String,
+    /// The unlinked library references.
+    pub link_references: LinkReferences,
+}
+
+impl Bytecode {
+    /// A shortcut constructor.
+    pub fn new(object: String, link_references: LinkReferences) -> Self {
+        Self {
+            object,
+            source_map: String::new(),
+            link_references,
+        }
+    }
+}
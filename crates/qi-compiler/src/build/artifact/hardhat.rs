@@ -0,0 +1,121 @@
+//! The Hardhat artifacts.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use revive_solc_json_interface::SolcStandardJsonOutput;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+
+use super::LinkReferences;
+use crate::solc::version::Version as SolcVersion;
+
+/// The Hardhat contract artifact, written to `artifacts/<path>/<Contract>.json`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+    /// The artifact format identifier.
+    #[serde(rename = "_format")]
+    pub format: String,
+    /// The contract name.
+    pub contract_name: String,
+    /// The contract source path.
+    pub source_name: String,
+    /// The contract ABI.
+    pub abi: serde_json::Value,
+    /// The `0x`-prefixed hexadecimal deploy bytecode, empty while the QiVM code generator is unavailable.
+    pub bytecode: String,
+    /// The `0x`-prefixed hexadecimal runtime bytecode, equal to the deploy one.
+    pub deployed_bytecode: String,
+    /// The unlinked library references of the deploy bytecode.
+    pub link_references: LinkReferences,
+    /// The unlinked library references of the runtime bytecode.
+    pub deployed_link_references: LinkReferences,
+    /// The metadata JSON serialized to string.
+    pub metadata: String,
+}
+
+impl Artifact {
+    /// The artifact format identifier.
+    pub const FORMAT: &'static str = "hh-sol-artifact-1";
+}
+
+/// The Hardhat debug file, written next to the contract artifact as `<Contract>.dbg.json`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugFile {
+    /// The debug file format identifier.
+    #[serde(rename = "_format")]
+    pub format: String,
+    /// The build info path, relative to the debug file.
+    pub build_info: String,
+}
+
+impl DebugFile {
+    /// The debug file format identifier.
+    pub const FORMAT: &'static str = "hh-sol-dbg-1";
+
+    /// Creates the debug file of the contract placed under the relative `source_path`, which refers to the build info `id`.
+    pub fn new(source_path: &Path, id: &str) -> Self {
+        let depth = source_path.components().count();
+        let mut build_info = PathBuf::new();
+        for _ in 0..depth {
+            build_info.push("..");
+        }
+        build_info.push(BuildInfo::DIRECTORY);
+        build_info.push(format!("{id}.json"));
+
+        Self {
+            format: Self::FORMAT.to_owned(),
+            build_info: build_info.to_string_lossy().replace('\\', "/"),
+        }
+    }
+}
+
+/// The Hardhat build info, written to `artifacts/build-info/<id>.json`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    /// The build info format identifier.
+    #[serde(rename = "_format")]
+    pub format: String,
+    /// The build identifier, derived from the compiler version and input.
+    pub id: String,
+    /// The `solc` version.
+    pub solc_version: String,
+    /// The `solc` long version.
+    pub solc_long_version: String,
+    /// The `solc` standard JSON input.
+    pub input: serde_json::Value,
+    /// The `solc` standard JSON output.
+    pub output: serde_json::Value,
+}
+
+impl BuildInfo {
+    /// The build info format identifier.
+    pub const FORMAT: &'static str = "hh-sol-build-info-1";
+    /// The build info directory name.
+    pub const DIRECTORY: &'static str = "build-info";
+
+    /// Creates the build info from the `solc` standard JSON input and output.
+    pub fn try_new(
+        solc_version: &SolcVersion,
+        input: serde_json::Value,
+        output: &SolcStandardJsonOutput,
+    ) -> anyhow::Result<Self> {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(solc_version.long.as_bytes());
+        hasher.update(serde_json::to_vec(&input)?);
+        let id = hex::encode(hasher.finalize());
+
+        Ok(Self {
+            format: Self::FORMAT.to_owned(),
+            id,
+            solc_version: solc_version.default.to_string(),
+            solc_long_version: solc_version.long.to_owned(),
+            input,
+            output: serde_json::to_value(output)?,
+        })
+    }
+}
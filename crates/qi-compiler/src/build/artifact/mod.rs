@@ -0,0 +1,123 @@
+//! The toolchain artifact formats.
+
+pub mod foundry;
+pub mod hardhat;
+
+use std::collections::BTreeMap;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::selection::Selection;
+
+/// The unlinked library references, keyed by the library source path and name.
+pub type LinkReferences = BTreeMap<String, BTreeMap<String, Vec<LinkReference>>>;
+
+/// The toolchain artifact format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactFormat {
+    /// The Foundry `out/<File>.sol/<Contract>.json` layout.
+    Foundry,
+    /// The Hardhat `artifacts/<path>/<Contract>.json` layout with the `build-info` directory.
+    Hardhat,
+}
+
+impl ArtifactFormat {
+    /// Returns the output directory used by the toolchain by default.
+    pub fn default_output_directory(&self) -> &'static str {
+        match self {
+            Self::Foundry => "out",
+            Self::Hardhat => "artifacts",
+        }
+    }
+
+    /// Returns the contract artifacts needed to fill the toolchain artifact.
+    pub fn selection(&self) -> Selection {
+        Selection {
+            bin: true,
+            abi: true,
+            hashes: true,
+            metadata: true,
+            ..Selection::default()
+        }
+    }
+}
+
+impl FromStr for ArtifactFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "foundry" => Ok(Self::Foundry),
+            "hardhat" => Ok(Self::Hardhat),
+            string => {
+                anyhow::bail!("Invalid artifact format `{string}`: expected `foundry` or `hardhat`")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ArtifactFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Foundry => write!(f, "foundry"),
+            Self::Hardhat => write!(f, "hardhat"),
+        }
+    }
+}
+
+/// The unlinked library reference.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct LinkReference {
+    /// The byte offset of the placeholder in the bytecode.
+    pub start: usize,
+    /// The placeholder length in bytes.
+    pub length: usize,
+}
+
+/// Formats the bytecode as a `0x`-prefixed hexadecimal string, as expected by the toolchains.
+pub fn bytecode_object(bytecode: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytecode))
+}
+
+/// Returns the source path the artifacts are placed under, relative to the output directory.
+/// The absolute paths lose their root, and the ones leaving the directory with `..` are rejected.
+pub fn relative_source_path(source_path: &str) -> anyhow::Result<PathBuf> {
+    let mut relative_path = PathBuf::new();
+    for component in Path::new(source_path).components() {
+        match component {
+            Component::Normal(component) => relative_path.push(component),
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => anyhow::bail!(
+                "Source `{source_path}` is outside the project, so its artifacts cannot be placed in the output directory"
+            ),
+        }
+    }
+    if relative_path.as_os_str().is_empty() {
+        anyhow::bail!("Invalid contract source path `{source_path}`");
+    }
+    Ok(relative_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    #[test]
+    fn keeps_the_source_paths_inside_the_output_directory() {
+        assert_eq!(
+            super::relative_source_path("contracts/./Token.sol").expect("Always valid"),
+            PathBuf::from("contracts/Token.sol")
+        );
+        assert_eq!(
+            super::relative_source_path("/home/user/Token.sol").expect("Always valid"),
+            PathBuf::from("home/user/Token.sol")
+        );
+        assert!(super::relative_source_path("../Token.sol").is_err());
+        assert!(super::relative_source_path("contracts/../../Token.sol").is_err());
+    }
+}
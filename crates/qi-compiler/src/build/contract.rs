@@ -0,0 +1,273 @@
+//! The Solidity contract build.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use revive_solc_json_interface::combined_json::contract::Contract as CombinedJsonContract;
+use revive_solc_json_interface::SolcStandardJsonOutputContract;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::artifact::foundry::Artifact as FoundryArtifact;
+use super::artifact::foundry::Bytecode as FoundryBytecode;
+use super::artifact::hardhat::Artifact as HardhatArtifact;
+use super::artifact::hardhat::DebugFile as HardhatDebugFile;
+use super::artifact::LinkReferences;
+use super::selection::Selection;
+
+/// The Solidity contract build.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Contract {
+    /// The full contract path, in the `<path>:<name>` format.
+    pub path: String,
+    /// The QiVM bytecode, if the code generator is available, see `Contract::IS_CODEGEN_AVAILABLE`.
+    pub bytecode: Option<Vec<u8>>,
+    /// The QiVM assembly listing, if the code generator is available.
+    pub assembly: Option<String>,
+    /// The metadata JSON.
+    pub metadata_json: serde_json::Value,
+    /// The ABI, if requested from `solc`.
+    pub abi: Option<serde_json::Value>,
+    /// The method identifiers, if requested from `solc`.
+    pub method_identifiers: Option<BTreeMap<String, String>>,
+    /// The storage layout, if requested from `solc`.
+    pub storage_layout: Option<serde_json::Value>,
+    /// The placeholders left in the bytecode for the unlinked libraries.
+    #[serde(default)]
+    pub link_references: LinkReferences,
+}
+
+impl Contract {
+    /// The QiVM bytecode file extension.
+    pub const EXTENSION_BINARY: &'static str = "bin";
+    /// The QiVM assembly file extension.
+    pub const EXTENSION_ASSEMBLY: &'static str = "asm";
+    /// The method identifiers file extension.
+    pub const EXTENSION_HASHES: &'static str = "signatures";
+    /// The metadata file extension.
+    pub const EXTENSION_METADATA: &'static str = "metadata.json";
+    /// The storage layout file extension.
+    pub const EXTENSION_STORAGE_LAYOUT: &'static str = "storage.json";
+
+    /// A shortcut constructor.
+    /// The build has no code until it is set with `with_code`.
+    pub fn new(
+        path: String,
+        metadata_json: serde_json::Value,
+        abi: Option<serde_json::Value>,
+        method_identifiers: Option<BTreeMap<String, String>>,
+        storage_layout: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            path,
+            bytecode: None,
+            assembly: None,
+            metadata_json,
+            abi,
+            method_identifiers,
+            storage_layout,
+            link_references: LinkReferences::new(),
+        }
+    }
+
+    /// Sets the QiVM bytecode, its assembly listing, and the placeholders left in the bytecode
+    /// for the unlinked libraries.
+    pub fn with_code(
+        mut self,
+        bytecode: Vec<u8>,
+        assembly: String,
+        link_references: LinkReferences,
+    ) -> Self {
+        self.bytecode = Some(bytecode);
+        self.assembly = Some(assembly);
+        self.link_references = link_references;
+        self
+    }
+
+    /// Splits the full contract path into the source path and the contract name.
+    pub fn source_path_and_name(&self) -> anyhow::Result<(&str, &str)> {
+        self.path
+            .rsplit_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Invalid contract path `{}`", self.path))
+    }
+
+    /// Returns the selected contract artifact files named `<output_directory>/<path>_<Contract>.<extension>`,
+    /// with their contents.
+    pub fn directory_files(
+        &self,
+        output_directory: &Path,
+        selection: &Selection,
+    ) -> anyhow::Result<Vec<(PathBuf, Vec<u8>)>> {
+        let (path, name) = self.source_path_and_name()?;
+        let file_prefix = format!("{}_{name}", path.replace(['/', '\\'], "_"));
+
+        let mut artifacts = Vec::with_capacity(6);
+        if selection.bin {
+            artifacts.push((Self::EXTENSION_BINARY, hex::encode(self.bytecode()?)));
+        }
+        if selection.asm {
+            artifacts.push((Self::EXTENSION_ASSEMBLY, self.assembly()?.to_owned()));
+        }
+        if selection.abi {
+            artifacts.push((revive_common::EXTENSION_ABI, self.abi()?.to_string()));
+        }
+        if selection.hashes {
+            let method_identifiers = self.method_identifiers.as_ref().ok_or_else(|| {
+                anyhow::anyhow!("Contract `{}` method identifiers not found", self.path)
+            })?;
+            let hashes = method_identifiers
+                .iter()
+                .map(|(signature, selector)| format!("{selector}: {signature}\n"))
+                .collect::<String>();
+            artifacts.push((Self::EXTENSION_HASHES, hashes));
+        }
+        if selection.metadata {
+            artifacts.push((Self::EXTENSION_METADATA, self.metadata_json.to_string()));
+        }
+        if selection.storage_layout {
+            let storage_layout = self.storage_layout.as_ref().ok_or_else(|| {
+                anyhow::anyhow!("Contract `{}` storage layout not found", self.path)
+            })?;
+            artifacts.push((Self::EXTENSION_STORAGE_LAYOUT, storage_layout.to_string()));
+        }
+
+        Ok(artifacts
+            .into_iter()
+            .map(|(extension, contents)| {
+                (
+                    output_directory.join(format!("{file_prefix}.{extension}")),
+                    contents.into_bytes(),
+                )
+            })
+            .collect())
+    }
+
+    /// Returns the Foundry artifact file `<output_directory>/<source_directory>/<Contract>.json` with its contents.
+    /// The `source_directory` is the source file name, or the whole source path if the file names collide.
+    /// The QiVM code has no separate deploy and runtime parts, so both bytecodes are equal, and empty if no code
+    /// has been generated.
+    pub fn foundry_file(
+        &self,
+        output_directory: &Path,
+        source_directory: &Path,
+    ) -> anyhow::Result<(PathBuf, Vec<u8>)> {
+        let (_, name) = self.source_path_and_name()?;
+        let file_path = output_directory
+            .join(source_directory)
+            .join(format!("{name}.{}", revive_common::EXTENSION_JSON));
+
+        let bytecode =
+            super::artifact::bytecode_object(self.bytecode.as_deref().unwrap_or_default());
+        let artifact = FoundryArtifact {
+            abi: self.abi()?.to_owned(),
+            bytecode: FoundryBytecode::new(bytecode.clone(), self.link_references.clone()),
+            deployed_bytecode: FoundryBytecode::new(bytecode, self.link_references.clone()),
+            method_identifiers: self.method_identifiers.clone().unwrap_or_default(),
+            raw_metadata: self.metadata_json.to_string(),
+            metadata: self.metadata_json.clone(),
+        };
+        Ok((file_path, serde_json::to_vec_pretty(&artifact)?))
+    }
+
+    /// Returns the Hardhat artifact file `<output_directory>/<path>/<Contract>.json` and the debug file
+    /// referring to the build info `build_info_id`, with their contents.
+    /// The bytecodes are empty if no code has been generated.
+    pub fn hardhat_files(
+        &self,
+        output_directory: &Path,
+        build_info_id: &str,
+    ) -> anyhow::Result<Vec<(PathBuf, Vec<u8>)>> {
+        let (path, name) = self.source_path_and_name()?;
+        let relative_path = super::artifact::relative_source_path(path)?;
+        let artifact_directory = output_directory.join(relative_path.as_path());
+
+        let bytecode =
+            super::artifact::bytecode_object(self.bytecode.as_deref().unwrap_or_default());
+        let artifact = HardhatArtifact {
+            format: HardhatArtifact::FORMAT.to_owned(),
+            contract_name: name.to_owned(),
+            source_name: path.to_owned(),
+            abi: self.abi()?.to_owned(),
+            bytecode: bytecode.clone(),
+            deployed_bytecode: bytecode,
+            link_references: self.link_references.clone(),
+            deployed_link_references: self.link_references.clone(),
+            metadata: self.metadata_json.to_string(),
+        };
+        let debug_file = HardhatDebugFile::new(relative_path.as_path(), build_info_id);
+
+        Ok(vec![
+            (
+                artifact_directory.join(format!("{name}.{}", revive_common::EXTENSION_JSON)),
+                serde_json::to_vec_pretty(&artifact)?,
+            ),
+            (
+                artifact_directory.join(format!("{name}.dbg.json")),
+                serde_json::to_vec_pretty(&debug_file)?,
+            ),
+        ])
+    }
+
+    /// Returns the ABI, which must have been requested from `solc`.
+    fn abi(&self) -> anyhow::Result<&serde_json::Value> {
+        self.abi
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Contract `{}` ABI not found", self.path))
+    }
+
+    /// Returns the QiVM bytecode, which must have been generated.
+    fn bytecode(&self) -> anyhow::Result<&[u8]> {
+        self.bytecode
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Contract `{}` bytecode not generated", self.path))
+    }
+
+    /// Returns the QiVM assembly listing, which must have been generated.
+    fn assembly(&self) -> anyhow::Result<&str> {
+        self.assembly
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Contract `{}` assembly not generated", self.path))
+    }
+
+    /// Writes the contract artifacts into the standard JSON output contract.
+    /// Without the QiVM code, the EVM bytecode and assembly `solc` may have output are removed.
+    pub fn write_to_standard_json(
+        self,
+        standard_json_contract: &mut SolcStandardJsonOutputContract,
+    ) -> anyhow::Result<()> {
+        standard_json_contract.metadata = Some(self.metadata_json);
+
+        if let Some(evm) = standard_json_contract.evm.as_mut() {
+            match (self.bytecode, self.assembly) {
+                (Some(bytecode), Some(assembly)) => evm.modify(assembly, hex::encode(bytecode)),
+                _ => {
+                    evm.bytecode = None;
+                    evm.deployed_bytecode = None;
+                    evm.assembly_text = None;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the contract artifacts into the combined JSON contract.
+    /// The QiVM code has no separate deploy and runtime parts, so `bin` and `bin-runtime` are equal.
+    pub fn write_to_combined_json(
+        self,
+        combined_json_contract: &mut CombinedJsonContract,
+    ) -> anyhow::Result<()> {
+        if let Some(bin) = combined_json_contract.bin.as_mut() {
+            *bin = hex::encode(self.bytecode()?);
+        }
+        if let Some(bin_runtime) = combined_json_contract.bin_runtime.as_mut() {
+            *bin_runtime = hex::encode(self.bytecode()?);
+        }
+        if let Some(asm) = combined_json_contract.asm.as_mut() {
+            *asm = serde_json::Value::String(self.assembly()?.to_owned());
+        }
+
+        Ok(())
+    }
+}
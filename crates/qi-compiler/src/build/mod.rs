@@ -0,0 +1,460 @@
+//! The Solidity project build.
+
+pub mod artifact;
+pub mod contract;
+pub mod selection;
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::path::PathBuf;
+
+use revive_solc_json_interface::combined_json::CombinedJson;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+
+use crate::solc::version::Version as SolcVersion;
+use crate::versions::Version;
+
+use self::artifact::hardhat::BuildInfo as HardhatBuildInfo;
+use self::contract::Contract;
+use self::selection::Selection;
+
+/// The Solidity project build.
+#[derive(Debug, Default)]
+pub struct Build {
+    /// The contract builds, or the errors which prevented them, keyed by the full contract path.
+    pub results: BTreeMap<String, anyhow::Result<Contract>>,
+}
+
+impl Build {
+    /// Checks whether any contract has failed to compile.
+    pub fn has_errors(&self) -> bool {
+        self.results.values().any(Result::is_err)
+    }
+
+    /// Writes the selected artifacts of every contract to the output directory.
+    /// Nothing is written if any contract has failed, or if any file exists and `overwrite` is not set.
+    pub fn write_to_directory(
+        self,
+        output_directory: &Path,
+        selection: &Selection,
+        overwrite: bool,
+    ) -> anyhow::Result<()> {
+        let mut files = Vec::new();
+        for build in self.successful_builds()?.into_iter() {
+            files.extend(build.directory_files(output_directory, selection)?);
+        }
+
+        std::fs::create_dir_all(output_directory)?;
+        write_files(files, overwrite)
+    }
+
+    /// Writes the Foundry artifacts of every contract to the output directory.
+    /// The artifacts are placed under the source file name, like Foundry does, unless several sources share it,
+    /// in which case they are placed under the whole source paths to keep them apart.
+    pub fn write_to_foundry(self, output_directory: &Path, overwrite: bool) -> anyhow::Result<()> {
+        let builds = self.successful_builds()?;
+
+        let mut source_paths = BTreeMap::<&OsStr, BTreeSet<&str>>::new();
+        for build in builds.iter() {
+            let (path, _) = build.source_path_and_name()?;
+            source_paths
+                .entry(source_file_name(path)?)
+                .or_default()
+                .insert(path);
+        }
+
+        let mut files = Vec::with_capacity(builds.len());
+        for build in builds.iter() {
+            let (path, _) = build.source_path_and_name()?;
+            let file_name = source_file_name(path)?;
+            let source_directory = if source_paths[file_name].len() > 1 {
+                artifact::relative_source_path(path)?
+            } else {
+                PathBuf::from(file_name)
+            };
+            files.push(build.foundry_file(output_directory, source_directory.as_path())?);
+        }
+
+        write_files(files, overwrite)
+    }
+
+    /// Writes the Hardhat artifacts of every contract and the shared build info to the output directory.
+    pub fn write_to_hardhat(
+        self,
+        output_directory: &Path,
+        build_info: &HardhatBuildInfo,
+        overwrite: bool,
+    ) -> anyhow::Result<()> {
+        let mut files = Vec::new();
+        for build in self.successful_builds()?.into_iter() {
+            files.extend(build.hardhat_files(output_directory, build_info.id.as_str())?);
+        }
+
+        let build_info_path = output_directory
+            .join(HardhatBuildInfo::DIRECTORY)
+            .join(format!(
+                "{}.{}",
+                build_info.id,
+                revive_common::EXTENSION_JSON
+            ));
+        files.push((build_info_path, serde_json::to_vec(build_info)?));
+
+        write_files(files, overwrite)
+    }
+
+    /// Returns the contract builds, failing on the first contract which has failed to compile.
+    fn successful_builds(&self) -> anyhow::Result<Vec<&Contract>> {
+        self.results
+            .iter()
+            .map(|(path, result)| {
+                result
+                    .as_ref()
+                    .map_err(|error| anyhow::anyhow!("{path}: {error:#}"))
+            })
+            .collect()
+    }
+
+    /// Writes the QiVM artifacts into the `solc` standard JSON output.
+    /// The contracts that failed to compile are reported in the `errors` list.
+    pub fn write_to_standard_json(
+        mut self,
+        standard_json: &mut SolcStandardJsonOutput,
+        solc_version: &SolcVersion,
+    ) -> anyhow::Result<()> {
+        if let Some(files) = standard_json.contracts.as_mut() {
+            for (path, contracts) in files.iter_mut() {
+                for (name, contract) in contracts.iter_mut() {
+                    let full_path = format!("{path}:{name}");
+
+                    match self.results.remove(full_path.as_str()) {
+                        Some(Ok(build)) => build.write_to_standard_json(contract)?,
+                        Some(Err(error)) => standard_json
+                            .errors
+                            .get_or_insert_with(Vec::new)
+                            .push(standard_json_error(format!("{full_path}: {error:#}"))),
+                        None => continue,
+                    }
+                }
+            }
+        }
+
+        standard_json.version = Some(solc_version.default.to_string());
+        standard_json.long_version = Some(solc_version.long.to_owned());
+        standard_json.revive_version = Some(Version::default().long);
+
+        Ok(())
+    }
+
+    /// Writes the QiVM artifacts into the `solc` combined JSON output.
+    /// Only the `asm`, `bin` and `bin-runtime` fields requested from `solc` are filled.
+    pub fn write_to_combined_json(
+        mut self,
+        combined_json: &mut CombinedJson,
+    ) -> anyhow::Result<()> {
+        for (path, combined_json_contract) in combined_json.contracts.iter_mut() {
+            match self.results.remove(path.as_str()) {
+                Some(Ok(build)) => build.write_to_combined_json(combined_json_contract)?,
+                Some(Err(error)) => anyhow::bail!("{path}: {error:#}"),
+                None => continue,
+            }
+        }
+
+        combined_json.revive_version = Some(Version::default().long);
+
+        Ok(())
+    }
+}
+
+/// Creates a standard JSON error not bound to any source location.
+pub fn standard_json_error(message: String) -> SolcStandardJsonOutputError {
+    SolcStandardJsonOutputError {
+        component: "general".to_owned(),
+        error_code: None,
+        formatted_message: format!("Error: {message}"),
+        message,
+        severity: "error".to_owned(),
+        source_location: None,
+        r#type: "Error".to_owned(),
+    }
+}
+
+/// Returns the file name of the contract source path.
+fn source_file_name(path: &str) -> anyhow::Result<&OsStr> {
+    Path::new(path)
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid contract source path `{path}`"))
+}
+
+/// Writes the files, creating their parent directories.
+/// Unless `overwrite` is set, all paths are checked first, so nothing is written if any file exists.
+pub fn write_files(files: Vec<(PathBuf, Vec<u8>)>, overwrite: bool) -> anyhow::Result<()> {
+    if !overwrite {
+        for (path, _) in files.iter() {
+            check_overwrite(path.as_path())?;
+        }
+    }
+    for (path, contents) in files.into_iter() {
+        write_file(path.as_path(), contents, true)?;
+    }
+    Ok(())
+}
+
+/// Writes the file, creating its parent directories.
+/// Existing files are only replaced if `overwrite` is set.
+pub fn write_file(path: &Path, contents: impl AsRef<[u8]>, overwrite: bool) -> anyhow::Result<()> {
+    if !overwrite {
+        check_overwrite(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+        .map_err(|error| anyhow::anyhow!("File {path:?} writing error: {error}"))
+}
+
+/// Fails if the file exists, since it must not be overwritten.
+fn check_overwrite(path: &Path) -> anyhow::Result<()> {
+    if path.exists() {
+        anyhow::bail!(
+            "Refusing to overwrite the existing file {path:?} (use --overwrite to force)."
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use revive_solc_json_interface::combined_json::CombinedJson;
+    use revive_solc_json_interface::SolcStandardJsonOutput;
+
+    use crate::debug_config::DebugConfig;
+    use crate::optimizer::settings::Settings as OptimizerSettings;
+    use crate::project::tests::counter_project;
+
+    use super::artifact::hardhat::BuildInfo as HardhatBuildInfo;
+    use super::selection::Selection;
+    use super::Build;
+
+    /// Compiles the `Counter` contracts defined in the source `paths`.
+    fn counter_build(paths: &[&str]) -> Build {
+        counter_project(paths).compile(
+            OptimizerSettings::cycles(),
+            DebugConfig::new(None, false),
+            None,
+        )
+    }
+
+    /// Returns the paths of the files in the `directory` and its subdirectories, relative to it.
+    fn relative_file_paths(directory: &Path) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        let mut directories = vec![directory.to_path_buf()];
+        while let Some(current) = directories.pop() {
+            for entry in std::fs::read_dir(current).expect("Always valid") {
+                let path = entry.expect("Always valid").path();
+                if path.is_dir() {
+                    directories.push(path);
+                } else {
+                    paths.push(
+                        path.strip_prefix(directory)
+                            .expect("Always valid")
+                            .to_owned(),
+                    );
+                }
+            }
+        }
+        paths.sort();
+        paths
+    }
+
+    /// Reads the JSON file at the `path`.
+    fn read_json(path: PathBuf) -> serde_json::Value {
+        serde_json::from_slice(std::fs::read(path).expect("Always valid").as_slice())
+            .expect("Always valid")
+    }
+
+    #[test]
+    fn writes_the_foundry_layout() {
+        let directory =
+            std::env::temp_dir().join(format!("qic-build-foundry-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(directory.as_path());
+
+        counter_build(&["src/Counter.sol"])
+            .write_to_foundry(directory.join("out").as_path(), false)
+            .expect("Always valid");
+        assert_eq!(
+            relative_file_paths(directory.join("out").as_path()),
+            [PathBuf::from("Counter.sol/Counter.json")]
+        );
+        let artifact = read_json(directory.join("out/Counter.sol/Counter.json"));
+        assert_eq!(artifact["bytecode"]["object"], "0x");
+        assert_eq!(artifact["deployedBytecode"]["object"], "0x");
+        assert_eq!(artifact["methodIdentifiers"]["increment()"], "d09de08a");
+        assert_eq!(artifact["abi"][0]["name"], "increment");
+
+        counter_build(&["src/Counter.sol", "test/Counter.sol"])
+            .write_to_foundry(directory.join("collision").as_path(), false)
+            .expect("Always valid");
+        assert_eq!(
+            relative_file_paths(directory.join("collision").as_path()),
+            [
+                PathBuf::from("src/Counter.sol/Counter.json"),
+                PathBuf::from("test/Counter.sol/Counter.json"),
+            ]
+        );
+
+        std::fs::remove_dir_all(directory).expect("Always valid");
+    }
+
+    #[test]
+    fn writes_the_hardhat_layout() {
+        let directory =
+            std::env::temp_dir().join(format!("qic-build-hardhat-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(directory.as_path());
+
+        let project = counter_project(&["contracts/Counter.sol"]);
+        let output: SolcStandardJsonOutput =
+            serde_json::from_value(serde_json::json!({})).expect("Always valid");
+        let build_info = HardhatBuildInfo::try_new(
+            &project.solc_version,
+            serde_json::json!({ "language": "Solidity" }),
+            &output,
+        )
+        .expect("Always valid");
+        project
+            .compile(
+                OptimizerSettings::cycles(),
+                DebugConfig::new(None, false),
+                None,
+            )
+            .write_to_hardhat(directory.as_path(), &build_info, false)
+            .expect("Always valid");
+
+        assert_eq!(
+            relative_file_paths(directory.as_path()),
+            [
+                PathBuf::from(format!("build-info/{}.json", build_info.id)),
+                PathBuf::from("contracts/Counter.sol/Counter.dbg.json"),
+                PathBuf::from("contracts/Counter.sol/Counter.json"),
+            ]
+        );
+        let artifact = read_json(directory.join("contracts/Counter.sol/Counter.json"));
+        assert_eq!(artifact["_format"], "hh-sol-artifact-1");
+        assert_eq!(artifact["sourceName"], "contracts/Counter.sol");
+        assert_eq!(artifact["bytecode"], "0x");
+        assert_eq!(artifact["deployedBytecode"], "0x");
+        let debug_file = read_json(directory.join("contracts/Counter.sol/Counter.dbg.json"));
+        assert_eq!(
+            debug_file["buildInfo"],
+            format!("../../build-info/{}.json", build_info.id)
+        );
+
+        std::fs::remove_dir_all(directory).expect("Always valid");
+    }
+
+    #[test]
+    fn writes_the_combined_json_without_code() {
+        let mut combined_json: CombinedJson = serde_json::from_value(serde_json::json!({
+            "contracts": { "src/Counter.sol:Counter": { "abi": [], "hashes": { "increment()": "d09de08a" } } },
+            "version": "0.8.28",
+        }))
+        .expect("Always valid");
+        counter_build(&["src/Counter.sol"])
+            .write_to_combined_json(&mut combined_json)
+            .expect("Always valid");
+
+        let contract = &combined_json.contracts["src/Counter.sol:Counter"];
+        assert_eq!(contract.hashes.as_ref().expect("Always valid").len(), 1);
+        assert!(contract.bin.is_none());
+        assert!(combined_json.revive_version.is_some());
+
+        let mut combined_json: CombinedJson = serde_json::from_value(serde_json::json!({
+            "contracts": { "src/Counter.sol:Counter": { "bin": "" } },
+            "version": "0.8.28",
+        }))
+        .expect("Always valid");
+        assert!(counter_build(&["src/Counter.sol"])
+            .write_to_combined_json(&mut combined_json)
+            .is_err());
+    }
+
+    #[test]
+    fn writes_the_directory_artifacts_without_code() {
+        let directory =
+            std::env::temp_dir().join(format!("qic-build-directory-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(directory.as_path());
+
+        let selection = Selection {
+            abi: true,
+            hashes: true,
+            metadata: true,
+            storage_layout: true,
+            ..Selection::default()
+        };
+        counter_build(&["src/Counter.sol"])
+            .write_to_directory(directory.as_path(), &selection, false)
+            .expect("Always valid");
+        let mut files = std::fs::read_dir(directory.as_path())
+            .expect("Always valid")
+            .map(|entry| entry.expect("Always valid").file_name())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                "src_Counter.sol_Counter.abi",
+                "src_Counter.sol_Counter.metadata.json",
+                "src_Counter.sol_Counter.signatures",
+                "src_Counter.sol_Counter.storage.json",
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(directory.join("src_Counter.sol_Counter.signatures"))
+                .expect("Always valid"),
+            "d09de08a: increment()\n"
+        );
+
+        let selection = Selection {
+            bin: true,
+            ..Selection::default()
+        };
+        assert!(counter_build(&["src/Counter.sol"])
+            .write_to_directory(directory.as_path(), &selection, true)
+            .is_err());
+
+        std::fs::remove_dir_all(directory).expect("Always valid");
+    }
+
+    #[test]
+    fn refuses_to_overwrite_before_writing_anything() {
+        let directory = std::env::temp_dir().join(format!("qic-build-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(directory.as_path());
+        std::fs::create_dir_all(directory.as_path()).expect("Always valid");
+        let existing = directory.join("A.sol_A.bin");
+        std::fs::write(existing.as_path(), "00").expect("Always valid");
+
+        let files = vec![
+            (directory.join("A.sol_A.abi"), b"[]".to_vec()),
+            (existing.clone(), b"ff".to_vec()),
+        ];
+        assert!(super::write_files(files.clone(), false).is_err());
+        assert!(!directory.join("A.sol_A.abi").exists());
+        assert_eq!(
+            std::fs::read(existing.as_path()).expect("Always valid"),
+            b"00"
+        );
+
+        super::write_files(files, true).expect("Always valid");
+        assert_eq!(
+            std::fs::read(existing.as_path()).expect("Always valid"),
+            b"ff"
+        );
+
+        std::fs::remove_dir_all(directory).expect("Always valid");
+    }
+}
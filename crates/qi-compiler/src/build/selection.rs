@@ -0,0 +1,162 @@
+//! The contract artifacts selection.
+
+use std::collections::HashSet;
+
+use revive_solc_json_interface::SolcStandardJsonInputSettingsSelection;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsSelectionFile;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsSelectionFileFlag as SelectionFlag;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The contract artifacts selection.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    /// Whether to output the QiVM bytecode.
+    pub bin: bool,
+    /// Whether to output the QiVM assembly.
+    pub asm: bool,
+    /// Whether to output the ABI.
+    pub abi: bool,
+    /// Whether to output the method identifiers.
+    pub hashes: bool,
+    /// Whether to output the metadata.
+    pub metadata: bool,
+    /// Whether to output the storage layout.
+    pub storage_layout: bool,
+}
+
+impl Selection {
+    /// Checks whether no artifacts are selected.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Checks whether the selected artifacts need the QiVM code generator: the bytecode or the assembly.
+    pub fn requires_codegen(&self) -> bool {
+        self.bin || self.asm
+    }
+
+    /// Returns the artifacts selected by the `settings.outputSelection` of the standard JSON input.
+    /// The `*` and `evm` wildcards select all the artifacts they cover.
+    pub fn from_standard_json_input(input: &serde_json::Value) -> Self {
+        let flags = input
+            .pointer("/settings/outputSelection")
+            .and_then(serde_json::Value::as_object)
+            .into_iter()
+            .flat_map(|files| files.values())
+            .filter_map(serde_json::Value::as_object)
+            .flat_map(|contracts| contracts.values())
+            .filter_map(serde_json::Value::as_array)
+            .flatten()
+            .filter_map(serde_json::Value::as_str);
+
+        let mut selection = Self::default();
+        for flag in flags {
+            let is_all = flag == "*";
+            let is_evm = is_all || flag == "evm";
+            selection.bin |= is_evm
+                || flag.starts_with("evm.bytecode")
+                || flag.starts_with("evm.deployedBytecode");
+            selection.asm |= is_evm || flag == "evm.assembly";
+            selection.abi |= is_all || flag == "abi";
+            selection.hashes |= is_evm || flag == "evm.methodIdentifiers";
+            selection.metadata |= is_all || flag == "metadata";
+            selection.storage_layout |= is_all || flag == "storageLayout";
+        }
+        selection
+    }
+
+    /// Returns the artifacts selected by the comma-separated `--combined-json` argument.
+    /// The QiVM code has no separate deploy and runtime parts, so `bin-runtime` selects the bytecode as well.
+    pub fn from_combined_json_argument(argument: &str) -> Self {
+        let mut selection = Self::default();
+        for selector in argument.split(',').map(str::trim) {
+            match selector {
+                "bin" | "bin-runtime" => selection.bin = true,
+                "asm" => selection.asm = true,
+                "abi" => selection.abi = true,
+                "hashes" => selection.hashes = true,
+                "metadata" => selection.metadata = true,
+                "storage-layout" => selection.storage_layout = true,
+                _ => {}
+            }
+        }
+        selection
+    }
+
+    /// Returns the `solc` output selection needed to produce the selected artifacts.
+    /// The Yul IR, metadata and AST are always requested, as the compilation pipeline relies on them.
+    pub fn solc_output_selection(&self) -> SolcStandardJsonInputSettingsSelection {
+        let mut per_contract = HashSet::from([SelectionFlag::Yul, SelectionFlag::Metadata]);
+        if self.abi {
+            per_contract.insert(SelectionFlag::ABI);
+        }
+        if self.hashes {
+            per_contract.insert(SelectionFlag::MethodIdentifiers);
+        }
+        if self.storage_layout {
+            per_contract.insert(SelectionFlag::StorageLayout);
+        }
+
+        SolcStandardJsonInputSettingsSelection {
+            all: Some(SolcStandardJsonInputSettingsSelectionFile {
+                per_file: Some(HashSet::from([SelectionFlag::AST])),
+                per_contract: Some(per_contract),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selection;
+
+    #[test]
+    fn reads_the_standard_json_output_selection() {
+        let selection = |flags: serde_json::Value| {
+            Selection::from_standard_json_input(&serde_json::json!({
+                "settings": { "outputSelection": { "*": { "": ["ast"], "*": flags } } },
+            }))
+        };
+
+        assert_eq!(
+            selection(serde_json::json!(["abi", "evm.methodIdentifiers"])),
+            Selection {
+                abi: true,
+                hashes: true,
+                ..Selection::default()
+            }
+        );
+        assert!(!selection(serde_json::json!(["metadata", "storageLayout"])).requires_codegen());
+        assert!(selection(serde_json::json!(["evm.bytecode.object"])).requires_codegen());
+        assert!(selection(serde_json::json!(["evm.deployedBytecode"])).requires_codegen());
+        assert!(selection(serde_json::json!(["evm"])).requires_codegen());
+        assert_eq!(
+            selection(serde_json::json!(["*"])),
+            Selection {
+                bin: true,
+                asm: true,
+                abi: true,
+                hashes: true,
+                metadata: true,
+                storage_layout: true,
+            }
+        );
+    }
+
+    #[test]
+    fn reads_the_combined_json_argument() {
+        assert_eq!(
+            Selection::from_combined_json_argument("abi,hashes,metadata,storage-layout,userdoc"),
+            Selection {
+                abi: true,
+                hashes: true,
+                metadata: true,
+                storage_layout: true,
+                ..Selection::default()
+            }
+        );
+        assert!(Selection::from_combined_json_argument("abi,bin-runtime").requires_codegen());
+        assert!(Selection::from_combined_json_argument("asm").requires_codegen());
+    }
+}
@@ -0,0 +1,197 @@
+//! The content-addressed build cache.
+
+pub mod statistics;
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+use sha2::Digest;
+
+use crate::build::contract::Contract as ContractBuild;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::project::contract::Contract;
+use crate::solc::version::Version as SolcVersion;
+use crate::versions::Version;
+
+use self::statistics::Statistics;
+
+/// The content-addressed build cache.
+/// The `solc` outputs and the contract builds are stored as JSON files named after the hash of
+/// everything they depend on, so changing any input results in a different entry.
+#[derive(Debug)]
+pub struct Cache {
+    /// The cache directory.
+    directory: PathBuf,
+    /// The hit and miss counters.
+    pub statistics: Statistics,
+}
+
+impl Cache {
+    /// The default cache directory.
+    pub const DEFAULT_DIRECTORY: &'static str = ".qic-cache";
+    /// The `solc` outputs subdirectory.
+    const SOLC_DIRECTORY: &'static str = "solc";
+    /// The contract builds subdirectory.
+    const CONTRACTS_DIRECTORY: &'static str = "contracts";
+
+    /// A shortcut constructor.
+    pub fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            statistics: Statistics::default(),
+        }
+    }
+
+    /// Computes the `solc` output key from the normalized standard JSON input, the contents of
+    /// the `sources` read from the filesystem, the compiler versions and the optimizer settings.
+    pub fn solc_output_key(
+        input: &SolcStandardJsonInput,
+        sources: &BTreeMap<String, PathBuf>,
+        solc_version: &SolcVersion,
+        optimizer_settings: &OptimizerSettings,
+    ) -> anyhow::Result<String> {
+        let mut input = serde_json::to_value(input)?;
+        normalize_output_selection(&mut input);
+
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(serde_json::to_vec(&input)?);
+        for (source_unit_name, path) in sources.iter() {
+            let source_code = std::fs::read(path)
+                .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}"))?;
+            hasher.update(source_unit_name.as_bytes());
+            hasher.update(sha2::Sha256::digest(source_code));
+        }
+        Self::update_with_settings(&mut hasher, solc_version, optimizer_settings)?;
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Computes the contract build key from the contract IR and `solc` artifacts,
+    /// the compiler versions and the optimizer settings.
+    pub fn contract_key(
+        contract: &Contract,
+        solc_version: &SolcVersion,
+        optimizer_settings: &OptimizerSettings,
+    ) -> anyhow::Result<String> {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(serde_json::to_vec(contract)?);
+        Self::update_with_settings(&mut hasher, solc_version, optimizer_settings)?;
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Returns the cached `solc` output.
+    pub fn get_solc_output(&self, key: &str) -> Option<SolcStandardJsonOutput> {
+        let output = self.read(Self::SOLC_DIRECTORY, key);
+        self.statistics.record_solc_output(output.is_some());
+        output
+    }
+
+    /// Stores the `solc` output.
+    pub fn put_solc_output(
+        &self,
+        key: &str,
+        output: &SolcStandardJsonOutput,
+    ) -> anyhow::Result<()> {
+        self.write(Self::SOLC_DIRECTORY, key, output)
+    }
+
+    /// Returns the cached contract build.
+    pub fn get_contract(&self, key: &str) -> Option<ContractBuild> {
+        let build = self.read(Self::CONTRACTS_DIRECTORY, key);
+        self.statistics.record_contract(build.is_some());
+        build
+    }
+
+    /// Stores the contract build.
+    pub fn put_contract(&self, key: &str, build: &ContractBuild) -> anyhow::Result<()> {
+        self.write(Self::CONTRACTS_DIRECTORY, key, build)
+    }
+
+    /// Hashes the compiler versions and the optimizer settings.
+    fn update_with_settings(
+        hasher: &mut sha2::Sha256,
+        solc_version: &SolcVersion,
+        optimizer_settings: &OptimizerSettings,
+    ) -> anyhow::Result<()> {
+        hasher.update(solc_version.long.as_bytes());
+        hasher.update(Version::default().long.as_bytes());
+        hasher.update(serde_json::to_vec(optimizer_settings)?);
+        Ok(())
+    }
+
+    /// Reads the entry. Missing or unreadable entries are treated as misses.
+    fn read<T>(&self, subdirectory: &str, key: &str) -> Option<T>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let contents = std::fs::read(self.entry_path(subdirectory, key)).ok()?;
+        serde_json::from_slice(contents.as_slice()).ok()
+    }
+
+    /// Writes the entry through a temporary file, so that readers never see a partial one.
+    /// The temporary file name is unique to the writer, so the concurrent writers of the same entry,
+    /// whether threads or `qic` processes, never share it, and the last rename wins with complete contents.
+    fn write<T>(&self, subdirectory: &str, key: &str, value: &T) -> anyhow::Result<()>
+    where
+        T: serde::Serialize,
+    {
+        static TEMPORARY_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = self.entry_path(subdirectory, key);
+        let temporary_path = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            TEMPORARY_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_vec(value)?;
+        let result = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(temporary_path.as_path())
+            .and_then(|mut file| file.write_all(contents.as_slice()))
+            .map_err(|error| anyhow::anyhow!("File {temporary_path:?} writing error: {error}"))
+            .and_then(|()| {
+                std::fs::rename(temporary_path.as_path(), path.as_path())
+                    .map_err(|error| anyhow::anyhow!("File {path:?} writing error: {error}"))
+            });
+        if result.is_err() {
+            let _ = std::fs::remove_file(temporary_path.as_path());
+        }
+        result
+    }
+
+    /// Returns the path of the entry.
+    fn entry_path(&self, subdirectory: &str, key: &str) -> PathBuf {
+        self.directory
+            .join(subdirectory)
+            .join(format!("{key}.{}", revive_common::EXTENSION_JSON))
+    }
+}
+
+/// Sorts the output selection flags, which are not serialized in a stable order.
+pub(crate) fn normalize_output_selection(input: &mut serde_json::Value) {
+    let Some(selection) = input.pointer_mut("/settings/outputSelection") else {
+        return;
+    };
+    let Some(files) = selection.as_object_mut() else {
+        return;
+    };
+    for contracts in files.values_mut() {
+        let Some(contracts) = contracts.as_object_mut() else {
+            continue;
+        };
+        for flags in contracts.values_mut() {
+            if let Some(flags) = flags.as_array_mut() {
+                flags.sort_by_key(|flag| flag.to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! The build cache statistics.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+/// The build cache hit and miss counters.
+#[derive(Debug, Default)]
+pub struct Statistics {
+    /// The number of reused `solc` outputs.
+    pub solc_output_hits: AtomicUsize,
+    /// The number of `solc` runs.
+    pub solc_output_misses: AtomicUsize,
+    /// The number of reused contract builds.
+    pub contract_hits: AtomicUsize,
+    /// The number of compiled contracts.
+    pub contract_misses: AtomicUsize,
+}
+
+impl Statistics {
+    /// Records a `solc` output lookup.
+    pub fn record_solc_output(&self, is_hit: bool) {
+        let counter = if is_hit {
+            &self.solc_output_hits
+        } else {
+            &self.solc_output_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a contract build lookup.
+    pub fn record_contract(&self, is_hit: bool) {
+        let counter = if is_hit {
+            &self.contract_hits
+        } else {
+            &self.contract_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl std::fmt::Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Cache: solc output {} hit(s), {} miss(es); contracts {} hit(s), {} miss(es)",
+            self.solc_output_hits.load(Ordering::Relaxed),
+            self.solc_output_misses.load(Ordering::Relaxed),
+            self.contract_hits.load(Ordering::Relaxed),
+            self.contract_misses.load(Ordering::Relaxed),
+        )
+    }
+}
@@ -1,9 +1,19 @@
 use std::collections::BTreeSet;
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
 
+use clap::Args;
 use clap::Parser;
+use clap::Subcommand;
 use path_slash::PathExt;
+use qi_compiler::build::artifact::ArtifactFormat;
+use qi_compiler::build::selection::Selection;
+use qi_compiler::config::profile::Profile;
+use qi_compiler::diagnostic::ErrorFormat;
+use qi_compiler::imports::remapping::Remapping;
+use qi_compiler::Config;
+use qi_compiler::Error;
 
 #[derive(Debug, Parser)]
 #[command(name = "The Qi compiler", arg_required_else_help = true)]
@@ -13,7 +23,8 @@ pub struct Arguments {
     pub version: bool,
 
     /// Specify the input paths and remappings.
-    /// If an argument contains a '=', it is considered a remapping.
+    /// If an argument contains a '=', it is considered a remapping: `[<context>:]<prefix>=<target>`.
+    /// The remappings from `remappings.txt` and the dependencies in `lib` are added automatically.
     /// Multiple Solidity files can be passed in the default Solidity mode.
     /// Yul, LLVM IR, and PolkaVM Assembly modes currently support only a single file.
     pub inputs: Vec<String>,
@@ -38,27 +49,107 @@ pub struct Arguments {
     #[arg(short = 'o', long = "output-dir")]
     pub output_directory: Option<PathBuf>,
 
+    /// Output the QiVM bytecode of every contract.
+    #[arg(long = "bin")]
+    pub output_binary: bool,
+
+    /// Output the QiVM assembly of every contract.
+    #[arg(long = "asm")]
+    pub output_assembly: bool,
+
+    /// Output the ABI of every contract.
+    #[arg(long = "abi")]
+    pub output_abi: bool,
+
+    /// Output the function signature hashes of every contract.
+    #[arg(long = "hashes")]
+    pub output_hashes: bool,
+
+    /// Output the metadata of every contract.
+    #[arg(long = "metadata")]
+    pub output_metadata: bool,
+
+    /// Output the storage layout of every contract.
+    #[arg(long = "storage-layout")]
+    pub output_storage_layout: bool,
+
+    /// Write the contract artifacts in the layout expected by the toolchain: `foundry` or `hardhat`.
+    /// The output directory defaults to `out` for Foundry and `artifacts` for Hardhat.
+    #[arg(long = "artifact-format")]
+    pub artifact_format: Option<ArtifactFormat>,
+
+    /// Overwrite the existing files in the output directory.
+    #[arg(long = "overwrite")]
+    pub overwrite: bool,
+
     /// Specify the path to the `solc` executable. By default, the one in `${PATH}` is used.
     /// Yul mode: `solc` is used for source code validation, as `resolc` itself assumes that the input Yul is valid.
     /// LLVM IR mode: `solc` is unused.
     #[arg(long = "solc")]
     pub solc: Option<String>,
 
+    /// Specify the path to a `soljson.js` or `soljson.wasm` build to run in the embedded WebAssembly
+    /// runtime instead of the `solc` executable. Combined JSON mode is not supported.
+    #[cfg(feature = "soljson")]
+    #[arg(long = "soljson")]
+    pub soljson: Option<PathBuf>,
+
+    /// Select the `solc` executable for every input file automatically: the newest supported
+    /// `solc-<version>` found in the `--solc-dir` directories or in `${PATH}` which satisfies the
+    /// version pragmas of the file and of the files it imports.
+    #[arg(long = "auto-solc")]
+    pub auto_solc: bool,
+
+    /// Add a directory to search for the `solc-<version>` executables in `--auto-solc` mode.
+    #[arg(long = "solc-dir")]
+    pub solc_directories: Vec<PathBuf>,
+
+    /// Switch to standard JSON input/output mode. Read from stdin, write the result to stdout.
+    /// This is the default used by the Hardhat and Foundry plugins.
+    #[arg(long = "standard-json")]
+    pub standard_json: bool,
+
+    /// Output a single JSON document containing the specified information.
+    /// Available arguments: `abi`, `hashes`, `metadata`, `devdoc`, `userdoc`, `storage-layout`, `ast`, `asm`, `bin`, `bin-runtime`.
+    /// The `asm`, `bin` and `bin-runtime` fields are filled with the QiVM artifacts.
+    #[arg(long = "combined-json")]
+    pub combined_json: Option<String>,
+
     /// Set the optimization parameter -O[0 | 1 | 2 | 3 | s | z].
     /// Use `3` for best performance and `z` for minimal size.
     #[arg(short = 'O', long = "optimization")]
     pub optimization: Option<char>,
 
     /// Try to recompile with -Oz if the bytecode is too large.
-    #[arg(long = "fallback-Oz")]
+    #[arg(
+        long = "fallback-Oz",
+        overrides_with = "no_fallback_to_optimizing_for_size"
+    )]
     pub fallback_to_optimizing_for_size: bool,
 
+    /// Do not recompile with -Oz, even if the configuration file profile enables it.
+    #[arg(
+        long = "no-fallback-Oz",
+        overrides_with = "fallback_to_optimizing_for_size"
+    )]
+    pub no_fallback_to_optimizing_for_size: bool,
+
     /// Disable the `solc` optimizer.
     /// Use it if your project uses the `MSIZE` instruction, or in other cases.
     /// Beware that it will prevent libraries from being inlined.
-    #[arg(long = "disable-solc-optimizer")]
+    #[arg(
+        long = "disable-solc-optimizer",
+        overrides_with = "enable_solc_optimizer"
+    )]
     pub disable_solc_optimizer: bool,
 
+    /// Enable the `solc` optimizer, even if the configuration file profile disables it.
+    #[arg(
+        long = "enable-solc-optimizer",
+        overrides_with = "disable_solc_optimizer"
+    )]
+    pub enable_solc_optimizer: bool,
+
     /// The EVM target version to generate IR for.
     /// See https://github.com/paritytech/revive/blob/main/crates/common/src/evm_version.rs for reference.
     #[arg(long = "evm-version")]
@@ -83,32 +174,438 @@ pub struct Arguments {
     #[arg(long = "llvm-debug-logging")]
     pub llvm_debug_logging: bool,
 
+    /// Disable the specified Yul SSA IR optimization passes.
+    /// Available arguments: `constant-folding`, `memory-forwarding`, `sload-elimination`,
+    /// `dead-store-elimination`, `unused-function-removal`.
+    /// Only for testing and debugging.
+    #[arg(long = "disable-yul-pass")]
+    pub disabled_yul_passes: Vec<String>,
+
     /// Suppress specified warnings.
-    /// Available arguments: `ecrecover`, `sendtransfer`, `extcodesize`, `txorigin`, `blocktimestamp`, `blocknumber`, `blockhash`.
+    /// Available arguments: `ecrecover`, `sendtransfer`, `extcodesize`, `txorigin`, `blocktimestamp`, `blocknumber`, `blockhash`,
+    /// and the QiVM compatibility lints `msize`, `gas`, `selfdestruct`.
     #[arg(long = "suppress-warnings")]
     pub suppress_warnings: Option<Vec<String>>,
 
+    /// Set the number of threads compiling the contracts. Defaults to the number of CPUs.
+    /// Requires the `parallel` feature.
+    #[arg(long = "threads")]
+    pub threads: Option<usize>,
+
+    /// Compile every contract in a separate `qic` process, so that a crash while compiling
+    /// one contract is reported as an error of that contract instead of aborting the build.
+    #[arg(long = "process-per-contract")]
+    pub process_per_contract: bool,
+
+    /// Run the recursive process mode: compile the contract read from stdin and write the result to stdout.
+    /// Only for internal use by `--process-per-contract`.
+    #[arg(long = "recursive-process", hide = true)]
+    pub recursive_process: bool,
+
+    /// Do not reuse or store the `solc` outputs and the contract builds in the cache directory.
+    #[arg(long = "no-cache")]
+    pub no_cache: bool,
+
+    /// Set the build cache directory. Defaults to `.qic-cache`.
+    #[arg(long = "cache-dir")]
+    pub cache_directory: Option<PathBuf>,
+
+    /// Print the build cache hits and misses to stderr.
+    #[arg(long = "cache-stats")]
+    pub cache_statistics: bool,
+
+    /// Use the given configuration file instead of looking for `qic.toml`
+    /// in the current directory and its ancestors.
+    #[arg(long = "config")]
+    pub config: Option<PathBuf>,
+
+    /// Select the build profile from the configuration file: `dev`, `release`, or a custom one.
+    /// Without it, no profile is applied.
+    #[arg(long = "profile")]
+    pub profile: Option<String>,
+
+    /// Print the configuration merged from the configuration file and the command line, and exit.
+    #[arg(long = "print-config")]
+    pub print_config: bool,
+
+    /// The diagnostics format: `human`, `json` for one JSON object per line, or `sarif` for a SARIF log.
+    /// The diagnostics are printed to stderr.
+    #[arg(long = "error-format")]
+    pub error_format: Option<ErrorFormat>,
+
+    /// Print the description of the error code, e.g. `Q0001`, and exit.
+    #[arg(long = "explain")]
+    pub explain: Option<String>,
+
     /// Generate source based debug information in the output code file. This only has an effect
     /// with the LLVM-IR code generator and is ignored otherwise.
-    #[arg(short = 'g')]
+    #[arg(short = 'g', overrides_with = "no_emit_source_debug_info")]
     pub emit_source_debug_info: bool,
 
+    /// Do not generate the source based debug information, even if the configuration file profile enables it.
+    #[arg(long = "no-debug-info", overrides_with = "emit_source_debug_info")]
+    pub no_emit_source_debug_info: bool,
+
     /// Dump all IRs to files in the specified directory.
     /// Only for testing and debugging.
     #[arg(long = "debug-output-dir")]
     pub debug_output_directory: Option<PathBuf>,
+
+    /// The subcommand, such as `fmt`.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// The `qic` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Format the Yul files in place.
+    Fmt(FmtArguments),
+    /// Check the Yul files for syntax, scoping and, optionally, type errors.
+    Check(CheckArguments),
+}
+
+/// The `qic fmt` arguments.
+#[derive(Debug, Args)]
+pub struct FmtArguments {
+    /// Format Yul. Required, since Yul is the only language formatted for now.
+    #[arg(long = "yul", required = true)]
+    pub yul: bool,
+
+    /// Check that the files are formatted instead of writing them, and fail if they are not.
+    #[arg(long = "check")]
+    pub check: bool,
+
+    /// The files to format.
+    #[arg(required = true)]
+    pub paths: Vec<PathBuf>,
+}
+
+/// The `qic check` arguments.
+#[derive(Debug, Args)]
+pub struct CheckArguments {
+    /// Check Yul. Required, since Yul is the only language checked standalone for now.
+    #[arg(long = "yul", required = true)]
+    pub yul: bool,
+
+    /// Check the types of the typed Yul dialect, with the `u256`, `bool`, `uintN` and `intN` types.
+    #[arg(long = "typed")]
+    pub typed: bool,
+
+    /// The files to check, each holding a Yul object or a bare block.
+    #[arg(required = true)]
+    pub paths: Vec<PathBuf>,
 }
 
 impl Arguments {
     /// Validate the arguments.
     pub fn validate(&self) -> anyhow::Result<()> {
         if self.version && std::env::args().count() > 2 {
-            anyhow::bail!("No other options are allowed while getting the compiler version.");
+            return Err(Error::invalid_arguments(
+                "No other options are allowed while getting the compiler version.",
+            )
+            .into());
+        }
+
+        if self.standard_json && self.combined_json.is_some() {
+            return Err(Error::invalid_arguments(
+                "Only one of standard JSON and combined JSON modes can be used at once.",
+            )
+            .into());
+        }
+
+        if self.auto_solc && self.solc.is_some() {
+            return Err(Error::invalid_arguments(
+                "Only one of `--solc` and `--auto-solc` can be used at once.",
+            )
+            .into());
+        }
+        if !self.auto_solc && !self.solc_directories.is_empty() {
+            return Err(Error::invalid_arguments(
+                "`--solc-dir` can only be used with `--auto-solc`.",
+            )
+            .into());
+        }
+        #[cfg(feature = "soljson")]
+        if self.soljson.is_some() {
+            if self.solc.is_some() || self.auto_solc {
+                return Err(Error::invalid_arguments(
+                    "`--soljson` cannot be used with `--solc` or `--auto-solc`.",
+                )
+                .into());
+            }
+            if self.combined_json.is_some() {
+                return Err(Error::invalid_arguments(
+                    "`--soljson` cannot be used in combined JSON mode.",
+                )
+                .into());
+            }
+        }
+
+        if self.artifact_format.is_some() {
+            if self.standard_json || self.combined_json.is_some() {
+                return Err(Error::invalid_arguments(
+                    "Artifact format cannot be used in standard JSON or combined JSON modes.",
+                )
+                .into());
+            }
+            if !self.artifact_selection().is_empty() {
+                return Err(Error::invalid_arguments(
+                    "Artifact format cannot be combined with the individual artifact options.",
+                )
+                .into());
+            }
+        }
+
+        if self.output_directory.is_none() && !self.artifact_selection().is_empty() {
+            return Err(Error::invalid_arguments(
+                "Output directory must be specified to output the contract artifacts.",
+            )
+            .into());
+        }
+        if self.output_directory.is_some()
+            && self.combined_json.is_none()
+            && !self.standard_json
+            && self.artifact_format.is_none()
+            && self.artifact_selection().is_empty()
+        {
+            return Err(Error::invalid_arguments(
+                "No output requested. Use --bin, --asm, --abi, --hashes, --metadata, --storage-layout or --artifact-format."
+            ).into());
+        }
+
+        if self.standard_json {
+            if !self.inputs.is_empty() {
+                return Err(Error::invalid_arguments(
+                    "Input files must be passed via standard JSON input.",
+                )
+                .into());
+            }
+            if !self.libraries.is_empty() {
+                return Err(Error::invalid_arguments(
+                    "Libraries must be passed via standard JSON input.",
+                )
+                .into());
+            }
+            if self.error_format.is_some() {
+                return Err(Error::invalid_arguments(
+                    "Error format cannot be used in standard JSON mode.",
+                )
+                .into());
+            }
+            if self.output_directory.is_some() {
+                return Err(Error::invalid_arguments(
+                    "Output directory cannot be used in standard JSON mode.",
+                )
+                .into());
+            }
+            if self.optimization.is_some() {
+                return Err(Error::invalid_arguments(
+                    "LLVM optimizations must be specified in standard JSON input settings.",
+                )
+                .into());
+            }
+            if self.fallback_to_optimizing_for_size {
+                return Err(Error::invalid_arguments(
+                    "Falling back to -Oz must be specified in standard JSON input settings.",
+                )
+                .into());
+            }
+            if self.disable_solc_optimizer {
+                return Err(Error::invalid_arguments(
+                    "Disabling the solc optimizer must be specified in standard JSON input settings."
+                ).into());
+            }
+            if self.evm_version.is_some() {
+                return Err(Error::invalid_arguments(
+                    "EVM version must be passed via standard JSON input.",
+                )
+                .into());
+            }
+            if self.suppress_warnings.is_some() {
+                return Err(Error::invalid_arguments(
+                    "Warnings suppression must be specified in standard JSON input settings.",
+                )
+                .into());
+            }
+            if self.auto_solc {
+                return Err(Error::invalid_arguments(
+                    "Automatic `solc` selection is not available in standard JSON mode.",
+                )
+                .into());
+            }
+            if self.no_cache || self.cache_directory.is_some() || self.cache_statistics {
+                return Err(Error::invalid_arguments(
+                    "The build cache is not used in standard JSON mode.",
+                )
+                .into());
+            }
         }
 
         Ok(())
     }
 
+    /// Loads the configuration file, if any, and applies it to the arguments not set on the command line.
+    pub fn apply_config(&mut self) -> anyhow::Result<()> {
+        let config = match self.config {
+            Some(ref path) => {
+                let mut config = Config::try_from_path(path.as_path())?;
+                if let Some(root) = path.parent() {
+                    config.resolve_paths(root);
+                }
+                Some(config)
+            }
+            None => match Config::discover(std::env::current_dir()?.as_path())? {
+                Some((root, mut config)) => {
+                    config.resolve_paths(root.as_path());
+                    Some(config)
+                }
+                None => None,
+            },
+        };
+        let config = match (config, self.profile.is_some()) {
+            (Some(config), _) => config,
+            (None, true) => Config::default(),
+            (None, false) => return Ok(()),
+        };
+
+        let profile = match self.profile {
+            Some(ref profile_name) => config.profile(profile_name.as_str())?,
+            None => Profile::default(),
+        };
+
+        let (input_files, remappings) = self.split_input_files_and_remappings()?;
+        if input_files.is_empty() {
+            self.inputs.extend(
+                config
+                    .sources
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string()),
+            );
+        }
+        let remappings = remappings
+            .into_iter()
+            .flatten()
+            .map(|remapping| Remapping::from_str(remapping.as_str()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        for remapping in config.remappings.into_iter() {
+            let parsed = Remapping::from_str(remapping.as_str())?;
+            if !remappings.iter().any(|overridden| {
+                overridden.context == parsed.context && overridden.prefix == parsed.prefix
+            }) {
+                self.inputs.push(remapping);
+            }
+        }
+
+        if self.base_path.is_none() {
+            self.base_path = config
+                .base_path
+                .map(|path| path.to_string_lossy().to_string());
+        }
+        if self.include_paths.is_empty() {
+            self.include_paths = config
+                .include_paths
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect();
+        }
+        if self.allow_paths.is_none() && !config.allow_paths.is_empty() {
+            self.allow_paths = Some(
+                config
+                    .allow_paths
+                    .iter()
+                    .map(|path| path.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+        if self.libraries.is_empty() {
+            self.libraries = config.libraries;
+        }
+        if self.suppress_warnings.is_none() && !config.suppress_warnings.is_empty() {
+            self.suppress_warnings = Some(config.suppress_warnings);
+        }
+
+        if self.optimization.is_none() {
+            self.optimization = profile.optimization;
+        }
+        if !self.fallback_to_optimizing_for_size && !self.no_fallback_to_optimizing_for_size {
+            self.fallback_to_optimizing_for_size =
+                profile.fallback_to_optimizing_for_size.unwrap_or_default();
+        }
+        if !self.emit_source_debug_info && !self.no_emit_source_debug_info {
+            self.emit_source_debug_info = profile.debug_info.unwrap_or_default();
+        }
+        if !self.disable_solc_optimizer && !self.enable_solc_optimizer {
+            self.disable_solc_optimizer = profile.disable_solc_optimizer.unwrap_or_default();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the effective configuration described by the arguments.
+    /// Only the selected profile is included.
+    pub fn effective_config(&self) -> anyhow::Result<Config> {
+        let (sources, remappings) = self.split_input_files_and_remappings()?;
+
+        let mut config = Config {
+            sources,
+            base_path: self.base_path.as_ref().map(PathBuf::from),
+            include_paths: self.include_paths.iter().map(PathBuf::from).collect(),
+            allow_paths: self
+                .allow_paths
+                .iter()
+                .flat_map(|paths| paths.split(','))
+                .map(PathBuf::from)
+                .collect(),
+            remappings: remappings.into_iter().flatten().collect(),
+            libraries: self.libraries.clone(),
+            suppress_warnings: self.suppress_warnings.clone().unwrap_or_default(),
+            ..Config::default()
+        };
+        if let Some(ref profile_name) = self.profile {
+            config.profiles.insert(
+                profile_name.to_owned(),
+                Profile {
+                    optimization: self.optimization,
+                    fallback_to_optimizing_for_size: Some(self.fallback_to_optimizing_for_size),
+                    debug_info: Some(self.emit_source_debug_info),
+                    disable_solc_optimizer: Some(self.disable_solc_optimizer),
+                },
+            );
+        }
+
+        Ok(config)
+    }
+
+    /// Returns the contract artifacts selected for the output directory.
+    pub fn artifact_selection(&self) -> Selection {
+        Selection {
+            bin: self.output_binary,
+            asm: self.output_assembly,
+            abi: self.output_abi,
+            hashes: self.output_hashes,
+            metadata: self.output_metadata,
+            storage_layout: self.output_storage_layout,
+        }
+    }
+
+    /// Returns the requested output mode which requires the QiVM bytecode, if any.
+    /// The standard JSON mode is checked separately, since it reports the errors as JSON.
+    pub fn bytecode_output_mode(&self) -> Option<&'static str> {
+        if let Some(argument) = self.combined_json.as_deref() {
+            Selection::from_combined_json_argument(argument)
+                .requires_codegen()
+                .then_some("The `bin`, `bin-runtime` and `asm` combined JSON outputs")
+        } else if self.output_directory.is_some() {
+            self.artifact_selection()
+                .requires_codegen()
+                .then_some("The `--bin` and `--asm` outputs")
+        } else {
+            None
+        }
+    }
+
     /// Returns remappings from input paths.
     pub fn split_input_files_and_remappings(
         &self,
@@ -118,22 +615,8 @@ impl Arguments {
 
         for input in self.inputs.iter() {
             if input.contains('=') {
-                let mut parts = Vec::with_capacity(2);
-                for path in input.trim().split('=') {
-                    let path = PathBuf::from(path);
-                    parts.push(
-                        Self::path_to_posix(path.as_path())?
-                            .to_string_lossy()
-                            .to_string(),
-                    );
-                }
-                if parts.len() != 2 {
-                    anyhow::bail!(
-                        "Invalid remapping `{}`: expected two parts separated by '='",
-                        input
-                    );
-                }
-                remappings.insert(parts.join("="));
+                let remapping = Remapping::from_str(input)?;
+                remappings.insert(remapping.to_string());
             } else {
                 let path = PathBuf::from(input.trim());
                 let path = Self::path_to_posix(path.as_path())?;
@@ -160,3 +643,165 @@ impl Arguments {
         Ok(path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::Arguments;
+
+    #[test]
+    fn gates_only_the_combined_json_bytecode() {
+        let mode = |argument: &str| {
+            Arguments::parse_from(["qic", "--combined-json", argument, "A.sol"])
+                .bytecode_output_mode()
+        };
+        assert_eq!(mode("abi,hashes,metadata,storage-layout"), None);
+        assert!(mode("abi,bin").is_some());
+        assert!(mode("bin-runtime").is_some());
+        assert!(mode("asm").is_some());
+    }
+
+    #[test]
+    fn gates_only_the_output_directory_bytecode() {
+        let mode = |flags: &[&str]| {
+            Arguments::parse_from(["qic", "-o", "out", "A.sol"].iter().chain(flags))
+                .bytecode_output_mode()
+        };
+        assert_eq!(
+            mode(&["--abi", "--hashes", "--metadata", "--storage-layout"]),
+            None
+        );
+        assert!(mode(&["--abi", "--bin"]).is_some());
+        assert!(mode(&["--asm"]).is_some());
+    }
+
+    #[test]
+    fn accepts_the_artifact_formats() {
+        for format in ["foundry", "hardhat"] {
+            let arguments = Arguments::parse_from(["qic", "--artifact-format", format, "A.sol"]);
+            assert_eq!(arguments.bytecode_output_mode(), None);
+        }
+    }
+
+    /// Applies the configuration file `contents`, written to the temporary directory `name`, to the command line
+    /// `arguments`.
+    fn apply_config(name: &str, contents: &str, arguments: &[&str]) -> Arguments {
+        let directory =
+            std::env::temp_dir().join(format!("qic-arguments-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(directory.as_path()).expect("Always valid");
+        let path = directory.join("qic.toml");
+        std::fs::write(path.as_path(), contents).expect("Always valid");
+
+        let config = path.to_string_lossy().to_string();
+        let mut arguments =
+            Arguments::parse_from(["qic", "--config", config.as_str()].iter().chain(arguments));
+        arguments.apply_config().expect("Always valid");
+
+        std::fs::remove_dir_all(directory).expect("Always valid");
+        arguments
+    }
+
+    #[test]
+    fn prefers_the_command_line_to_the_config() {
+        let config = r#"
+sources = ["B.sol"]
+base-path = "base"
+remappings = ["a/=config/a/", "b/=config/b/", "A.sol:c/=config/c/"]
+libraries = ["B.sol:B=0x0000000000000000000000000000000000000001"]
+
+[profile.release]
+optimization = "z"
+"#;
+        let arguments = apply_config(
+            "precedence",
+            config,
+            &[
+                "--profile",
+                "release",
+                "-O",
+                "2",
+                "--base-path",
+                "cli",
+                "A.sol",
+                "a/=cli/a/",
+                "c/=cli/c/",
+            ],
+        );
+        assert_eq!(arguments.optimization, Some('2'));
+        assert!(arguments.fallback_to_optimizing_for_size);
+        assert_eq!(arguments.base_path.as_deref(), Some("cli"));
+        assert_eq!(
+            arguments.libraries,
+            ["B.sol:B=0x0000000000000000000000000000000000000001"]
+        );
+
+        let (input_files, remappings) = arguments
+            .split_input_files_and_remappings()
+            .expect("Always valid");
+        assert_eq!(input_files, [std::path::PathBuf::from("A.sol")]);
+        let remappings = remappings.expect("Always valid");
+        assert!(remappings
+            .iter()
+            .any(|remapping| remapping.ends_with("=cli/a/")));
+        assert!(remappings
+            .iter()
+            .any(|remapping| remapping.ends_with("/config/b/")));
+        assert!(remappings
+            .iter()
+            .any(|remapping| remapping.ends_with("=cli/c/")));
+        assert!(remappings
+            .iter()
+            .any(|remapping| remapping.ends_with("/config/c/")));
+        assert!(!remappings
+            .iter()
+            .any(|remapping| remapping.ends_with("/config/a/")));
+    }
+
+    #[test]
+    fn turns_the_profile_flags_off() {
+        let config = r#"
+[profile.custom]
+fallback-Oz = true
+debug-info = true
+disable-solc-optimizer = true
+"#;
+        let arguments = apply_config("profile", config, &["--profile", "custom", "A.sol"]);
+        assert!(arguments.fallback_to_optimizing_for_size);
+        assert!(arguments.emit_source_debug_info);
+        assert!(arguments.disable_solc_optimizer);
+
+        let arguments = apply_config(
+            "overrides",
+            config,
+            &[
+                "--profile",
+                "custom",
+                "--no-fallback-Oz",
+                "--no-debug-info",
+                "--enable-solc-optimizer",
+                "A.sol",
+            ],
+        );
+        assert!(!arguments.fallback_to_optimizing_for_size);
+        assert!(!arguments.emit_source_debug_info);
+        assert!(!arguments.disable_solc_optimizer);
+
+        let arguments = apply_config(
+            "last",
+            config,
+            &[
+                "--profile",
+                "custom",
+                "--fallback-Oz",
+                "--no-fallback-Oz",
+                "A.sol",
+            ],
+        );
+        assert!(!arguments.fallback_to_optimizing_for_size);
+
+        let arguments = apply_config("no-profile", config, &["A.sol"]);
+        assert!(!arguments.fallback_to_optimizing_for_size);
+        assert!(!arguments.emit_source_debug_info);
+    }
+}
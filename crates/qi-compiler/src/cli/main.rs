@@ -1,12 +1,34 @@
 pub mod arguments;
+pub mod reporter;
 
-use arguments::Arguments;
-use qi_compiler::{Compiler, DebugConfig, OptimizerSettings, SolcCompiler, Version};
+use arguments::{Arguments, CheckArguments, Command, FmtArguments};
+use qi_compiler::build::artifact::hardhat::BuildInfo as HardhatBuildInfo;
+use qi_compiler::build::artifact::ArtifactFormat;
+use qi_compiler::build::selection::Selection;
+use qi_compiler::diagnostic::Severity;
+use qi_compiler::imports::remapping::Remapping;
+use qi_compiler::imports::resolver::{Resolution, Resolver};
+use qi_compiler::solc::installation::Installation;
+use qi_compiler::solc::pragma::VersionPragma;
+use qi_compiler::yul::ast::Source;
+use qi_compiler::yul::lint::Lint;
+use qi_compiler::yul::semantic;
+use qi_compiler::yul::semantic::typing;
+use qi_compiler::Diagnostic;
+use qi_compiler::{
+    Build, Cache, Compiler, DebugConfig, OptimizerSettings, Project, SolcCompiler, Version,
+};
+use reporter::Reporter;
 use revive_solc_json_interface::{
     SolcStandardJsonInput, SolcStandardJsonInputLanguage, SolcStandardJsonInputSettingsOptimizer,
-    SolcStandardJsonInputSettingsSelection,
+    SolcStandardJsonInputSettingsSelection, SolcStandardJsonOutput, SolcStandardJsonOutputError,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
 };
-use std::{collections::BTreeMap, io::Write};
 
 /// The common application success exit code.
 pub const EXIT_CODE_SUCCESS: i32 = 0;
@@ -15,17 +37,48 @@ pub const EXIT_CODE_SUCCESS: i32 = 0;
 pub const EXIT_CODE_FAILURE: i32 = 1;
 
 fn main() -> anyhow::Result<()> {
-    std::process::exit(match main_inner() {
+    let mut reporter = Reporter::default();
+    let exit_code = match main_inner(&mut reporter) {
         Ok(()) => EXIT_CODE_SUCCESS,
         Err(error) => {
-            writeln!(std::io::stderr(), "{error}")?;
+            reporter.report_error(&error)?;
             EXIT_CODE_FAILURE
         }
-    })
+    };
+    reporter.finish()?;
+    std::process::exit(exit_code)
 }
 
-fn main_inner() -> anyhow::Result<()> {
-    let arguments = <Arguments as clap::Parser>::try_parse()?;
+fn main_inner(reporter: &mut Reporter) -> anyhow::Result<()> {
+    let mut arguments = <Arguments as clap::Parser>::try_parse()?;
+    reporter.format = arguments.error_format.unwrap_or_default();
+    if arguments.recursive_process {
+        return qi_compiler::process::run();
+    }
+    if let Some(ref code) = arguments.explain {
+        let explanation =
+            qi_compiler::error::explanation::explain(code.as_str()).ok_or_else(|| {
+                qi_compiler::Error::invalid_arguments(format!("Unknown error code `{code}`."))
+            })?;
+        writeln!(std::io::stdout(), "{explanation}")?;
+        return Ok(());
+    }
+    match arguments.command {
+        Some(Command::Fmt(ref fmt_arguments)) => return fmt(fmt_arguments, reporter),
+        Some(Command::Check(ref check_arguments)) => return check(check_arguments, reporter),
+        None => {}
+    }
+    if !arguments.version && !arguments.standard_json {
+        arguments.apply_config()?;
+    }
+    if arguments.print_config {
+        write!(
+            std::io::stdout(),
+            "{}",
+            arguments.effective_config()?.to_toml()?
+        )?;
+        return Ok(());
+    }
     arguments.validate()?;
 
     if arguments.version {
@@ -49,26 +102,141 @@ fn main_inner() -> anyhow::Result<()> {
         None => DebugConfig::new(None, arguments.emit_source_debug_info),
     };
 
+    if let Some(threads) = arguments.threads {
+        #[cfg(feature = "parallel")]
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()?;
+        #[cfg(not(feature = "parallel"))]
+        if threads > 1 {
+            anyhow::bail!("Compiling with more than one thread requires the `parallel` feature.");
+        }
+    }
+
+    if arguments.process_per_contract {
+        qi_compiler::process::EXECUTABLE
+            .set(std::env::current_exe()?)
+            .expect("Always empty");
+    }
+
+    if arguments.standard_json {
+        return standard_json(arguments, debug_config);
+    }
+    if let Some(mode) = arguments.bytecode_output_mode() {
+        Project::ensure_codegen_available(mode)?;
+    }
+
+    let cache = if arguments.no_cache {
+        None
+    } else {
+        Some(Cache::new(
+            arguments
+                .cache_directory
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(Cache::DEFAULT_DIRECTORY)),
+        ))
+    };
+    let print_cache_statistics = arguments.cache_statistics;
+
+    let result = compile(arguments, debug_config, cache.as_ref(), reporter);
+    if let Some(cache) = cache.as_ref().filter(|_| print_cache_statistics) {
+        writeln!(std::io::stderr(), "{}", cache.statistics)?;
+    }
+    result
+}
+
+/// Compiles the input files, reusing the `solc` output and the contract builds from the `cache`.
+fn compile(
+    arguments: Arguments,
+    debug_config: DebugConfig,
+    cache: Option<&Cache>,
+    reporter: &mut Reporter,
+) -> anyhow::Result<()> {
     let (input_files, remappings) = arguments.split_input_files_and_remappings()?;
+    let resolution = resolve_imports(
+        input_files.as_slice(),
+        remappings,
+        arguments.base_path.as_deref(),
+        arguments.include_paths.as_slice(),
+        reporter,
+    )?;
 
-    let suppressed_warnings = match arguments.suppress_warnings {
-        Some(warnings) => Some(revive_solc_json_interface::ResolcWarning::try_from_strings(
-            warnings.as_slice(),
-        )?),
-        None => None,
+    let compilation_units = if arguments.auto_solc {
+        select_solc(input_files, &resolution, &arguments)?
+    } else {
+        vec![(solc(&arguments)?, input_files)]
+    };
+    if compilation_units.len() > 1 && arguments.combined_json.is_some() {
+        anyhow::bail!(
+            "Combined JSON mode requires all input files to be compiled with the same `solc` version."
+        );
+    }
+
+    let options = CompilationOptions {
+        arguments: &arguments,
+        resolution: &resolution,
+        debug_config,
+        cache,
     };
+    let mut compiled_contracts = BTreeSet::new();
+    for (mut solc, input_files) in compilation_units.into_iter() {
+        compile_unit(
+            &options,
+            solc.as_mut(),
+            input_files,
+            &mut compiled_contracts,
+            reporter,
+        )?;
+    }
 
-    let mut solc = {
-        #[cfg(not(target_os = "emscripten"))]
-        {
-            SolcCompiler::new(
-                arguments
-                    .solc
-                    .unwrap_or_else(|| SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned()),
-            )?
-        }
+    Ok(())
+}
+
+/// The options shared by all compilation units.
+struct CompilationOptions<'a> {
+    /// The command line arguments.
+    arguments: &'a Arguments,
+    /// The remappings and the source units of the input files.
+    resolution: &'a Resolution,
+    /// The debug output settings.
+    debug_config: DebugConfig,
+    /// The build cache, unless disabled.
+    cache: Option<&'a Cache>,
+}
+
+/// Compiles the input files with one `solc` executable.
+/// The contracts in `compiled_contracts` were output by the previous compilation units and are skipped.
+fn compile_unit(
+    options: &CompilationOptions,
+    solc: &mut dyn Compiler,
+    input_files: Vec<PathBuf>,
+    compiled_contracts: &mut BTreeSet<String>,
+    reporter: &mut Reporter,
+) -> anyhow::Result<()> {
+    let CompilationOptions {
+        arguments,
+        resolution,
+        ref debug_config,
+        cache,
+    } = *options;
+    let remappings = Some(
+        resolution
+            .remappings
+            .iter()
+            .map(Remapping::to_string)
+            .collect::<BTreeSet<_>>(),
+    )
+    .filter(|remappings| !remappings.is_empty());
+    let sources = &resolution.sources;
+    let artifact_selection = match arguments.artifact_format {
+        Some(artifact_format) => artifact_format.selection(),
+        None => arguments.artifact_selection(),
     };
 
+    let (suppressed_warnings, suppressed_lints) =
+        Lint::split_suppressed(arguments.suppress_warnings.as_deref().unwrap_or_default())?;
+    let suppressed_warnings = Some(suppressed_warnings).filter(|warnings| !warnings.is_empty());
+
     let solc_version = solc.version()?;
 
     let mut optimizer_settings = match arguments.optimization {
@@ -80,14 +248,17 @@ fn main_inner() -> anyhow::Result<()> {
     }
     optimizer_settings.is_verify_each_enabled = arguments.llvm_verify_each;
     optimizer_settings.is_debug_logging_enabled = arguments.llvm_debug_logging;
+    for pass in arguments.disabled_yul_passes.iter() {
+        optimizer_settings.disable_yul_pass(pass.parse()?);
+    }
 
     let solc_input = SolcStandardJsonInput::try_from_paths(
         SolcStandardJsonInputLanguage::Solidity,
         None,
         input_files.as_slice(),
-        arguments.libraries,
-        remappings,
-        SolcStandardJsonInputSettingsSelection::new_required(),
+        arguments.libraries.clone(),
+        remappings.clone(),
+        artifact_selection.solc_output_selection(),
         SolcStandardJsonInputSettingsOptimizer::new(
             !arguments.disable_solc_optimizer,
             None,
@@ -98,12 +269,44 @@ fn main_inner() -> anyhow::Result<()> {
         suppressed_warnings,
     )?;
 
-    let solc_output = solc.standard_json(
-        solc_input,
-        arguments.base_path,
-        arguments.include_paths,
-        arguments.allow_paths,
-    )?;
+    // The Hardhat build info records the exact input passed to `solc`.
+    let build_info_input = match arguments.artifact_format {
+        Some(ArtifactFormat::Hardhat) => Some(serde_json::to_value(&solc_input)?),
+        _ => None,
+    };
+
+    let solc_output_key = match cache {
+        Some(_) => Some(Cache::solc_output_key(
+            &solc_input,
+            sources,
+            &solc_version,
+            &optimizer_settings,
+        )?),
+        None => None,
+    };
+    let cached_solc_output = cache
+        .zip(solc_output_key.as_deref())
+        .and_then(|(cache, key)| cache.get_solc_output(key));
+    let solc_output = match cached_solc_output {
+        Some(solc_output) => solc_output,
+        None => {
+            let solc_output = solc.standard_json(
+                solc_input,
+                arguments.base_path.clone(),
+                arguments.include_paths.clone(),
+                arguments.allow_paths.clone(),
+            )?;
+            let has_errors = solc_output.errors.as_deref().is_some_and(|errors| {
+                errors
+                    .iter()
+                    .any(|error| error.severity.as_str() == "error")
+            });
+            if let (Some(cache), Some(key), false) = (cache, solc_output_key, has_errors) {
+                cache.put_solc_output(key.as_str(), &solc_output)?;
+            }
+            solc_output
+        }
+    };
 
     if let Some(errors) = solc_output.errors.as_deref() {
         let mut has_errors = false;
@@ -113,14 +316,128 @@ fn main_inner() -> anyhow::Result<()> {
                 has_errors = true;
             }
 
-            writeln!(std::io::stderr(), "{error}")?;
+            let contents = error
+                .source_location
+                .as_ref()
+                .and_then(|location| sources.get(location.file.as_str()))
+                .and_then(|path| std::fs::read_to_string(path).ok());
+            reporter.report(Diagnostic::from_solc(error, contents.as_deref()))?;
         }
 
         if has_errors {
-            anyhow::bail!("Error(s) found. Compilation aborted");
+            return Err(qi_compiler::Error::CompilationFailed.into());
         }
     }
 
+    let mut project = Project::try_from_standard_json_output(&solc_output, solc_version.clone())?
+        .with_ir_optimized_asts(solc.take_ir_optimized_asts());
+    project
+        .contracts
+        .retain(|path, _| !compiled_contracts.contains(path));
+    let source_contents = sources
+        .iter()
+        .filter_map(|(name, path)| Some((name.to_owned(), std::fs::read_to_string(path).ok()?)))
+        .collect::<BTreeMap<_, _>>();
+    let mut has_errors = false;
+    for diagnostic in project.check(&source_contents, suppressed_lints.as_slice()) {
+        has_errors |= diagnostic.severity == Severity::Error;
+        reporter.report(diagnostic)?;
+    }
+    if has_errors {
+        return Err(qi_compiler::Error::CompilationFailed.into());
+    }
+
+    if let Some(combined_json_argument) = arguments.combined_json.as_ref() {
+        let build = build(
+            project,
+            optimizer_settings,
+            debug_config.clone(),
+            cache,
+            compiled_contracts,
+            reporter,
+        )?;
+
+        // `solc` treats the `<prefix>=<target>` arguments as remappings, just like `qic` does.
+        let mut combined_json_inputs = input_files;
+        combined_json_inputs.extend(
+            remappings
+                .into_iter()
+                .flatten()
+                .map(std::path::PathBuf::from),
+        );
+        let mut combined_json = solc.combined_json(
+            combined_json_inputs.as_slice(),
+            combined_json_argument.as_str(),
+        )?;
+        build.write_to_combined_json(&mut combined_json)?;
+
+        match arguments.output_directory.as_ref() {
+            Some(output_directory) => {
+                std::fs::create_dir_all(output_directory.as_path())?;
+                combined_json
+                    .write_to_directory(output_directory.as_path(), arguments.overwrite)?;
+            }
+            None => {
+                serde_json::to_writer(std::io::stdout(), &combined_json)?;
+                writeln!(std::io::stdout())?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    if let Some(artifact_format) = arguments.artifact_format {
+        let output_directory = arguments
+            .output_directory
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(artifact_format.default_output_directory()));
+        let build = build(
+            project,
+            optimizer_settings,
+            debug_config.clone(),
+            cache,
+            compiled_contracts,
+            reporter,
+        )?;
+        match artifact_format {
+            ArtifactFormat::Foundry => {
+                build.write_to_foundry(output_directory.as_path(), arguments.overwrite)?
+            }
+            ArtifactFormat::Hardhat => {
+                let build_info = HardhatBuildInfo::try_new(
+                    &solc_version,
+                    build_info_input.unwrap_or_default(),
+                    &solc_output,
+                )?;
+                build.write_to_hardhat(
+                    output_directory.as_path(),
+                    &build_info,
+                    arguments.overwrite,
+                )?
+            }
+        }
+
+        return Ok(());
+    }
+
+    if let Some(output_directory) = arguments.output_directory.as_ref() {
+        let build = build(
+            project,
+            optimizer_settings,
+            debug_config.clone(),
+            cache,
+            compiled_contracts,
+            reporter,
+        )?;
+        build.write_to_directory(
+            output_directory.as_path(),
+            &artifact_selection,
+            arguments.overwrite,
+        )?;
+
+        return Ok(());
+    }
+
     let files = match solc_output.contracts.as_ref() {
         Some(files) => files,
         None => match &solc_output.errors {
@@ -130,11 +447,13 @@ fn main_inner() -> anyhow::Result<()> {
             _ => &BTreeMap::new(),
         },
     };
-    // let mut project_contracts = BTreeMap::new();
 
     for (path, contracts) in files.iter() {
         for (name, contract) in contracts.iter() {
             let full_path = format!("{path}:{name}");
+            if !compiled_contracts.insert(full_path.clone()) {
+                continue;
+            }
 
             let ir_optimized = match contract.ir_optimized.to_owned() {
                 Some(ir_optimized) => ir_optimized,
@@ -150,3 +469,521 @@ fn main_inner() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Formats the Yul files in place or, with `--check`, checks that they are formatted.
+/// The files with syntax errors are reported and left unchanged.
+fn fmt(arguments: &FmtArguments, reporter: &mut Reporter) -> anyhow::Result<()> {
+    let mut has_errors = false;
+    let mut unformatted = Vec::new();
+    for path in arguments.paths.iter() {
+        let code = std::fs::read_to_string(path)
+            .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}"))?;
+        let formatted = match qi_compiler::yul::formatter::format(code.as_str()) {
+            Ok(formatted) => formatted,
+            Err(errors) => {
+                let name = path.to_string_lossy();
+                for error in errors.into_iter() {
+                    reporter.report(Diagnostic::from_yul(
+                        Severity::Error,
+                        None,
+                        error.message,
+                        name.as_ref(),
+                        code.as_str(),
+                        error.range,
+                        &BTreeMap::new(),
+                    ))?;
+                }
+                has_errors = true;
+                continue;
+            }
+        };
+        if formatted == code {
+            continue;
+        }
+
+        if arguments.check {
+            writeln!(std::io::stdout(), "Would reformat: {}", path.display())?;
+            unformatted.push(path);
+        } else {
+            std::fs::write(path, formatted)
+                .map_err(|error| anyhow::anyhow!("File {path:?} writing error: {error}"))?;
+        }
+    }
+
+    if has_errors {
+        return Err(qi_compiler::Error::CompilationFailed.into());
+    }
+    if !unformatted.is_empty() {
+        anyhow::bail!(
+            "{} of {} files are not formatted. Run `qic fmt --yul` to format them.",
+            unformatted.len(),
+            arguments.paths.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks the Yul files for syntax and semantic errors and, with `--typed`, type errors.
+/// The type errors are only checked in the files free of the other errors.
+fn check(arguments: &CheckArguments, reporter: &mut Reporter) -> anyhow::Result<()> {
+    let mut has_errors = false;
+    for path in arguments.paths.iter() {
+        let code = std::fs::read_to_string(path)
+            .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}"))?;
+        let errors = match Source::parse(code.as_str()) {
+            Ok(source) => {
+                let analysis = match source {
+                    Source::Object(ref object) => semantic::analyze(object),
+                    Source::Block(ref block) => semantic::analyze_block(block),
+                };
+                let errors = if analysis.errors.is_empty() && arguments.typed {
+                    let typing = match source {
+                        Source::Object(ref object) => typing::check(object, &analysis.symbols),
+                        Source::Block(ref block) => typing::check_block(block, &analysis.symbols),
+                    };
+                    typing.errors
+                } else {
+                    analysis.errors
+                };
+                errors
+                    .into_iter()
+                    .map(|error| (error.message, error.range))
+                    .collect::<Vec<_>>()
+            }
+            Err(errors) => errors
+                .into_iter()
+                .map(|error| (error.message, error.range))
+                .collect(),
+        };
+
+        let name = path.to_string_lossy();
+        for (message, range) in errors.into_iter() {
+            reporter.report(Diagnostic::from_yul(
+                Severity::Error,
+                None,
+                message,
+                name.as_ref(),
+                code.as_str(),
+                range,
+                &BTreeMap::new(),
+            ))?;
+            has_errors = true;
+        }
+    }
+
+    if has_errors {
+        return Err(qi_compiler::Error::CompilationFailed.into());
+    }
+
+    Ok(())
+}
+
+/// Runs the standard JSON mode.
+/// Failures are reported as standard JSON errors instead of failing the process.
+fn standard_json(arguments: Arguments, debug_config: DebugConfig) -> anyhow::Result<()> {
+    let output = match standard_json_inner(arguments, debug_config) {
+        Ok(output) => serde_json::to_value(output)?,
+        Err(error) => serde_json::json!({ "errors": [to_standard_json_error(&error)] }),
+    };
+    serde_json::to_writer(std::io::stdout(), &output)?;
+    Ok(())
+}
+
+/// Compiles the standard JSON input read from stdin.
+fn standard_json_inner(
+    arguments: Arguments,
+    debug_config: DebugConfig,
+) -> anyhow::Result<SolcStandardJsonOutput> {
+    let mut solc = solc(&arguments)?;
+
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input)?;
+
+    compile_standard_json(input.as_slice(), solc.as_mut(), arguments, debug_config)
+}
+
+/// Compiles the standard JSON `input` with the `solc` compiler.
+/// The artifacts which need no QiVM code are output even if the code generator is unavailable,
+/// in which case the selected bytecode and assembly outputs are reported as an error.
+fn compile_standard_json(
+    input: &[u8],
+    solc: &mut dyn Compiler,
+    arguments: Arguments,
+    debug_config: DebugConfig,
+) -> anyhow::Result<SolcStandardJsonOutput> {
+    let solc_version = solc.version()?;
+
+    let input: serde_json::Value = revive_common::deserialize_from_slice(input)
+        .map_err(|error| anyhow::anyhow!("Standard JSON input parsing error: {error}"))?;
+    let selection = Selection::from_standard_json_input(&input);
+    let mut solc_input: SolcStandardJsonInput = serde_json::from_value(input)
+        .map_err(|error| anyhow::anyhow!("Standard JSON input parsing error: {error}"))?;
+
+    let mut optimizer_settings = OptimizerSettings::try_from(&solc_input.settings.optimizer)?;
+    optimizer_settings.is_verify_each_enabled = arguments.llvm_verify_each;
+    optimizer_settings.is_debug_logging_enabled = arguments.llvm_debug_logging;
+    for pass in arguments.disabled_yul_passes.iter() {
+        optimizer_settings.disable_yul_pass(pass.parse()?);
+    }
+
+    let suppressed_lints = solc_input
+        .suppressed_warnings
+        .iter()
+        .flatten()
+        .filter_map(Lint::from_resolc_warning)
+        .collect::<Vec<_>>();
+    let source_contents = solc_input
+        .sources
+        .iter()
+        .filter_map(|(name, source)| {
+            let source = serde_json::to_value(source).ok()?;
+            let content = source.get("content")?.as_str()?;
+            Some((name.to_owned(), content.to_owned()))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    solc_input
+        .settings
+        .output_selection
+        .get_or_insert_with(SolcStandardJsonInputSettingsSelection::new_required)
+        .extend_with_required();
+
+    let mut solc_output = solc.standard_json(
+        solc_input,
+        arguments.base_path,
+        arguments.include_paths,
+        arguments.allow_paths,
+    )?;
+
+    let has_errors = solc_output.errors.as_deref().is_some_and(|errors| {
+        errors
+            .iter()
+            .any(|error| error.severity.as_str() == "error")
+    });
+    if has_errors {
+        return Ok(solc_output);
+    }
+
+    let project = Project::try_from_standard_json_output(&solc_output, solc_version.clone())?
+        .with_ir_optimized_asts(solc.take_ir_optimized_asts());
+    let check_errors = project
+        .check(&source_contents, suppressed_lints.as_slice())
+        .iter()
+        .map(Diagnostic::to_standard_json_error)
+        .collect::<Vec<_>>();
+    let has_errors = check_errors
+        .iter()
+        .any(|error| error.severity.as_str() == "error");
+    solc_output
+        .errors
+        .get_or_insert_with(Vec::new)
+        .extend(check_errors);
+    if has_errors {
+        return Ok(solc_output);
+    }
+
+    let build = project.compile(optimizer_settings, debug_config, None);
+    build.write_to_standard_json(&mut solc_output, &solc_version)?;
+    if selection.requires_codegen()
+        && let Err(error) = Project::ensure_codegen_available(
+            "The `evm.bytecode`, `evm.deployedBytecode` and `evm.assembly` output selection",
+        )
+    {
+        solc_output
+            .errors
+            .get_or_insert_with(Vec::new)
+            .push(to_standard_json_error(&error));
+    }
+
+    Ok(solc_output)
+}
+
+/// Converts the error into a standard JSON error, with the `qic` error code if it has one.
+fn to_standard_json_error(error: &anyhow::Error) -> SolcStandardJsonOutputError {
+    let mut standard_json_error = qi_compiler::build::standard_json_error(format!("{error:#}"));
+    standard_json_error.error_code = error
+        .downcast_ref::<qi_compiler::Error>()
+        .map(|error| error.code().to_owned());
+    standard_json_error
+}
+
+/// Compiles the contracts of the project, taking the unchanged ones from the `cache`.
+/// The contracts in `compiled_contracts` are skipped, and the compiled ones are added there.
+/// The errors of the contracts which failed to compile are reported.
+fn build(
+    mut project: Project,
+    optimizer_settings: OptimizerSettings,
+    debug_config: DebugConfig,
+    cache: Option<&Cache>,
+    compiled_contracts: &mut BTreeSet<String>,
+    reporter: &mut Reporter,
+) -> anyhow::Result<Build> {
+    project
+        .contracts
+        .retain(|path, _| compiled_contracts.insert(path.to_owned()));
+    let build = project.compile(optimizer_settings, debug_config, cache);
+
+    for (path, result) in build.results.iter() {
+        if let Err(error) = result {
+            reporter.report(Diagnostic::from_contract_error(path, error))?;
+        }
+    }
+    if build.has_errors() {
+        return Err(qi_compiler::Error::CompilationFailed.into());
+    }
+
+    Ok(build)
+}
+
+/// Adds the discovered remappings to the ones given on the command line, and checks that
+/// every import of the input files resolves. The unresolved imports are reported.
+/// Returns all remappings to pass to `solc` and the files of all source units.
+fn resolve_imports(
+    input_files: &[PathBuf],
+    remappings: Option<BTreeSet<String>>,
+    base_path: Option<&str>,
+    include_paths: &[String],
+    reporter: &mut Reporter,
+) -> anyhow::Result<Resolution> {
+    let explicit_remappings = remappings
+        .into_iter()
+        .flatten()
+        .map(|remapping| Remapping::from_str(remapping.as_str()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut remappings = Remapping::discover(Path::new(""))?
+        .into_iter()
+        .filter(|discovered| {
+            !explicit_remappings.iter().any(|explicit| {
+                explicit.context == discovered.context && explicit.prefix == discovered.prefix
+            })
+        })
+        .collect::<Vec<_>>();
+    remappings.extend(explicit_remappings);
+
+    let resolver = Resolver::new(
+        remappings.clone(),
+        base_path.map(PathBuf::from),
+        include_paths.iter().map(PathBuf::from).collect(),
+        std::env::current_dir()?.as_path(),
+    );
+    let mut resolution = resolver.resolve_all(input_files)?;
+    if !resolution.unresolved.is_empty() {
+        for unresolved in resolution.unresolved.iter() {
+            reporter.report(Diagnostic::from_unresolved_import(unresolved))?;
+        }
+        return Err(qi_compiler::Error::CompilationFailed.into());
+    }
+    resolution.remappings.extend(remappings);
+
+    Ok(resolution)
+}
+
+/// The compilation unit: the `solc` compiler and the input files compiled with it.
+type CompilationUnit = (Box<dyn Compiler>, Vec<PathBuf>);
+
+/// Groups the input files into compilation units by the newest installed `solc` satisfying
+/// the version pragmas of the file and of the files it imports, from the newest version.
+fn select_solc(
+    input_files: Vec<PathBuf>,
+    resolution: &Resolution,
+    arguments: &Arguments,
+) -> anyhow::Result<Vec<CompilationUnit>> {
+    let installations = Installation::discover(arguments.solc_directories.as_slice());
+    if installations.is_empty() {
+        anyhow::bail!(
+            "No supported `solc-<version>` executables found in ${{PATH}} or in the `--solc-dir` directories."
+        );
+    }
+
+    let resolver = Resolver::new(
+        resolution.remappings.iter().cloned().collect(),
+        arguments.base_path.as_ref().map(PathBuf::from),
+        arguments.include_paths.iter().map(PathBuf::from).collect(),
+        std::env::current_dir()?.as_path(),
+    );
+
+    let mut compilation_units: BTreeMap<semver::Version, (PathBuf, Vec<PathBuf>)> = BTreeMap::new();
+    for input_file in input_files.into_iter() {
+        let sources = resolver
+            .resolve_all(std::slice::from_ref(&input_file))?
+            .sources;
+        let mut pragmas = Vec::new();
+        for path in sources.values() {
+            let source_code = std::fs::read_to_string(path)
+                .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}"))?;
+            pragmas.extend(
+                VersionPragma::parse_all(source_code.as_str())
+                    .map_err(|error| anyhow::anyhow!("{path:?}: {error}"))?,
+            );
+        }
+
+        let installation = Installation::select(installations.as_slice(), pragmas.as_slice())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No installed `solc` satisfies the version pragmas of {input_file:?} and its imports: `{}`. Found versions: {}",
+                    pragmas.iter().map(ToString::to_string).collect::<Vec<_>>().join("`, `"),
+                    installations
+                        .iter()
+                        .map(|installation| installation.version.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            })?;
+        compilation_units
+            .entry(installation.version.to_owned())
+            .or_insert_with(|| (installation.executable.to_owned(), Vec::new()))
+            .1
+            .push(input_file);
+    }
+
+    compilation_units
+        .into_values()
+        .rev()
+        .map(|(executable, input_files)| {
+            let solc: Box<dyn Compiler> =
+                Box::new(SolcCompiler::new(executable.to_string_lossy().to_string())?);
+            Ok((solc, input_files))
+        })
+        .collect()
+}
+
+/// Creates the `solc` compiler given on the command line: the `soljson` build if specified,
+/// and the `solc` executable otherwise.
+fn solc(arguments: &Arguments) -> anyhow::Result<Box<dyn Compiler>> {
+    #[cfg(feature = "soljson")]
+    if let Some(path) = arguments.soljson.clone() {
+        return Ok(Box::new(qi_compiler::SoljsonWasmCompiler::new(path)?));
+    }
+
+    Ok(Box::new(SolcCompiler::new(
+        arguments
+            .solc
+            .clone()
+            .unwrap_or_else(|| SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned()),
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+
+    use qi_compiler::solc::version::Version as SolcVersion;
+    use qi_compiler::{Compiler, DebugConfig};
+    use revive_solc_json_interface::combined_json::CombinedJson;
+    use revive_solc_json_interface::{SolcStandardJsonInput, SolcStandardJsonOutput};
+
+    use super::Arguments;
+
+    /// The Yul IR of the Foundry `Counter` contract, written in the form `solc` optimizes it to.
+    const COUNTER_YUL: &str = include_str!("../../tests/fixtures/Counter.yul");
+
+    /// The compiler outputting the `Counter` contract for every standard JSON input.
+    struct StubCompiler;
+
+    impl Compiler for StubCompiler {
+        fn standard_json(
+            &mut self,
+            _input: SolcStandardJsonInput,
+            _base_path: Option<String>,
+            _include_paths: Vec<String>,
+            _allow_paths: Option<String>,
+        ) -> anyhow::Result<SolcStandardJsonOutput> {
+            Ok(serde_json::from_value(serde_json::json!({
+                "contracts": {
+                    "src/Counter.sol": {
+                        "Counter": {
+                            "abi": [{ "type": "function", "name": "increment", "inputs": [], "outputs": [], "stateMutability": "nonpayable" }],
+                            "metadata": "{\"compiler\":{\"version\":\"0.8.28\"}}",
+                            "evm": { "methodIdentifiers": { "increment()": "d09de08a" } },
+                            "irOptimized": COUNTER_YUL,
+                        },
+                    },
+                },
+                "sources": { "src/Counter.sol": { "id": 0 } },
+            }))?)
+        }
+
+        fn combined_json(
+            &self,
+            _paths: &[PathBuf],
+            _argument: &str,
+        ) -> anyhow::Result<CombinedJson> {
+            unimplemented!()
+        }
+
+        fn validate_yul(&self, _path: &Path) -> anyhow::Result<()> {
+            unimplemented!()
+        }
+
+        fn version(&mut self) -> anyhow::Result<SolcVersion> {
+            Ok(SolcVersion::new_simple(semver::Version::new(0, 8, 28)))
+        }
+
+        fn take_ir_optimized_asts(&mut self) -> BTreeMap<String, serde_json::Value> {
+            BTreeMap::new()
+        }
+    }
+
+    /// Compiles the `Counter` contract with the output selection `flags` through the standard JSON mode.
+    fn compile_counter(flags: &[&str]) -> serde_json::Value {
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": {
+                "src/Counter.sol": { "content": include_str!("../../tests/fixtures/Counter.sol") },
+            },
+            "settings": {
+                "optimizer": { "enabled": true },
+                "outputSelection": { "*": { "*": flags } },
+            },
+        });
+        let arguments = <Arguments as clap::Parser>::parse_from(["qic", "--standard-json"]);
+        let output = super::compile_standard_json(
+            serde_json::to_vec(&input).expect("Always valid").as_slice(),
+            &mut StubCompiler,
+            arguments,
+            DebugConfig::new(None, false),
+        )
+        .expect("Always valid");
+        serde_json::to_value(output).expect("Always valid")
+    }
+
+    /// Returns the codes of the standard JSON errors.
+    fn error_codes(output: &serde_json::Value) -> Vec<String> {
+        output["errors"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|error| error["severity"] == "error")
+            .map(|error| error["errorCode"].as_str().unwrap_or_default().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn outputs_the_artifacts_without_code() {
+        let output = compile_counter(&["abi", "metadata", "evm.methodIdentifiers"]);
+        let contract = &output["contracts"]["src/Counter.sol"]["Counter"];
+        assert_eq!(contract["abi"][0]["name"], "increment");
+        assert_eq!(
+            contract["evm"]["methodIdentifiers"]["increment()"],
+            "d09de08a"
+        );
+        assert!(!contract["metadata"].is_null());
+        assert!(contract["evm"]
+            .get("bytecode")
+            .is_none_or(|bytecode| bytecode.is_null()));
+        assert!(error_codes(&output).is_empty(), "{output}");
+    }
+
+    #[test]
+    fn reports_the_unavailable_bytecode() {
+        let output = compile_counter(&["abi", "evm.bytecode"]);
+        let contract = &output["contracts"]["src/Counter.sol"]["Counter"];
+        assert_eq!(contract["abi"][0]["name"], "increment");
+        assert!(contract["evm"]
+            .get("bytecode")
+            .is_none_or(|bytecode| bytecode.is_null()));
+        assert_eq!(error_codes(&output), vec!["Q0010"]);
+    }
+}
@@ -0,0 +1,58 @@
+//! The diagnostics reporter, printing to stderr in the `--error-format` format.
+
+use std::io::Write;
+
+use qi_compiler::diagnostic::sarif;
+use qi_compiler::diagnostic::ErrorFormat;
+use qi_compiler::Diagnostic;
+
+/// The diagnostics reporter.
+#[derive(Debug, Default)]
+pub struct Reporter {
+    /// The output format.
+    pub format: ErrorFormat,
+    /// The diagnostics kept until the end of the compilation, for the formats printed at once.
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Reporter {
+    /// Reports the diagnostic.
+    /// The human-readable and JSON diagnostics are printed immediately.
+    pub fn report(&mut self, diagnostic: Diagnostic) -> anyhow::Result<()> {
+        match self.format {
+            ErrorFormat::Human => writeln!(std::io::stderr(), "{diagnostic}")?,
+            ErrorFormat::Json => writeln!(
+                std::io::stderr(),
+                "{}",
+                serde_json::to_string(&diagnostic).expect("Always valid")
+            )?,
+            ErrorFormat::Sarif => self.diagnostics.push(diagnostic),
+        }
+        Ok(())
+    }
+
+    /// Reports the error which has aborted the compilation.
+    /// In the machine-readable formats, the summary error following the reported diagnostics is skipped.
+    pub fn report_error(&mut self, error: &anyhow::Error) -> anyhow::Result<()> {
+        let is_summary = matches!(
+            error.downcast_ref::<qi_compiler::Error>(),
+            Some(qi_compiler::Error::CompilationFailed)
+        );
+        if is_summary && self.format != ErrorFormat::Human {
+            return Ok(());
+        }
+        self.report(Diagnostic::from_error(error))
+    }
+
+    /// Prints the diagnostics kept until the end of the compilation.
+    pub fn finish(self) -> anyhow::Result<()> {
+        if let ErrorFormat::Sarif = self.format {
+            serde_json::to_writer_pretty(
+                std::io::stderr(),
+                &sarif::to_sarif(self.diagnostics.as_slice()),
+            )?;
+            writeln!(std::io::stderr())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+//! The embeddable library compilation entry point.
+//! Unlike the command line, it reports everything in the output and never writes to stdout or stderr.
+
+pub mod output;
+pub mod request;
+
+use std::collections::BTreeMap;
+
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonInputLanguage;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsOptimizer;
+use revive_solc_json_interface::SolcStandardJsonInputSource;
+
+use crate::build::standard_json_error;
+use crate::diagnostic::Severity;
+use crate::project::Project;
+use crate::solc::solc_compiler::SolcCompiler;
+use crate::solc::Compiler;
+use crate::yul::lint::Lint;
+
+use self::output::CompileOutput;
+use self::request::CompileRequest;
+
+/// Compiles the request with the `solc` executable found in `${PATH}`.
+pub fn compile(request: CompileRequest) -> anyhow::Result<CompileOutput> {
+    let mut solc = SolcCompiler::new(SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned())?;
+    compile_with(request, &mut solc)
+}
+
+/// Compiles the request with the given `solc` compiler.
+/// The `solc` diagnostics and the contract compilation failures are returned in the output,
+/// while the errors preventing the compilation altogether, such as unreadable sources, are returned as `Err`.
+/// Until the QiVM code generator is available, it fails with `Error::CodegenUnavailable` before running `solc`.
+pub fn compile_with(
+    request: CompileRequest,
+    solc: &mut dyn Compiler,
+) -> anyhow::Result<CompileOutput> {
+    Project::ensure_codegen_available("The library compilation")?;
+
+    let solc_version = solc.version()?;
+
+    let remappings = Some(request.remappings).filter(|remappings| !remappings.is_empty());
+    let mut suppressed_lints = request.suppressed_lints;
+    suppressed_lints.extend(
+        request
+            .suppressed_warnings
+            .iter()
+            .filter_map(Lint::from_resolc_warning),
+    );
+    let suppressed_warnings =
+        Some(request.suppressed_warnings).filter(|warnings| !warnings.is_empty());
+    let mut input = SolcStandardJsonInput::try_from_paths(
+        SolcStandardJsonInputLanguage::Solidity,
+        None,
+        &[],
+        request.libraries,
+        remappings,
+        request.selection.solc_output_selection(),
+        SolcStandardJsonInputSettingsOptimizer::new(
+            !request.disable_solc_optimizer,
+            None,
+            &solc_version.default,
+            request.optimizer_settings.is_fallback_to_size_enabled(),
+        ),
+        None,
+        suppressed_warnings,
+    )?;
+    let mut source_contents = BTreeMap::new();
+    for (name, source) in request.sources.iter() {
+        let contents = source.read()?;
+        input.sources.insert(
+            name.to_owned(),
+            SolcStandardJsonInputSource::from(contents.clone()),
+        );
+        source_contents.insert(name.to_owned(), contents);
+    }
+
+    let solc_output = solc.standard_json(
+        input,
+        request.base_path,
+        request.include_paths,
+        request.allow_paths,
+    )?;
+
+    let mut output = CompileOutput::new(solc_version.clone());
+    for error in solc_output.errors.iter().flatten() {
+        if error.severity.as_str() == "error" {
+            output.errors.push(error.to_owned());
+        } else {
+            output.warnings.push(error.to_owned());
+        }
+    }
+    if output.has_errors() {
+        return Ok(output);
+    }
+
+    let project = Project::try_from_standard_json_output(&solc_output, solc_version)?
+        .with_ir_optimized_asts(solc.take_ir_optimized_asts());
+    for diagnostic in project.check(&source_contents, suppressed_lints.as_slice()) {
+        let error = diagnostic.to_standard_json_error();
+        match diagnostic.severity {
+            Severity::Error => output.errors.push(error),
+            Severity::Warning | Severity::Info => output.warnings.push(error),
+        }
+    }
+    if output.has_errors() {
+        return Ok(output);
+    }
+
+    let build = project.compile(request.optimizer_settings, request.debug_config, None);
+    for (path, result) in build.results.into_iter() {
+        match result {
+            Ok(contract) => {
+                output.contracts.insert(path, contract);
+            }
+            Err(error) => output
+                .errors
+                .push(standard_json_error(format!("{path}: {error:#}"))),
+        }
+    }
+
+    Ok(output)
+}
@@ -0,0 +1,39 @@
+//! The library compilation output.
+
+use std::collections::BTreeMap;
+
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+use serde::Serialize;
+
+use crate::build::contract::Contract as ContractBuild;
+use crate::solc::version::Version as SolcVersion;
+
+/// The library compilation output.
+#[derive(Debug, Serialize, Clone)]
+pub struct CompileOutput {
+    /// The `solc` version used.
+    pub solc_version: SolcVersion,
+    /// The compiled contracts, keyed by the full contract path.
+    pub contracts: BTreeMap<String, ContractBuild>,
+    /// The warnings and other non-error diagnostics.
+    pub warnings: Vec<SolcStandardJsonOutputError>,
+    /// The `solc` errors and the errors of the contracts which failed to compile.
+    pub errors: Vec<SolcStandardJsonOutputError>,
+}
+
+impl CompileOutput {
+    /// A shortcut constructor.
+    pub fn new(solc_version: SolcVersion) -> Self {
+        Self {
+            solc_version,
+            contracts: BTreeMap::new(),
+            warnings: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Checks whether the compilation has failed.
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
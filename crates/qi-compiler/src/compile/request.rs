@@ -0,0 +1,139 @@
+//! The library compilation request.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use revive_solc_json_interface::ResolcWarning;
+
+use crate::build::selection::Selection;
+use crate::debug_config::DebugConfig;
+use crate::imports::provider::ProvidedSources;
+use crate::imports::provider::SourceProvider;
+use crate::imports::remapping::Remapping;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::yul::lint::Lint;
+
+/// The Solidity source unit contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// The file to read the contents from.
+    Path(PathBuf),
+    /// The in-memory contents.
+    Content(String),
+}
+
+impl Source {
+    /// Returns the source unit contents, reading the file if needed.
+    pub fn read(&self) -> anyhow::Result<String> {
+        match self {
+            Self::Path(path) => std::fs::read_to_string(path)
+                .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}")),
+            Self::Content(content) => Ok(content.to_owned()),
+        }
+    }
+}
+
+/// The library compilation request.
+#[derive(Debug, Clone)]
+pub struct CompileRequest {
+    /// The source units, keyed by their source unit names.
+    pub sources: BTreeMap<String, Source>,
+    /// The library addresses, in the `<path>:<name>=<address>` format.
+    pub libraries: Vec<String>,
+    /// The `solc` remappings, in the `[<context>:]<prefix>=<target>` format.
+    pub remappings: BTreeSet<String>,
+    /// The base path for the imports `solc` reads from the filesystem.
+    pub base_path: Option<String>,
+    /// The include paths for the imports `solc` reads from the filesystem.
+    pub include_paths: Vec<String>,
+    /// The comma-separated directories `solc` is allowed to read the imports from.
+    pub allow_paths: Option<String>,
+    /// The artifacts to produce.
+    pub selection: Selection,
+    /// The QiVM optimizer settings.
+    pub optimizer_settings: OptimizerSettings,
+    /// Whether the `solc` optimizer is disabled.
+    pub disable_solc_optimizer: bool,
+    /// The warnings to suppress.
+    /// The `extcodesize` and `sendtransfer` warnings suppress the corresponding QiVM lints as well.
+    pub suppressed_warnings: Vec<ResolcWarning>,
+    /// The QiVM compatibility lints to suppress.
+    pub suppressed_lints: Vec<Lint>,
+    /// The debug configuration.
+    pub debug_config: DebugConfig,
+}
+
+impl CompileRequest {
+    /// A shortcut constructor.
+    /// All artifacts are selected, and the default settings of the command line are used.
+    pub fn new(sources: BTreeMap<String, Source>) -> Self {
+        Self {
+            sources,
+            libraries: Vec::new(),
+            remappings: BTreeSet::new(),
+            base_path: None,
+            include_paths: Vec::new(),
+            allow_paths: None,
+            selection: Selection {
+                bin: true,
+                asm: true,
+                abi: true,
+                hashes: true,
+                metadata: true,
+                storage_layout: true,
+            },
+            optimizer_settings: OptimizerSettings::cycles(),
+            disable_solc_optimizer: false,
+            suppressed_warnings: Vec::new(),
+            suppressed_lints: Vec::new(),
+            debug_config: DebugConfig::default(),
+        }
+    }
+
+    /// Creates the request for the `entries` and the source units they import, all read from the `provider`.
+    /// The source units are passed to `solc` with their contents, so no imports are read from the filesystem.
+    pub fn try_from_provider(
+        provider: &dyn SourceProvider,
+        remappings: Vec<Remapping>,
+        entries: &[String],
+    ) -> anyhow::Result<Self> {
+        let sources = ProvidedSources::collect(provider, remappings.as_slice(), entries)?;
+        if !sources.unresolved.is_empty() {
+            anyhow::bail!(
+                "{}",
+                sources
+                    .unresolved
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+
+        let mut request = Self::new(
+            sources
+                .contents
+                .into_iter()
+                .map(|(name, contents)| (name, Source::Content(contents)))
+                .collect(),
+        );
+        request.remappings = remappings.iter().map(Remapping::to_string).collect();
+        Ok(request)
+    }
+
+    /// A shortcut constructor for the files named after their paths, like on the command line.
+    pub fn from_paths(paths: &[PathBuf]) -> Self {
+        Self::new(
+            paths
+                .iter()
+                .map(|path| {
+                    (
+                        path.to_string_lossy().to_string(),
+                        Source::Path(path.to_owned()),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,115 @@
+//! The project configuration file.
+
+pub mod profile;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use self::profile::Profile;
+
+/// The project configuration, read from `qic.toml` at the project root.
+/// Relative paths are interpreted relative to the project root.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// The Solidity source files.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<PathBuf>,
+    /// The `solc` base path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<PathBuf>,
+    /// The `solc` include paths.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include_paths: Vec<PathBuf>,
+    /// The `solc` allowed paths.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allow_paths: Vec<PathBuf>,
+    /// The import remappings, in the `<prefix>=<target>` format.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub remappings: Vec<String>,
+    /// The deployed library addresses, in the `<path>:<name>=<address>` format.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub libraries: Vec<String>,
+    /// The suppressed warnings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suppress_warnings: Vec<String>,
+    /// The build profiles, keyed by name.
+    #[serde(rename = "profile", skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Config {
+    /// The configuration file name.
+    pub const FILE_NAME: &'static str = "qic.toml";
+
+    /// Reads the configuration file at `path`.
+    pub fn try_from_path(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}"))?;
+        toml::from_str(contents.as_str())
+            .map_err(|error| anyhow::anyhow!("File {path:?} parsing error: {error}"))
+    }
+
+    /// Looks for the configuration file in `directory` and its ancestors.
+    /// Returns the project root relative to `directory`, and the configuration found there.
+    pub fn discover(directory: &Path) -> anyhow::Result<Option<(PathBuf, Self)>> {
+        let mut root = PathBuf::new();
+        for ancestor in directory.ancestors() {
+            let path = ancestor.join(Self::FILE_NAME);
+            if path.is_file() {
+                return Ok(Some((root, Self::try_from_path(path.as_path())?)));
+            }
+            root.push("..");
+        }
+        Ok(None)
+    }
+
+    /// Makes the relative paths relative to the working directory, given the project `root` relative to it.
+    /// The remapping prefixes and library addresses are kept as is.
+    pub fn resolve_paths(&mut self, root: &Path) {
+        if root.as_os_str().is_empty() {
+            return;
+        }
+
+        for path in self
+            .sources
+            .iter_mut()
+            .chain(self.base_path.iter_mut())
+            .chain(self.include_paths.iter_mut())
+            .chain(self.allow_paths.iter_mut())
+        {
+            if path.is_relative() {
+                *path = root.join(path.as_path());
+            }
+        }
+
+        for remapping in self.remappings.iter_mut() {
+            if let Some((prefix, target)) = remapping.split_once('=')
+                && Path::new(target).is_relative()
+            {
+                *remapping = format!("{prefix}={}", root.join(target).to_string_lossy());
+            }
+        }
+    }
+
+    /// Returns the profile `name`, with the file settings applied over the built-in defaults.
+    pub fn profile(&self, name: &str) -> anyhow::Result<Profile> {
+        let builtin = Profile::builtin(name);
+        match (builtin, self.profiles.get(name)) {
+            (Some(builtin), Some(profile)) => Ok(builtin.merge(profile)),
+            (Some(builtin), None) => Ok(builtin),
+            (None, Some(profile)) => Ok(profile.to_owned()),
+            (None, None) => anyhow::bail!("Profile `{name}` is not defined"),
+        }
+    }
+
+    /// Serializes the configuration to TOML.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        toml::to_string_pretty(self)
+            .map_err(|error| anyhow::anyhow!("Configuration serializing error: {error}"))
+    }
+}
@@ -0,0 +1,59 @@
+//! The build profile.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The build profile, defined in the `[profile.<name>]` section of the configuration file.
+/// The unset fields fall back to the command line defaults.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Profile {
+    /// The optimization level, as in the `-O` option.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimization: Option<char>,
+    /// Whether to recompile with `-Oz` if the bytecode is too large.
+    #[serde(rename = "fallback-Oz", skip_serializing_if = "Option::is_none")]
+    pub fallback_to_optimizing_for_size: Option<bool>,
+    /// Whether to emit the source debug information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug_info: Option<bool>,
+    /// Whether to disable the `solc` optimizer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_solc_optimizer: Option<bool>,
+}
+
+impl Profile {
+    /// The built-in development profile name.
+    pub const DEV: &'static str = "dev";
+    /// The built-in release profile name.
+    pub const RELEASE: &'static str = "release";
+
+    /// Returns the built-in profile `name`, if there is one.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            Self::DEV => Some(Self {
+                optimization: Some('1'),
+                debug_info: Some(true),
+                ..Self::default()
+            }),
+            Self::RELEASE => Some(Self {
+                optimization: Some('3'),
+                fallback_to_optimizing_for_size: Some(true),
+                ..Self::default()
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the profile with the fields set in `other` taking precedence.
+    pub fn merge(self, other: &Self) -> Self {
+        Self {
+            optimization: other.optimization.or(self.optimization),
+            fallback_to_optimizing_for_size: other
+                .fallback_to_optimizing_for_size
+                .or(self.fallback_to_optimizing_for_size),
+            debug_info: other.debug_info.or(self.debug_info),
+            disable_solc_optimizer: other.disable_solc_optimizer.or(self.disable_solc_optimizer),
+        }
+    }
+}
@@ -6,6 +6,8 @@
 pub enum IRType {
     /// Whether to dump the Yul code.
     Yul,
+    /// Whether to dump the Yul SSA IR.
+    SSA,
     /// Whether to dump the LLVM IR code.
     LLVM,
     /// Whether to dump the assembly code.
@@ -22,6 +24,7 @@ impl IRType {
     pub fn file_extension(&self) -> &'static str {
         match self {
             Self::Yul => revive_common::EXTENSION_YUL,
+            Self::SSA => "ssa",
             Self::LLVM => revive_common::EXTENSION_LLVM_SOURCE,
             Self::Assembly => revive_common::EXTENSION_POLKAVM_ASSEMBLY,
             #[cfg(debug_assertions)]
@@ -39,6 +39,18 @@ impl DebugConfig {
         Ok(())
     }
 
+    /// Dumps the Yul SSA IR.
+    pub fn dump_ssa(&self, contract_path: &str, code: &str) -> anyhow::Result<()> {
+        if let Some(output_directory) = self.output_directory.as_ref() {
+            let mut file_path = output_directory.to_owned();
+            let full_file_name = Self::full_file_name(contract_path, None, IRType::SSA);
+            file_path.push(full_file_name);
+            std::fs::write(file_path, code)?;
+        }
+
+        Ok(())
+    }
+
     /// Dumps the assembly.
     pub fn dump_assembly(&self, contract_path: &str, code: &str) -> anyhow::Result<()> {
         if let Some(output_directory) = self.output_directory.as_ref() {
@@ -82,7 +94,7 @@ impl DebugConfig {
     }
 
     /// Creates a full file name, given the contract full path, suffix, and extension.
-    fn full_file_name(contract_path: &str, suffix: Option<&str>, ir_type: IRType) -> String {
+    pub fn full_file_name(contract_path: &str, suffix: Option<&str>, ir_type: IRType) -> String {
         let mut full_file_name = contract_path.replace('/', "_").replace(':', ".");
         if let Some(suffix) = suffix {
             full_file_name.push('.');
@@ -0,0 +1,88 @@
+//! The line index of a source file.
+
+use super::Position;
+
+/// The line index, mapping the byte offsets to the lines and columns.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    /// The source code.
+    contents: &'a str,
+    /// The byte offsets of the line starts.
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// A shortcut constructor.
+    pub fn new(contents: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(contents.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+        Self {
+            contents,
+            line_starts,
+        }
+    }
+
+    /// Returns the position of the byte offset.
+    /// Offsets past the end or inside a character are clamped to the preceding character.
+    pub fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.contents.len());
+        while !self.contents.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1];
+        Position {
+            line,
+            column: self.contents[line_start..offset].chars().count() + 1,
+        }
+    }
+
+    /// Returns the text of the 1-based line, without the line terminator.
+    pub fn line(&self, line: usize) -> &'a str {
+        let Some(start) = line
+            .checked_sub(1)
+            .and_then(|index| self.line_starts.get(index))
+        else {
+            return "";
+        };
+        let end = self
+            .line_starts
+            .get(line)
+            .map(|end| end - 1)
+            .unwrap_or(self.contents.len());
+        self.contents[*start..end].trim_end_matches('\r')
+    }
+
+    /// Returns the number of lines.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineIndex;
+    use crate::diagnostic::Position;
+
+    #[test]
+    fn positions_count_code_points() {
+        let contents = "pragma solidity ^0.8.0;\r\n// ünïcode\ncontract C {}\n";
+        let index = LineIndex::new(contents);
+
+        assert_eq!(index.position(0), Position { line: 1, column: 1 });
+        assert_eq!(
+            index.position(contents.find("contract").unwrap()),
+            Position { line: 3, column: 1 }
+        );
+        assert_eq!(
+            index.position(contents.find("code").unwrap()),
+            Position { line: 2, column: 7 }
+        );
+        assert_eq!(index.position(usize::MAX), Position { line: 4, column: 1 });
+        assert_eq!(index.line(1), "pragma solidity ^0.8.0;");
+        assert_eq!(index.line(3), "contract C {}");
+        assert_eq!(index.line(4), "");
+        assert_eq!(index.line(5), "");
+    }
+}
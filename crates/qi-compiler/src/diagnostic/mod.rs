@@ -0,0 +1,383 @@
+//! The compiler diagnostics.
+//! The `solc` errors, the QiVM warnings and the code generation errors are merged into one stream,
+//! which is printed as text, as JSON lines, or as a SARIF log.
+
+pub mod line_index;
+pub mod sarif;
+pub mod snippet;
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+use rowan::TextRange;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::imports::resolver::UnresolvedImport;
+use crate::yul::source_map::SourceMap;
+
+use self::line_index::LineIndex;
+use self::snippet::Snippet;
+
+/// The diagnostic severity.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The compilation cannot succeed.
+    Error,
+    /// The code compiles but is likely wrong.
+    Warning,
+    /// The informational message.
+    Info,
+}
+
+impl Severity {
+    /// Converts the `solc` standard JSON error severity.
+    pub fn from_solc(severity: &str) -> Self {
+        match severity {
+            "error" => Self::Error,
+            "warning" => Self::Warning,
+            _ => Self::Info,
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "Error"),
+            Self::Warning => write!(f, "Warning"),
+            Self::Info => write!(f, "Info"),
+        }
+    }
+}
+
+/// The 1-based line and column, counted in Unicode code points.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// The line number.
+    pub line: usize,
+    /// The column number.
+    pub column: usize,
+}
+
+/// The source code span.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// The start byte offset.
+    pub start: usize,
+    /// The end byte offset, exclusive.
+    pub end: usize,
+    /// The start position, if the source code is available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_position: Option<Position>,
+    /// The end position, if the source code is available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_position: Option<Position>,
+}
+
+impl Span {
+    /// A shortcut constructor.
+    /// The positions are only computed if the source code is given.
+    pub fn new(start: usize, end: usize, contents: Option<&str>) -> Self {
+        let index = contents.map(LineIndex::new);
+        Self {
+            start,
+            end,
+            start_position: index.as_ref().map(|index| index.position(start)),
+            end_position: index.as_ref().map(|index| index.position(end)),
+        }
+    }
+}
+
+/// The compiler diagnostic.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The severity.
+    pub severity: Severity,
+    /// The `solc` error code, or the `qi_compiler::Error` code such as `Q0001`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// The short message.
+    pub message: String,
+    /// The source unit name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// The source code span.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+    /// The message as it is printed in the human-readable format.
+    pub rendered: String,
+}
+
+impl Diagnostic {
+    /// Converts the `solc` standard JSON error.
+    /// The `contents` is the source code of the file the error points to, used to compute the positions.
+    pub fn from_solc(error: &SolcStandardJsonOutputError, contents: Option<&str>) -> Self {
+        let location = error.source_location.as_ref();
+        Self {
+            severity: Severity::from_solc(error.severity.as_str()),
+            code: error.error_code.clone(),
+            message: error.message.clone(),
+            file: location
+                .map(|location| location.file.clone())
+                .filter(|file| !file.is_empty()),
+            span: location
+                .filter(|location| location.start >= 0 && location.end >= location.start)
+                .map(|location| {
+                    Span::new(location.start as usize, location.end as usize, contents)
+                }),
+            rendered: error.formatted_message.clone(),
+        }
+    }
+
+    /// Creates the diagnostic for the range of the Yul code generated by `solc`.
+    /// The location is mapped back to the Solidity source with the `@src` annotations, if the source is
+    /// found in `sources`, and the Yul location is added as a note, like `solc` renders the secondary locations.
+    pub fn from_yul(
+        severity: Severity,
+        code: Option<&str>,
+        message: String,
+        yul_name: &str,
+        yul: &str,
+        range: TextRange,
+        sources: &BTreeMap<String, String>,
+    ) -> Self {
+        let header = match code {
+            Some(code) => format!("{severity} ({code}): {message}"),
+            None => format!("{severity}: {message}"),
+        };
+        let yul_snippet = Snippet::new(yul_name, yul, range.start().into(), range.end().into());
+
+        let location = SourceMap::new(yul).locate(range.start());
+        let source = location.as_ref().and_then(|location| {
+            let contents = sources.get(location.file.as_str())?;
+            Some((location, contents.as_str()))
+        });
+        let (file, span, rendered) = match source {
+            Some((location, contents)) => (
+                location.file.to_owned(),
+                Span::new(location.start, location.end, Some(contents)),
+                snippet::render(
+                    header.as_str(),
+                    Some(&Snippet::new(
+                        location.file.as_str(),
+                        contents,
+                        location.start,
+                        location.end,
+                    )),
+                    &[("In the generated Yul code.".to_owned(), yul_snippet)],
+                ),
+            ),
+            None => (
+                yul_name.to_owned(),
+                Span::new(yul_snippet.start, yul_snippet.end, Some(yul)),
+                snippet::render(header.as_str(), Some(&yul_snippet), &[]),
+            ),
+        };
+
+        Self {
+            severity,
+            code: code.map(str::to_owned),
+            message,
+            file: Some(file),
+            span: Some(span),
+            rendered,
+        }
+    }
+
+    /// Converts the code generation error of the contract at `path`, i.e. `<file>:<name>`.
+    pub fn from_contract_error(path: &str, error: &anyhow::Error) -> Self {
+        Self {
+            severity: Severity::Error,
+            code: error_code(error),
+            message: format!("{error:#}"),
+            file: Some(
+                path.rsplit_once(':')
+                    .map(|(file, _)| file)
+                    .unwrap_or(path)
+                    .to_owned(),
+            ),
+            span: None,
+            rendered: format!("{path}: {error:#}"),
+        }
+    }
+
+    /// Converts the import which could not be resolved.
+    pub fn from_unresolved_import(unresolved: &UnresolvedImport) -> Self {
+        Self {
+            severity: Severity::Error,
+            code: None,
+            message: format!(
+                "Source \"{}\" not found, imported from \"{}\"",
+                unresolved.import, unresolved.source
+            ),
+            file: Some(unresolved.source.clone()),
+            span: None,
+            rendered: format!("Error: {unresolved}"),
+        }
+    }
+
+    /// Converts the error which has aborted the compilation.
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        let code = error_code(error);
+        let rendered = match code.as_deref() {
+            Some(code) => format!("error[{code}]: {error}"),
+            None => format!("{error}"),
+        };
+        Self {
+            severity: Severity::Error,
+            code,
+            message: format!("{error:#}"),
+            file: None,
+            span: None,
+            rendered,
+        }
+    }
+
+    /// Converts the diagnostic to the `solc` standard JSON error, to be returned along with the `solc` ones.
+    pub fn to_standard_json_error(&self) -> SolcStandardJsonOutputError {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        };
+        let source_location = self.file.as_ref().zip(self.span).map(|(file, span)| {
+            serde_json::json!({
+                "file": file,
+                "start": span.start,
+                "end": span.end,
+            })
+        });
+        serde_json::from_value(serde_json::json!({
+            "component": "general",
+            "errorCode": self.code,
+            "formattedMessage": self.rendered,
+            "message": self.message,
+            "severity": severity,
+            "sourceLocation": source_location,
+            "type": self.severity.to_string(),
+        }))
+        .expect("Always valid")
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rendered)
+    }
+}
+
+/// The diagnostics output format.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// The messages as `solc` prints them.
+    #[default]
+    Human,
+    /// One JSON diagnostic per line.
+    Json,
+    /// The SARIF 2.1.0 log, printed when the compilation ends.
+    Sarif,
+}
+
+impl FromStr for ErrorFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            string => anyhow::bail!(
+                "Invalid error format `{string}`: expected `human`, `json` or `sarif`"
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Human => write!(f, "human"),
+            Self::Json => write!(f, "json"),
+            Self::Sarif => write!(f, "sarif"),
+        }
+    }
+}
+
+/// Returns the code of the `qi_compiler::Error` wrapped into the error, if any.
+fn error_code(error: &anyhow::Error) -> Option<String> {
+    error
+        .downcast_ref::<crate::Error>()
+        .map(|error| error.code().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rowan::TextRange;
+    use rowan::TextSize;
+
+    use super::Diagnostic;
+    use super::Position;
+    use super::Severity;
+
+    #[test]
+    fn maps_yul_back_to_solidity() {
+        let solidity = "contract A {\n    function f() public { selfdestruct(payable(0)); }\n}\n";
+        let start = solidity.find("selfdestruct").unwrap();
+        let yul = format!(
+            "/// @use-src 0:\"A.sol\"\nobject \"A_7\" {{\n    code {{\n        /// @src 0:{start}:{}\n        selfdestruct(0)\n    }}\n}}\n",
+            start + "selfdestruct(payable(0))".len()
+        );
+        let yul_start = yul.find("selfdestruct").unwrap() as u32;
+        let range = TextRange::at(TextSize::new(yul_start), TextSize::new(12));
+        let sources = BTreeMap::from([("A.sol".to_owned(), solidity.to_owned())]);
+
+        let diagnostic = Diagnostic::from_yul(
+            Severity::Warning,
+            None,
+            "Unsupported.".to_owned(),
+            "A_7.yul",
+            yul.as_str(),
+            range,
+            &sources,
+        );
+
+        assert_eq!(diagnostic.file.as_deref(), Some("A.sol"));
+        assert_eq!(
+            diagnostic.span.and_then(|span| span.start_position),
+            Some(Position {
+                line: 2,
+                column: 27
+            })
+        );
+        assert_eq!(
+            diagnostic.rendered,
+            "Warning: Unsupported.
+ --> A.sol:2:27:
+  |
+2 |     function f() public { selfdestruct(payable(0)); }
+  |                           ^^^^^^^^^^^^^^^^^^^^^^^^
+Note: In the generated Yul code.
+ --> A_7.yul:5:9:
+  |
+5 |         selfdestruct(0)
+  |         ^^^^^^^^^^^^"
+        );
+
+        let unmapped = Diagnostic::from_yul(
+            Severity::Error,
+            None,
+            "Failed.".to_owned(),
+            "A_7.yul",
+            yul.as_str(),
+            range,
+            &BTreeMap::new(),
+        );
+        assert_eq!(unmapped.file.as_deref(), Some("A_7.yul"));
+        assert!(!unmapped.rendered.contains("Note:"));
+    }
+}
@@ -0,0 +1,122 @@
+//! The SARIF 2.1.0 log, as consumed by GitHub code scanning.
+
+use std::collections::BTreeMap;
+
+use super::Diagnostic;
+use super::Severity;
+
+/// The SARIF schema URI.
+pub const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The SARIF version.
+pub const VERSION: &str = "2.1.0";
+
+/// Converts the diagnostics to a SARIF log with a single run of `qic`.
+/// The diagnostic codes become the rule IDs, with the `--explain` texts as the rule descriptions.
+pub fn to_sarif(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let rules = diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.code.as_deref())
+        .map(|code| {
+            let mut rule = serde_json::json!({ "id": code });
+            if let Some(explanation) = crate::error::explanation::explain(code) {
+                let (summary, _) = explanation.split_once('\n').unwrap_or((explanation, ""));
+                rule["shortDescription"] = serde_json::json!({ "text": summary });
+                rule["fullDescription"] = serde_json::json!({ "text": explanation });
+            }
+            (code, rule)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let results = diagnostics.iter().map(result).collect::<Vec<_>>();
+
+    serde_json::json!({
+        "$schema": SCHEMA,
+        "version": VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "qic",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.into_values().collect::<Vec<_>>(),
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+}
+
+/// Converts the diagnostic to a SARIF result.
+fn result(diagnostic: &Diagnostic) -> serde_json::Value {
+    let level = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    };
+    let mut result = serde_json::json!({
+        "level": level,
+        "message": { "text": diagnostic.message },
+    });
+    if let Some(code) = diagnostic.code.as_deref() {
+        result["ruleId"] = serde_json::json!(code);
+    }
+
+    if let Some(file) = diagnostic.file.as_deref() {
+        let mut physical_location = serde_json::json!({
+            "artifactLocation": { "uri": file },
+        });
+        if let Some(span) = diagnostic.span.as_ref() {
+            let mut region = serde_json::json!({
+                "byteOffset": span.start,
+                "byteLength": span.end - span.start,
+            });
+            if let (Some(start), Some(end)) = (span.start_position, span.end_position) {
+                region["startLine"] = serde_json::json!(start.line);
+                region["startColumn"] = serde_json::json!(start.column);
+                region["endLine"] = serde_json::json!(end.line);
+                region["endColumn"] = serde_json::json!(end.column);
+            }
+            physical_location["region"] = region;
+        }
+        result["locations"] = serde_json::json!([{ "physicalLocation": physical_location }]);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostic::Diagnostic;
+    use crate::diagnostic::Severity;
+    use crate::diagnostic::Span;
+
+    #[test]
+    fn converts_locations_and_rules() {
+        let contents = "contract C {\n    uint x\n}\n";
+        let start = contents.find("uint").unwrap();
+        let diagnostics = [
+            Diagnostic {
+                severity: Severity::Error,
+                code: Some("2314".to_owned()),
+                message: "Expected ';' but got '}'".to_owned(),
+                file: Some("C.sol".to_owned()),
+                span: Some(Span::new(start, start + 6, Some(contents))),
+                rendered: String::new(),
+            },
+            Diagnostic::from_error(&crate::Error::invalid_arguments("Invalid").into()),
+        ];
+        let log = super::to_sarif(&diagnostics);
+        let run = &log["runs"][0];
+
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "Q0008");
+        assert_eq!(run["results"][0]["ruleId"], "2314");
+        let region = &run["results"][0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 2);
+        assert_eq!(region["startColumn"], 5);
+        assert_eq!(region["endColumn"], 11);
+        assert_eq!(region["byteLength"], 6);
+        assert!(run["results"][1].get("locations").is_none());
+    }
+}
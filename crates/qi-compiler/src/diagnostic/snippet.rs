@@ -0,0 +1,124 @@
+//! The source code excerpts, rendered in the style of the `solc` formatted messages.
+
+use std::fmt::Write;
+
+use super::line_index::LineIndex;
+
+/// The source code span to excerpt.
+#[derive(Debug, Clone, Copy)]
+pub struct Snippet<'a> {
+    /// The file name, as it is printed.
+    pub file: &'a str,
+    /// The file contents.
+    pub contents: &'a str,
+    /// The start byte offset.
+    pub start: usize,
+    /// The end byte offset, exclusive.
+    pub end: usize,
+}
+
+impl<'a> Snippet<'a> {
+    /// A shortcut constructor.
+    pub fn new(file: &'a str, contents: &'a str, start: usize, end: usize) -> Self {
+        Self {
+            file,
+            contents,
+            start,
+            end,
+        }
+    }
+}
+
+/// Renders the message with the primary excerpt and the secondary notes, e.g.
+/// ```text
+/// Warning: `selfdestruct` only transfers the balance on QiVM.
+///   --> contracts/Token.sol:12:9:
+///    |
+/// 12 |         selfdestruct(owner);
+///    |         ^^^^^^^^^^^^^^^^^^^
+/// Note: In the generated Yul code.
+///    --> Token.yul:345:13:
+///     |
+/// 345 |             selfdestruct(...)
+///     |             ^^^^^^^^^^^^^^^^^
+/// ```
+pub fn render(header: &str, primary: Option<&Snippet>, notes: &[(String, Snippet)]) -> String {
+    let mut rendered = header.to_owned();
+    if let Some(primary) = primary {
+        rendered.push('\n');
+        render_excerpt(&mut rendered, primary);
+    }
+    for (message, snippet) in notes.iter() {
+        write!(rendered, "\nNote: {message}\n").expect("Always valid");
+        render_excerpt(&mut rendered, snippet);
+    }
+    rendered
+}
+
+/// Renders the excerpt with the caret underline.
+/// The spans crossing lines are marked on their first line only, like `solc` does.
+fn render_excerpt(rendered: &mut String, snippet: &Snippet) {
+    let index = LineIndex::new(snippet.contents);
+    let start = index.position(snippet.start);
+    let end = index.position(snippet.end.max(snippet.start));
+    let width = start.line.to_string().len();
+    let gutter = " ".repeat(width + 1);
+
+    let text = index.line(start.line);
+    let prefix = text
+        .chars()
+        .take(start.column - 1)
+        .map(|char| if char == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+    let underline = if end.line > start.line {
+        "^ (Relevant source part starts here and spans across multiple lines).".to_owned()
+    } else {
+        "^".repeat(end.column.saturating_sub(start.column).max(1))
+    };
+
+    write!(
+        rendered,
+        "{} --> {}:{}:{}:\n{gutter}|\n{:>width$} | {text}\n{gutter}| {prefix}{underline}",
+        " ".repeat(width.saturating_sub(1)),
+        snippet.file,
+        start.line,
+        start.column,
+        start.line,
+    )
+    .expect("Always valid");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Snippet;
+
+    #[test]
+    fn renders_like_solc() {
+        let contents =
+            "contract C {\n\tfunction f() public {\n\t\tselfdestruct(payable(0));\n\t}\n}\n";
+        let start = contents.find("selfdestruct").unwrap();
+        let primary = Snippet::new("C.sol", contents, start, start + "selfdestruct".len());
+        let function = contents.find("function").unwrap();
+        let note = Snippet::new("C.sol", contents, function, contents.len());
+
+        let rendered = super::render(
+            "Warning: Unsupported.",
+            Some(&primary),
+            &[("The function is here.".to_owned(), note)],
+        );
+
+        assert_eq!(
+            rendered,
+            "Warning: Unsupported.
+ --> C.sol:3:3:
+  |
+3 | \t\tselfdestruct(payable(0));
+  | \t\t^^^^^^^^^^^^
+Note: The function is here.
+ --> C.sol:2:2:
+  |
+2 | \tfunction f() public {
+  | \t^ (Relevant source part starts here and spans across multiple lines)."
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! The detailed error code descriptions.
+
+/// Returns the detailed description of the error code, such as `Q0001`.
+pub fn explain(code: &str) -> Option<&'static str> {
+    let explanation = match code.to_ascii_uppercase().as_str() {
+        "Q0001" => {
+            r#"The `solc` executable could not be found.
+
+By default, `qic` runs `solc` from ${PATH}. Install `solc` or pass its path with
+`--solc <path>`. With `--auto-solc`, the `solc-<version>` executables are looked up
+in the `--solc-dir` directories and in ${PATH} instead."#
+        }
+        "Q0002" => {
+            r#"The `solc` subprocess could not be started, or it exited with a failure status.
+
+The message includes the `solc` stderr. Check that the executable is a working
+`solc` build, and that it accepts the arguments `qic` passes, such as `--base-path`,
+`--include-path` and `--allow-paths`."#
+        }
+        "Q0003" => {
+            r#"The `solc` output is not the expected JSON.
+
+This usually means that the executable is not `solc`, or that it has printed
+something besides the JSON output. The message includes the output received."#
+        }
+        "Q0004" => {
+            r#"The `solc --version` output could not be parsed.
+
+`qic` expects the version on the second line, such as
+`Version: 0.8.28+commit.7893614a.Linux.g++`."#
+        }
+        "Q0005" => {
+            r#"The `solc` version is not supported.
+
+Only the `solc` versions with the standard JSON interface and the Yul IR output `qic`
+relies on are supported. Install a supported version and pass it with `--solc`,
+or use `--auto-solc` to select one by the version pragmas."#
+        }
+        "Q0006" => {
+            r#"`--include-path` was given, but the `solc` version does not support it.
+
+The include paths were introduced in `solc` 0.8.8. Upgrade `solc`, or replace the
+include paths with remappings."#
+        }
+        "Q0007" => {
+            r#"The optimization level is not recognized.
+
+The supported levels are `0`, `1`, `2`, `3`, `s` and `z`, passed as `-O<level>`
+or as the `optimization` of a configuration file profile."#
+        }
+        "Q0008" => {
+            r#"The command line arguments are inconsistent.
+
+Some options cannot be combined, such as standard JSON and combined JSON modes,
+and some require others, such as `--solc-dir` requiring `--auto-solc`. In standard
+JSON mode, the settings must be given in the JSON input instead."#
+        }
+        "Q0009" => {
+            r#"The sources have errors.
+
+The errors found by `solc` or by the QiVM code generator have been printed before
+this one. Fix them and compile again."#
+        }
+        "Q0010" => {
+            r#"The output mode requires the QiVM bytecode, which cannot be generated yet.
+
+The QiVM code generator is not available in this version of `qic`. In standard JSON
+mode, the other artifacts are still output, and the error is reported if the
+`evm.bytecode`, `evm.deployedBytecode` or `evm.assembly` outputs are selected. The
+other modes producing the bytecode are rejected before compiling anything: the
+`bin`, `bin-runtime` and `asm` selectors of `--combined-json`, the `--bin` and
+`--asm` outputs of `--output-dir` and the library `compile` functions. The
+`--artifact-format` artifacts are written with the empty `0x` bytecode. Without
+these options, `qic` still reports the `solc` errors and the QiVM compatibility
+lints, and dumps the Yul IR with `--debug-output-dir`."#
+        }
+        "Q0101" => {
+            r#"The code checks whether an address is a contract with `extcodesize`.
+
+On QiVM, the code size of a contract is not the size of its EVM bytecode, and a
+contract under construction already has code. Checks such as
+`address.code.length > 0` are therefore not a reliable way to tell contracts from
+accounts. The checks `solc` generates before the external calls are not reported.
+Suppress with `--suppress-warnings extcodesize`."#
+        }
+        "Q0102" => {
+            r#"The code relies on the 2300 gas stipend of `transfer` and `send`.
+
+QiVM meters the execution differently from the EVM, so the stipend does not bound
+what the recipient can do, and it may not be enough to receive the value at all.
+Use `call` with the value and check the result instead, protecting against
+reentrancy explicitly. Suppress with `--suppress-warnings sendtransfer`."#
+        }
+        "Q0103" => {
+            r#"The code uses `msize`.
+
+The memory on QiVM is not grown in 32-byte words the way the EVM memory is, so the
+value of `msize` differs. Inline assembly relying on it, such as allocating at
+`msize()`, should use the free memory pointer instead.
+Suppress with `--suppress-warnings msize`."#
+        }
+        "Q0104" => {
+            r#"The code depends on the remaining gas.
+
+The gas costs on QiVM differ from the EVM costs, so the values of `gas()` and
+`gasleft()` do not match the EVM ones, and logic comparing them to constants
+may behave differently. Forwarding all the remaining gas to a call is not reported.
+Suppress with `--suppress-warnings gas`."#
+        }
+        "Q0105" => {
+            r#"The code uses `selfdestruct`.
+
+QiVM does not delete the contract code and storage, following the EVM semantics
+since the Cancun upgrade: only the balance is transferred to the beneficiary.
+Suppress with `--suppress-warnings selfdestruct`."#
+        }
+        _ => return None,
+    };
+    Some(explanation)
+}
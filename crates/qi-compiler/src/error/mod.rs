@@ -0,0 +1,204 @@
+//! The library errors.
+//! Every error has a stable code, which `qic --explain <code>` describes in detail.
+
+pub mod explanation;
+
+/// The library error.
+/// The functions return `anyhow::Result`, so the callers distinguish the errors with
+/// `error.downcast_ref::<qi_compiler::Error>()` and match on the variant or the code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The `solc` executable is not found.
+    SolcNotFound {
+        /// The executable name or path.
+        executable: String,
+        /// The lookup error.
+        reason: String,
+    },
+    /// The `solc` subprocess could not be run or has failed.
+    SolcFailed {
+        /// The executable name or path.
+        executable: String,
+        /// The failure description or the `solc` stderr.
+        message: String,
+    },
+    /// The `solc` output could not be parsed.
+    SolcOutputParsing {
+        /// The executable name or path.
+        executable: String,
+        /// The parsing error and the output.
+        message: String,
+    },
+    /// The `solc --version` output could not be parsed.
+    SolcVersionParsing {
+        /// The executable name or path.
+        executable: String,
+        /// The parsing error.
+        message: String,
+    },
+    /// The `solc` version is not supported.
+    UnsupportedSolcVersion {
+        /// The version found.
+        found: semver::Version,
+        /// The first supported version.
+        first_supported: semver::Version,
+        /// The last supported version.
+        last_supported: semver::Version,
+    },
+    /// The `solc` version does not support `--include-path`.
+    IncludePathUnsupported {
+        /// The version found.
+        found: semver::Version,
+    },
+    /// The optimization level is not recognized.
+    InvalidOptimizationLevel {
+        /// The level given.
+        level: char,
+    },
+    /// The command line arguments are inconsistent.
+    InvalidArguments {
+        /// The description of the conflict.
+        message: String,
+    },
+    /// The sources have errors, which have been reported separately.
+    CompilationFailed,
+    /// The output mode requires the QiVM bytecode, which this build cannot generate.
+    CodegenUnavailable {
+        /// The output mode requested.
+        mode: String,
+    },
+}
+
+impl Error {
+    /// Returns the stable error code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::SolcNotFound { .. } => "Q0001",
+            Self::SolcFailed { .. } => "Q0002",
+            Self::SolcOutputParsing { .. } => "Q0003",
+            Self::SolcVersionParsing { .. } => "Q0004",
+            Self::UnsupportedSolcVersion { .. } => "Q0005",
+            Self::IncludePathUnsupported { .. } => "Q0006",
+            Self::InvalidOptimizationLevel { .. } => "Q0007",
+            Self::InvalidArguments { .. } => "Q0008",
+            Self::CompilationFailed => "Q0009",
+            Self::CodegenUnavailable { .. } => "Q0010",
+        }
+    }
+
+    /// A shortcut constructor.
+    pub fn invalid_arguments(message: impl Into<String>) -> Self {
+        Self::InvalidArguments {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SolcNotFound { executable, reason } => {
+                write!(
+                    f,
+                    "The `{executable}` executable not found in ${{PATH}}: {reason}"
+                )
+            }
+            Self::SolcFailed {
+                executable,
+                message,
+            } => write!(f, "{executable} error: {message}"),
+            Self::SolcOutputParsing {
+                executable,
+                message,
+            } => write!(f, "{executable} subprocess output parsing error: {message}"),
+            Self::SolcVersionParsing {
+                executable,
+                message,
+            } => write!(f, "{executable} version parsing: {message}"),
+            Self::UnsupportedSolcVersion {
+                found,
+                first_supported,
+                ..
+            } if found < first_supported => write!(
+                f,
+                "`solc` versions <{first_supported} are not supported, found {found}"
+            ),
+            Self::UnsupportedSolcVersion {
+                found,
+                last_supported,
+                ..
+            } => write!(
+                f,
+                "`solc` versions >{last_supported} are not supported, found {found}"
+            ),
+            Self::IncludePathUnsupported { found } => {
+                write!(f, "--include-path is not supported in solc {found}")
+            }
+            Self::InvalidOptimizationLevel { level } => {
+                write!(f, "Unexpected optimization option '{level}'")
+            }
+            Self::InvalidArguments { message } => write!(f, "{message}"),
+            Self::CompilationFailed => write!(f, "Error(s) found. Compilation aborted"),
+            Self::CodegenUnavailable { mode } => write!(
+                f,
+                "{mode} is not supported: the QiVM code generator is not available yet"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn every_code_is_explained() {
+        let errors = [
+            Error::SolcNotFound {
+                executable: "solc".to_owned(),
+                reason: String::new(),
+            },
+            Error::SolcFailed {
+                executable: "solc".to_owned(),
+                message: String::new(),
+            },
+            Error::SolcOutputParsing {
+                executable: "solc".to_owned(),
+                message: String::new(),
+            },
+            Error::SolcVersionParsing {
+                executable: "solc".to_owned(),
+                message: String::new(),
+            },
+            Error::UnsupportedSolcVersion {
+                found: semver::Version::new(0, 4, 0),
+                first_supported: semver::Version::new(0, 8, 0),
+                last_supported: semver::Version::new(0, 8, 30),
+            },
+            Error::IncludePathUnsupported {
+                found: semver::Version::new(0, 8, 0),
+            },
+            Error::InvalidOptimizationLevel { level: 'x' },
+            Error::invalid_arguments(""),
+            Error::CompilationFailed,
+            Error::CodegenUnavailable {
+                mode: String::new(),
+            },
+        ];
+        for error in errors {
+            assert!(super::explanation::explain(error.code()).is_some());
+        }
+        assert!(super::explanation::explain("Q9999").is_none());
+    }
+
+    #[test]
+    fn downcasts_from_anyhow() {
+        let error: anyhow::Error = Error::CompilationFailed.into();
+        assert_eq!(
+            error.downcast_ref::<Error>().map(Error::code),
+            Some("Q0009")
+        );
+    }
+}
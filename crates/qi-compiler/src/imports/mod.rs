@@ -0,0 +1,125 @@
+//! The Solidity import resolution.
+
+pub mod provider;
+pub mod remapping;
+pub mod resolver;
+
+use std::ops::Range;
+
+/// Extracts the import paths from the Solidity source code.
+/// Only the import directives are recognized, so the source code does not need to be valid otherwise.
+pub fn parse_imports(source_code: &str) -> Vec<String> {
+    let mut imports = Vec::new();
+    let mut tokens = tokenize(source_code).into_iter();
+    while let Some((token, _)) = tokens.next() {
+        if token != Token::Word("import".to_owned()) {
+            continue;
+        }
+        for (token, _) in tokens.by_ref() {
+            match token {
+                Token::String(path) => {
+                    imports.push(path);
+                    break;
+                }
+                Token::Semicolon => break,
+                _ => continue,
+            }
+        }
+    }
+    imports
+}
+
+/// The token of the Solidity directive scanner.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// An identifier or keyword.
+    Word(String),
+    /// A string literal, without quotes.
+    String(String),
+    /// The `;` character.
+    Semicolon,
+    /// Any other character.
+    Other,
+}
+
+/// Splits the source code into the tokens relevant to the directives, skipping comments.
+/// Every token comes with its byte range in the source code.
+pub(crate) fn tokenize(source_code: &str) -> Vec<(Token, Range<usize>)> {
+    let is_identifier_char =
+        |char: char| char.is_ascii_alphanumeric() || char == '_' || char == '$';
+
+    let mut tokens = Vec::new();
+    let mut chars = source_code.char_indices().peekable();
+    while let Some((start, char)) = chars.next() {
+        let token = match char {
+            '"' | '\'' => {
+                let mut literal = String::new();
+                while let Some((_, next)) = chars.next() {
+                    match next {
+                        '\\' => literal.extend(chars.next().map(|(_, escaped)| escaped)),
+                        next if next == char || next == '\n' => break,
+                        next => literal.push(next),
+                    }
+                }
+                Token::String(literal)
+            }
+            '/' if chars.next_if(|(_, next)| *next == '/').is_some() => {
+                for (_, next) in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            '/' if chars.next_if(|(_, next)| *next == '*').is_some() => {
+                let mut previous = ' ';
+                for (_, next) in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+                continue;
+            }
+            ';' => Token::Semicolon,
+            char if is_identifier_char(char) => {
+                let mut word = String::from(char);
+                while let Some((_, next)) = chars.next_if(|(_, next)| is_identifier_char(*next)) {
+                    word.push(next);
+                }
+                Token::Word(word)
+            }
+            char if char.is_whitespace() => continue,
+            _ => Token::Other,
+        };
+        let end = chars
+            .peek()
+            .map(|(index, _)| *index)
+            .unwrap_or(source_code.len());
+        tokens.push((token, start..end));
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_imports;
+
+    #[test]
+    fn parses_import_directives() {
+        let source_code = r#"
+            // import "commented.sol";
+            /* import "block.sol"; */
+            import "./A.sol";
+            import {B as C} from '@oz/contracts/B.sol';
+            import * as D from "lib/D.sol";
+            import "E.sol" as E;
+            contract important { string s = "import \"x.sol\";"; }
+        "#;
+
+        assert_eq!(
+            parse_imports(source_code),
+            vec!["./A.sol", "@oz/contracts/B.sol", "lib/D.sol", "E.sol"]
+        );
+    }
+}
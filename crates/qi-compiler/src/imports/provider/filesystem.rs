@@ -0,0 +1,37 @@
+//! The filesystem source provider.
+
+use std::path::PathBuf;
+
+use super::SourceProvider;
+
+/// The source provider reading the files on disk.
+/// Like in `solc`, the source unit names are looked up in the base path and then in the include paths.
+#[derive(Debug, Clone, Default)]
+pub struct FilesystemProvider {
+    /// The directories the source unit names are looked up in.
+    roots: Vec<PathBuf>,
+}
+
+impl FilesystemProvider {
+    /// A shortcut constructor.
+    /// The base path defaults to the working directory.
+    pub fn new(base_path: Option<PathBuf>, include_paths: Vec<PathBuf>) -> Self {
+        let mut roots = vec![base_path.unwrap_or_default()];
+        roots.extend(include_paths);
+        Self { roots }
+    }
+}
+
+impl SourceProvider for FilesystemProvider {
+    fn read(&self, source_unit_name: &str) -> anyhow::Result<Option<String>> {
+        for root in self.roots.iter() {
+            let path = root.join(source_unit_name);
+            if path.is_file() {
+                return std::fs::read_to_string(path.as_path())
+                    .map(Some)
+                    .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}"));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,32 @@
+//! The in-memory source provider.
+
+use std::collections::BTreeMap;
+
+use super::SourceProvider;
+
+/// The source provider serving the contents kept in memory, such as unsaved editor buffers
+/// or contracts declared inline in tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryProvider {
+    /// The source unit contents, keyed by their source unit names.
+    pub sources: BTreeMap<String, String>,
+}
+
+impl MemoryProvider {
+    /// A shortcut constructor.
+    pub fn new(sources: BTreeMap<String, String>) -> Self {
+        Self { sources }
+    }
+
+    /// Adds or replaces the source unit.
+    pub fn insert(&mut self, source_unit_name: impl Into<String>, contents: impl Into<String>) {
+        self.sources
+            .insert(source_unit_name.into(), contents.into());
+    }
+}
+
+impl SourceProvider for MemoryProvider {
+    fn read(&self, source_unit_name: &str) -> anyhow::Result<Option<String>> {
+        Ok(self.sources.get(source_unit_name).cloned())
+    }
+}
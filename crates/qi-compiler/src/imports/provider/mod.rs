@@ -0,0 +1,129 @@
+//! The Solidity source providers.
+//! The imports are resolved against the provider, so the standard JSON input can be built with
+//! the `content` of every source unit, and `solc` never reads the filesystem itself.
+
+pub mod filesystem;
+pub mod memory;
+pub mod overlay;
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use revive_solc_json_interface::SolcStandardJsonInputSource;
+
+use super::remapping::Remapping;
+use super::resolver::UnresolvedImport;
+
+/// The source of the Solidity source unit contents.
+pub trait SourceProvider {
+    /// Returns the source unit contents, or `None` if the provider does not have it.
+    fn read(&self, source_unit_name: &str) -> anyhow::Result<Option<String>>;
+}
+
+impl<T: SourceProvider + ?Sized> SourceProvider for Box<T> {
+    fn read(&self, source_unit_name: &str) -> anyhow::Result<Option<String>> {
+        self.as_ref().read(source_unit_name)
+    }
+}
+
+/// The source units collected from a provider.
+#[derive(Debug, Default, Clone)]
+pub struct ProvidedSources {
+    /// The source unit contents, keyed by their source unit names.
+    pub contents: BTreeMap<String, String>,
+    /// The imports the provider does not have.
+    pub unresolved: Vec<UnresolvedImport>,
+}
+
+impl ProvidedSources {
+    /// Collects the `entries` and the source units they import, transitively.
+    /// The imports are resolved with the `remappings`, just like `solc` does before requesting them.
+    pub fn collect(
+        provider: &dyn SourceProvider,
+        remappings: &[Remapping],
+        entries: &[String],
+    ) -> anyhow::Result<Self> {
+        let mut sources = Self::default();
+        let mut queue = VecDeque::new();
+        for entry in entries.iter() {
+            let contents = provider
+                .read(entry.as_str())?
+                .ok_or_else(|| anyhow::anyhow!("Source \"{entry}\" not found"))?;
+            queue.push_back((entry.to_owned(), contents));
+        }
+
+        while let Some((source_unit_name, contents)) = queue.pop_front() {
+            if sources.contents.contains_key(source_unit_name.as_str()) {
+                continue;
+            }
+
+            for import in super::parse_imports(contents.as_str()).into_iter() {
+                let imported = super::resolver::source_unit_name(
+                    remappings,
+                    source_unit_name.as_str(),
+                    import.as_str(),
+                );
+                if sources.contents.contains_key(imported.as_str()) {
+                    continue;
+                }
+                match provider.read(imported.as_str())? {
+                    Some(imported_contents) => queue.push_back((imported, imported_contents)),
+                    None => sources.unresolved.push(UnresolvedImport {
+                        source: source_unit_name.clone(),
+                        import,
+                        candidates: vec![PathBuf::from(imported)],
+                    }),
+                }
+            }
+            sources.contents.insert(source_unit_name, contents);
+        }
+
+        Ok(sources)
+    }
+
+    /// Returns the standard JSON input `sources`, with the `content` of every source unit.
+    pub fn into_standard_json_sources(self) -> BTreeMap<String, SolcStandardJsonInputSource> {
+        self.contents
+            .into_iter()
+            .map(|(name, contents)| (name, SolcStandardJsonInputSource::from(contents)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::memory::MemoryProvider;
+    use super::overlay::OverlayProvider;
+    use super::ProvidedSources;
+    use crate::imports::remapping::Remapping;
+
+    #[test]
+    fn collects_imports_from_overlay() {
+        let mut disk = MemoryProvider::default();
+        disk.insert(
+            "src/Token.sol",
+            r#"import "./Base.sol"; import "@oz/Ownable.sol";"#,
+        );
+        disk.insert("src/Base.sol", "contract Base {}");
+        disk.insert("lib/oz/Ownable.sol", r#"import "./Missing.sol";"#);
+        let mut buffers = MemoryProvider::default();
+        buffers.insert("src/Base.sol", r#"import "./Token.sol"; contract Base {}"#);
+        let provider = OverlayProvider::new(buffers, disk);
+
+        let remappings = [Remapping::from_str("@oz/=lib/oz/").unwrap()];
+        let sources =
+            ProvidedSources::collect(&provider, &remappings, &["src/Token.sol".to_owned()])
+                .unwrap();
+
+        assert_eq!(
+            sources.contents.keys().collect::<Vec<_>>(),
+            ["lib/oz/Ownable.sol", "src/Base.sol", "src/Token.sol"]
+        );
+        assert!(sources.contents["src/Base.sol"].starts_with("import"));
+        assert_eq!(sources.unresolved.len(), 1);
+        assert_eq!(sources.unresolved[0].import, "./Missing.sol");
+    }
+}
@@ -0,0 +1,29 @@
+//! The overlay source provider.
+
+use super::SourceProvider;
+
+/// The source provider serving the `upper` sources over the `lower` ones.
+/// For example, the unsaved editor buffers in memory over the files on disk.
+#[derive(Debug, Clone, Default)]
+pub struct OverlayProvider<U: SourceProvider, L: SourceProvider> {
+    /// The provider taking precedence.
+    pub upper: U,
+    /// The provider used for the source units missing in the `upper` one.
+    pub lower: L,
+}
+
+impl<U: SourceProvider, L: SourceProvider> OverlayProvider<U, L> {
+    /// A shortcut constructor.
+    pub fn new(upper: U, lower: L) -> Self {
+        Self { upper, lower }
+    }
+}
+
+impl<U: SourceProvider, L: SourceProvider> SourceProvider for OverlayProvider<U, L> {
+    fn read(&self, source_unit_name: &str) -> anyhow::Result<Option<String>> {
+        match self.upper.read(source_unit_name)? {
+            Some(contents) => Ok(Some(contents)),
+            None => self.lower.read(source_unit_name),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! The Solidity import remapping.
+
+use std::path::Path;
+use std::str::FromStr;
+
+/// The Solidity import remapping, in the `[<context>:]<prefix>=<target>` format.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Remapping {
+    /// The prefix of the importing source unit names the remapping is restricted to.
+    pub context: Option<String>,
+    /// The prefix of the import paths to replace.
+    pub prefix: String,
+    /// The replacement of the prefix.
+    pub target: String,
+}
+
+impl Remapping {
+    /// The Foundry remappings file name.
+    pub const FILE_NAME: &'static str = "remappings.txt";
+    /// The Foundry dependencies directory name.
+    pub const LIBRARIES_DIRECTORY: &'static str = "lib";
+
+    /// A shortcut constructor.
+    pub fn new(context: Option<String>, prefix: String, target: String) -> Self {
+        Self {
+            context,
+            prefix,
+            target,
+        }
+    }
+
+    /// Reads the remappings file, one remapping per line.
+    /// Empty lines and lines starting with `#` are skipped.
+    pub fn read_file(path: &Path) -> anyhow::Result<Vec<Self>> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}"))?;
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| Self::from_str(line).map_err(|error| anyhow::anyhow!("{path:?}: {error}")))
+            .collect()
+    }
+
+    /// Discovers the remappings of the project at `root`: the ones from `remappings.txt`,
+    /// followed by `<name>/=lib/<name>/src/` (or `lib/<name>/` if there is no `src`) for
+    /// every dependency in `lib` whose prefix is not remapped yet.
+    pub fn discover(root: &Path) -> anyhow::Result<Vec<Self>> {
+        let mut remappings = Vec::new();
+
+        let file_path = root.join(Self::FILE_NAME);
+        if file_path.is_file() {
+            remappings.extend(Self::read_file(file_path.as_path())?);
+        }
+
+        let libraries_directory = root.join(Self::LIBRARIES_DIRECTORY);
+        if libraries_directory.is_dir() {
+            let mut libraries = std::fs::read_dir(libraries_directory.as_path())?
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect::<Vec<_>>();
+            libraries.sort();
+
+            for name in libraries.into_iter() {
+                let prefix = format!("{name}/");
+                if remappings
+                    .iter()
+                    .any(|remapping| remapping.context.is_none() && remapping.prefix == prefix)
+                {
+                    continue;
+                }
+
+                let library_path = libraries_directory.join(name.as_str());
+                let target_path = if library_path.join("src").is_dir() {
+                    library_path.join("src")
+                } else {
+                    library_path
+                };
+                let target = format!("{}/", target_path.to_string_lossy().replace('\\', "/"));
+                remappings.push(Self::new(None, prefix, target));
+            }
+        }
+
+        Ok(remappings)
+    }
+
+    /// Applies the remapping to the `import` path found in the `source` unit.
+    /// Returns `None` if the remapping does not match.
+    pub fn apply(&self, source: &str, import: &str) -> Option<String> {
+        if !self.matches(source, import) {
+            return None;
+        }
+        Some(format!("{}{}", self.target, &import[self.prefix.len()..]))
+    }
+
+    /// Checks whether the remapping applies to the `import` path found in the `source` unit.
+    pub fn matches(&self, source: &str, import: &str) -> bool {
+        let context = self.context.as_deref().unwrap_or_default();
+        source.starts_with(context) && import.starts_with(self.prefix.as_str())
+    }
+
+    /// Selects the remapping `solc` would apply to the `import` path found in the `source` unit:
+    /// the one with the longest context, then the longest prefix, then the last one.
+    pub fn select<'a>(remappings: &'a [Self], source: &str, import: &str) -> Option<&'a Self> {
+        remappings
+            .iter()
+            .filter(|remapping| remapping.matches(source, import))
+            .max_by_key(|remapping| {
+                (
+                    remapping
+                        .context
+                        .as_deref()
+                        .map(str::len)
+                        .unwrap_or_default(),
+                    remapping.prefix.len(),
+                )
+            })
+    }
+}
+
+impl FromStr for Remapping {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let string = string.trim();
+        let (left, target) = string.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid remapping `{string}`: expected `[<context>:]<prefix>=<target>`, but there is no `=`"
+            )
+        })?;
+        if target.contains('=') {
+            anyhow::bail!(
+                "Invalid remapping `{string}`: expected `[<context>:]<prefix>=<target>`, but there is more than one `=`"
+            );
+        }
+
+        let (context, prefix) = match left.split_once(':') {
+            Some((context, prefix)) => (Some(context), prefix),
+            None => (None, left),
+        };
+        if prefix.is_empty() {
+            anyhow::bail!("Invalid remapping `{string}`: the prefix must not be empty");
+        }
+
+        Ok(Self::new(
+            context
+                .filter(|context| !context.is_empty())
+                .map(str::to_owned),
+            prefix.to_owned(),
+            target.to_owned(),
+        ))
+    }
+}
+
+impl std::fmt::Display for Remapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(context) = self.context.as_deref() {
+            write!(f, "{context}:")?;
+        }
+        write!(f, "{}={}", self.prefix, self.target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Remapping;
+
+    #[test]
+    fn parses_context_qualified_remapping() {
+        let remapping = Remapping::from_str("lib/oz:@openzeppelin/=lib/oz/contracts/").unwrap();
+        assert_eq!(remapping.context.as_deref(), Some("lib/oz"));
+        assert_eq!(remapping.prefix, "@openzeppelin/");
+        assert_eq!(remapping.target, "lib/oz/contracts/");
+        assert_eq!(
+            remapping.to_string(),
+            "lib/oz:@openzeppelin/=lib/oz/contracts/"
+        );
+
+        assert_eq!(
+            remapping.apply("lib/oz/token/ERC20.sol", "@openzeppelin/utils/Context.sol"),
+            Some("lib/oz/contracts/utils/Context.sol".to_owned())
+        );
+        assert_eq!(
+            remapping.apply("src/Token.sol", "@openzeppelin/utils/Context.sol"),
+            None
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! The Solidity import resolver.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::remapping::Remapping;
+
+/// The Solidity import resolver.
+/// Mirrors the `solc` import callback: remappings are applied to the source unit name,
+/// which is then looked up in the base path and the include paths. Unlike `solc`, it also
+/// looks for packages in the `node_modules` directories of the project and its ancestors.
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    /// The remappings.
+    remappings: Vec<Remapping>,
+    /// The directories the source unit names are looked up in.
+    roots: Vec<PathBuf>,
+    /// The `node_modules` directories, from the innermost one.
+    node_modules: Vec<PathBuf>,
+}
+
+/// The resolved import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedImport {
+    /// The source unit name `solc` is going to request.
+    pub source_unit_name: String,
+    /// The file the source unit is read from.
+    pub path: PathBuf,
+    /// The remapping to pass to `solc` for the import to resolve, if it was found in `node_modules`.
+    pub remapping: Option<Remapping>,
+}
+
+/// The import which could not be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedImport {
+    /// The importing source unit name.
+    pub source: String,
+    /// The import path.
+    pub import: String,
+    /// The files which were tried, in order.
+    pub candidates: Vec<PathBuf>,
+}
+
+/// The result of resolving the imports of a project.
+#[derive(Debug, Default, Clone)]
+pub struct Resolution {
+    /// The remappings needed for the `node_modules` packages.
+    pub remappings: BTreeSet<Remapping>,
+    /// The imports which could not be resolved.
+    pub unresolved: Vec<UnresolvedImport>,
+    /// The files of the input and resolved source units, keyed by source unit name.
+    pub sources: BTreeMap<String, PathBuf>,
+}
+
+impl Resolver {
+    /// The Node.js packages directory name.
+    pub const NODE_MODULES_DIRECTORY: &'static str = "node_modules";
+
+    /// Creates the resolver for the project in the working directory `directory`.
+    /// The source unit names are looked up in `base_path` (the working directory by default)
+    /// and in the `include_paths`.
+    pub fn new(
+        remappings: Vec<Remapping>,
+        base_path: Option<PathBuf>,
+        include_paths: Vec<PathBuf>,
+        directory: &Path,
+    ) -> Self {
+        let mut roots = vec![base_path.unwrap_or_default()];
+        roots.extend(include_paths);
+
+        let mut node_modules = Vec::new();
+        let mut relative = PathBuf::new();
+        for ancestor in directory.ancestors() {
+            if ancestor.join(Self::NODE_MODULES_DIRECTORY).is_dir() {
+                node_modules.push(relative.join(Self::NODE_MODULES_DIRECTORY));
+            }
+            relative.push("..");
+        }
+
+        Self {
+            remappings,
+            roots,
+            node_modules,
+        }
+    }
+
+    /// Resolves the `import` path found in the `source` unit.
+    pub fn resolve(&self, source: &str, import: &str) -> Result<ResolvedImport, UnresolvedImport> {
+        let source_unit_name = source_unit_name(self.remappings.as_slice(), source, import);
+
+        let mut candidates = Vec::with_capacity(self.roots.len() + self.node_modules.len());
+        for root in self.roots.iter() {
+            let path = root.join(source_unit_name.as_str());
+            if path.is_file() {
+                return Ok(ResolvedImport {
+                    source_unit_name,
+                    path,
+                    remapping: None,
+                });
+            }
+            candidates.push(path);
+        }
+
+        let package = package_name(source_unit_name.as_str());
+        for node_modules in self.node_modules.iter() {
+            let path = node_modules.join(source_unit_name.as_str());
+            if path.is_file() {
+                let target = node_modules
+                    .join(package)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let remapping = Remapping::new(None, format!("{package}/"), format!("{target}/"));
+                return Ok(ResolvedImport {
+                    source_unit_name: remapping
+                        .apply(source, source_unit_name.as_str())
+                        .unwrap_or_else(|| path.to_string_lossy().to_string()),
+                    path,
+                    remapping: Some(remapping),
+                });
+            }
+            candidates.push(path);
+        }
+
+        Err(UnresolvedImport {
+            source: source.to_owned(),
+            import: import.to_owned(),
+            candidates,
+        })
+    }
+
+    /// Resolves the imports of the `input_files` and of the files they import, transitively.
+    pub fn resolve_all(&self, input_files: &[PathBuf]) -> anyhow::Result<Resolution> {
+        let mut resolution = Resolution::default();
+        let mut queue = input_files
+            .iter()
+            .map(|path| (path.to_string_lossy().replace('\\', "/"), path.to_owned()))
+            .collect::<VecDeque<_>>();
+
+        while let Some((source_unit_name, path)) = queue.pop_front() {
+            if resolution.sources.contains_key(source_unit_name.as_str()) {
+                continue;
+            }
+            resolution
+                .sources
+                .insert(source_unit_name.clone(), path.clone());
+
+            let source_code = std::fs::read_to_string(path.as_path())
+                .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}"))?;
+            for import in super::parse_imports(source_code.as_str()).into_iter() {
+                match self.resolve(source_unit_name.as_str(), import.as_str()) {
+                    Ok(resolved) => {
+                        resolution.remappings.extend(resolved.remapping);
+                        queue.push_back((resolved.source_unit_name, resolved.path));
+                    }
+                    Err(unresolved) => resolution.unresolved.push(unresolved),
+                }
+            }
+        }
+
+        Ok(resolution)
+    }
+}
+
+impl std::fmt::Display for UnresolvedImport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Source \"{}\" not found, imported from \"{}\". Tried:",
+            self.import, self.source
+        )?;
+        for candidate in self.candidates.iter() {
+            write!(f, "\n    {}", candidate.to_string_lossy())?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the source unit name `solc` requests for the `import` path found in the `source` unit:
+/// relative imports are joined with the directory of the `source`, and then the remappings are applied.
+pub fn source_unit_name(remappings: &[Remapping], source: &str, import: &str) -> String {
+    let source_unit_name = if import.starts_with("./") || import.starts_with("../") {
+        let directory = source.rsplit_once('/').map(|(directory, _)| directory);
+        normalize(format!("{}/{import}", directory.unwrap_or(".")).as_str())
+    } else {
+        import.to_owned()
+    };
+    match Remapping::select(remappings, source, source_unit_name.as_str()) {
+        Some(remapping) => remapping
+            .apply(source, source_unit_name.as_str())
+            .expect("Always matches"),
+        None => source_unit_name,
+    }
+}
+
+/// Resolves the `.` and `..` segments of the source unit name, as `solc` does for relative imports.
+fn normalize(source_unit_name: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in source_unit_name.split('/') {
+        match segment {
+            "." => continue,
+            ".." if segments
+                .last()
+                .is_some_and(|last| !last.is_empty() && *last != "..") =>
+            {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// Returns the package name the source unit name starts with: `@scope/name` or `name`.
+fn package_name(source_unit_name: &str) -> &str {
+    let segments = if source_unit_name.starts_with('@') {
+        2
+    } else {
+        1
+    };
+    match source_unit_name.match_indices('/').nth(segments - 1) {
+        Some((index, _)) => &source_unit_name[..index],
+        None => source_unit_name,
+    }
+}
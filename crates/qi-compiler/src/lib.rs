@@ -1,11 +1,36 @@
+pub mod build;
+pub mod cache;
+pub mod compile;
+pub mod config;
 pub mod debug_config;
+pub mod diagnostic;
+pub mod error;
+pub mod imports;
 pub mod optimizer;
+pub mod process;
+pub mod project;
 pub mod solc;
 pub mod versions;
 pub mod yul;
 
+pub use build::Build;
+pub use cache::Cache;
+pub use compile::compile;
+pub use compile::compile_with;
+pub use compile::output::CompileOutput;
+pub use compile::request::CompileRequest;
+pub use compile::request::Source;
+pub use config::Config;
 pub use debug_config::DebugConfig;
+pub use diagnostic::Diagnostic;
+pub use error::Error;
+pub use imports::provider::SourceProvider;
 pub use optimizer::settings::Settings as OptimizerSettings;
+pub use project::Project;
+pub use solc::recording_compiler::RecordingCompiler;
+pub use solc::replay_compiler::ReplayCompiler;
 pub use solc::Compiler;
 pub use solc::solc_compiler::SolcCompiler;
+#[cfg(all(feature = "soljson", not(target_os = "emscripten")))]
+pub use solc::soljson_wasm_compiler::SoljsonWasmCompiler;
 pub use versions::Version;
@@ -1,6 +1,9 @@
 //! The LLVM optimizer settings.
 
 pub mod size_level;
+pub mod yul_pass;
+
+use std::collections::BTreeSet;
 
 use revive_solc_json_interface::SolcStandardJsonInputSettingsOptimizer;
 use serde::Deserialize;
@@ -9,6 +12,7 @@ use serde::Serialize;
 use itertools::Itertools;
 
 use self::size_level::SizeLevel;
+use self::yul_pass::YulPass;
 use super::OptimizationLevel;
 
 /// The LLVM optimizer and code-gen settings.
@@ -28,6 +32,10 @@ pub struct Settings {
     pub is_verify_each_enabled: bool,
     /// Whether the LLVM `debug logging` option is enabled.
     pub is_debug_logging_enabled: bool,
+
+    /// The Yul SSA IR passes disabled for debugging.
+    #[serde(default)]
+    pub disabled_yul_passes: BTreeSet<YulPass>,
 }
 
 impl Settings {
@@ -46,6 +54,8 @@ impl Settings {
 
             is_verify_each_enabled: false,
             is_debug_logging_enabled: false,
+
+            disabled_yul_passes: BTreeSet::new(),
         }
     }
 
@@ -67,6 +77,8 @@ impl Settings {
 
             is_verify_each_enabled,
             is_debug_logging_enabled,
+
+            disabled_yul_passes: BTreeSet::new(),
         }
     }
 
@@ -110,7 +122,7 @@ impl Settings {
                 SizeLevel::Z,
                 OptimizationLevel::Aggressive,
             ),
-            char => anyhow::bail!("Unexpected optimization option '{}'", char),
+            level => return Err(crate::error::Error::InvalidOptimizationLevel { level }.into()),
         })
     }
 
@@ -155,6 +167,16 @@ impl Settings {
             || self.level_middle_end_size != SizeLevel::Zero
     }
 
+    /// Disables the Yul SSA IR pass.
+    pub fn disable_yul_pass(&mut self, pass: YulPass) {
+        self.disabled_yul_passes.insert(pass);
+    }
+
+    /// Whether the Yul SSA IR pass runs, which requires the middle-end optimizations.
+    pub fn is_yul_pass_enabled(&self, pass: YulPass) -> bool {
+        self.is_middle_end_enabled() && !self.disabled_yul_passes.contains(&pass)
+    }
+
     /// Returns all possible combinations of the optimizer settings.
     /// Used only for testing purposes.
     pub fn combinations() -> Vec<Self> {
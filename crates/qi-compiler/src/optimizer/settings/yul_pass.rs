@@ -0,0 +1,56 @@
+//! The Yul SSA IR optimization pass.
+
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The Yul SSA IR optimization pass, which knows the meaning of the EVM builtins.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum YulPass {
+    /// Evaluates the builtins with constant arguments and the branches on constants.
+    ConstantFolding,
+    /// Forwards the values stored by `mstore` to the `mload` of the same word, and evaluates
+    /// `keccak256` over the memory filled with constants.
+    MemoryForwarding,
+    /// Replaces the `sload` of a slot already loaded or stored with the known value.
+    SloadElimination,
+    /// Removes the `mstore` and `sstore` overwritten before anything can read them.
+    DeadStoreElimination,
+    /// Removes the functions the object code never calls.
+    UnusedFunctionRemoval,
+}
+
+impl YulPass {
+    /// All the passes, in the order they run in.
+    pub const ALL: [Self; 5] = [
+        Self::ConstantFolding,
+        Self::MemoryForwarding,
+        Self::SloadElimination,
+        Self::DeadStoreElimination,
+        Self::UnusedFunctionRemoval,
+    ];
+}
+
+impl FromStr for YulPass {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|pass| pass.to_string() == string)
+            .ok_or_else(|| anyhow::anyhow!("Invalid Yul optimization pass `{string}`"))
+    }
+}
+
+impl std::fmt::Display for YulPass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConstantFolding => write!(f, "constant-folding"),
+            Self::MemoryForwarding => write!(f, "memory-forwarding"),
+            Self::SloadElimination => write!(f, "sload-elimination"),
+            Self::DeadStoreElimination => write!(f, "dead-store-elimination"),
+            Self::UnusedFunctionRemoval => write!(f, "unused-function-removal"),
+        }
+    }
+}
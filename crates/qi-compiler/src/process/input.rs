@@ -0,0 +1,39 @@
+//! The recursive process input.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::debug_config::DebugConfig;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::project::contract::Contract;
+use crate::solc::version::Version as SolcVersion;
+
+/// The recursive process input, passed to the subprocess on stdin.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Input {
+    /// The contract to compile.
+    pub contract: Contract,
+    /// The `solc` compiler version.
+    pub solc_version: SolcVersion,
+    /// The optimizer settings.
+    pub optimizer_settings: OptimizerSettings,
+    /// The debug output settings.
+    pub debug_config: DebugConfig,
+}
+
+impl Input {
+    /// A shortcut constructor.
+    pub fn new(
+        contract: Contract,
+        solc_version: SolcVersion,
+        optimizer_settings: OptimizerSettings,
+        debug_config: DebugConfig,
+    ) -> Self {
+        Self {
+            contract,
+            solc_version,
+            optimizer_settings,
+            debug_config,
+        }
+    }
+}
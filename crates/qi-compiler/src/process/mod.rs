@@ -0,0 +1,82 @@
+//! The recursive process, compiling one contract in a subprocess.
+//! A crash or an out-of-memory kill of the subprocess fails only the contract it was compiling.
+
+pub mod input;
+pub mod output;
+
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::OnceLock;
+
+use crate::build::contract::Contract as ContractBuild;
+
+use self::input::Input;
+use self::output::Output;
+
+/// The executable re-invoked with `--recursive-process` for every contract.
+/// If unset, the contracts are compiled in the current process.
+pub static EXECUTABLE: OnceLock<PathBuf> = OnceLock::new();
+
+/// The command line flag switching the executable to the recursive process mode.
+pub const FLAG: &str = "--recursive-process";
+
+/// Reads the input from stdin, compiles the contract, and writes the output to stdout.
+pub fn run() -> anyhow::Result<()> {
+    let mut stdin = Vec::new();
+    std::io::stdin().read_to_end(&mut stdin)?;
+    let input: Input = serde_json::from_slice(stdin.as_slice())
+        .map_err(|error| anyhow::anyhow!("Recursive process input parsing error: {error}"))?;
+
+    let build = input.contract.compile(
+        &input.solc_version,
+        input.optimizer_settings,
+        &input.debug_config,
+    )?;
+
+    let output = Output::new(build);
+    serde_json::to_writer(std::io::stdout(), &output)?;
+    Ok(())
+}
+
+/// Compiles the contract in a subprocess of the `executable`.
+/// A subprocess failure is reported with its exit status and stderr.
+pub fn call(executable: &Path, input: &Input) -> anyhow::Result<ContractBuild> {
+    let mut command = Command::new(executable);
+    command.arg(FLAG);
+    command.stdin(Stdio::piped());
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::piped());
+
+    let mut process = command
+        .spawn()
+        .map_err(|error| anyhow::anyhow!("{executable:?} subprocess spawning error: {error}"))?;
+    let stdin = serde_json::to_vec(input)?;
+    process
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("{executable:?} subprocess stdin getting error"))?
+        .write_all(stdin.as_slice())
+        .map_err(|error| {
+            anyhow::anyhow!("{executable:?} subprocess stdin writing error: {error}")
+        })?;
+
+    let result = process
+        .wait_with_output()
+        .map_err(|error| anyhow::anyhow!("{executable:?} subprocess output error: {error}"))?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(result.stderr.as_slice());
+        anyhow::bail!(
+            "The compiler subprocess failed ({}): {}",
+            result.status,
+            stderr.trim()
+        );
+    }
+
+    let output: Output = serde_json::from_slice(result.stdout.as_slice())
+        .map_err(|error| anyhow::anyhow!("Recursive process output parsing error: {error}"))?;
+    Ok(output.build)
+}
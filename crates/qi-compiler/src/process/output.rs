@@ -0,0 +1,20 @@
+//! The recursive process output.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::build::contract::Contract as ContractBuild;
+
+/// The recursive process output, read from the subprocess stdout.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Output {
+    /// The contract build.
+    pub build: ContractBuild,
+}
+
+impl Output {
+    /// A shortcut constructor.
+    pub fn new(build: ContractBuild) -> Self {
+        Self { build }
+    }
+}
@@ -0,0 +1,215 @@
+//! The Solidity project contract.
+
+use std::collections::BTreeMap;
+
+use rowan::TextRange;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::build::artifact::LinkReferences;
+use crate::build::contract::Contract as ContractBuild;
+use crate::debug_config::ir_type::IRType;
+use crate::debug_config::DebugConfig;
+use crate::diagnostic::Diagnostic;
+use crate::diagnostic::Severity;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::solc::version::Version as SolcVersion;
+use crate::versions::Version;
+use crate::yul::ast::Object;
+use crate::yul::ir::Object as IRObject;
+use crate::yul::lint::Lint;
+use crate::yul::parser::ParseError;
+
+/// The Solidity project contract.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Contract {
+    /// The full contract path, in the `<path>:<name>` format.
+    pub path: String,
+    /// The optimized Yul IR emitted by `solc`.
+    pub ir: String,
+    /// The `solc` metadata.
+    pub source_metadata: serde_json::Value,
+    /// The ABI, if requested from `solc`.
+    pub abi: Option<serde_json::Value>,
+    /// The method identifiers, if requested from `solc`.
+    pub method_identifiers: Option<BTreeMap<String, String>>,
+    /// The storage layout, if requested from `solc`.
+    pub storage_layout: Option<serde_json::Value>,
+    /// The optimized Yul AST emitted by `solc`, if supported by its version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ir_ast: Option<serde_json::Value>,
+}
+
+impl Contract {
+    /// Whether the QiVM code generator is available, so `compile` can produce the bytecode.
+    /// Without it, the builds carry the `solc` artifacts and the metadata only.
+    pub const IS_CODEGEN_AVAILABLE: bool = false;
+
+    /// A shortcut constructor.
+    pub fn new(
+        path: String,
+        ir: String,
+        source_metadata: serde_json::Value,
+        abi: Option<serde_json::Value>,
+        method_identifiers: Option<BTreeMap<String, String>>,
+        storage_layout: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            path,
+            ir,
+            source_metadata,
+            abi,
+            method_identifiers,
+            storage_layout,
+            ir_ast: None,
+        }
+    }
+
+    /// Returns the Yul object, deserialized from the `solc` JSON AST if there is one,
+    /// and parsed from the Yul IR text otherwise.
+    pub fn object(&self) -> Result<Object, Vec<ParseError>> {
+        match self.ir_ast.as_ref() {
+            Some(ast) => Object::from_solc_json(ast).map_err(|error| {
+                vec![ParseError {
+                    message: format!(
+                        "The `solc` `irOptimizedAst` output cannot be read: {error:#}"
+                    ),
+                    range: TextRange::default(),
+                }]
+            }),
+            None => Object::parse(self.ir.as_str()),
+        }
+    }
+
+    /// Checks the contract Yul IR: the syntax, the semantic rules and the QiVM compatibility lints,
+    /// skipping the `suppressed` ones. The locations are mapped back to the Solidity `sources` where possible.
+    pub fn check(
+        &self,
+        sources: &BTreeMap<String, String>,
+        suppressed: &[Lint],
+    ) -> Vec<Diagnostic> {
+        let yul_name = DebugConfig::full_file_name(self.path.as_str(), None, IRType::Yul);
+        let diagnostic = |severity, code, message, range| {
+            Diagnostic::from_yul(
+                severity,
+                code,
+                message,
+                yul_name.as_str(),
+                self.ir.as_str(),
+                range,
+                sources,
+            )
+        };
+
+        let object = match self.object() {
+            Ok(object) => object,
+            Err(errors) => {
+                return errors
+                    .into_iter()
+                    .map(|error| diagnostic(Severity::Error, None, error.message, error.range))
+                    .collect();
+            }
+        };
+        let mut diagnostics = crate::yul::semantic::analyze(&object)
+            .errors
+            .into_iter()
+            .map(|error| diagnostic(Severity::Error, None, error.message, error.range))
+            .collect::<Vec<_>>();
+        diagnostics.extend(
+            crate::yul::lint::check(&object, suppressed)
+                .into_iter()
+                .map(|finding| {
+                    diagnostic(
+                        Severity::Warning,
+                        Some(finding.lint.code()),
+                        finding.lint.message().to_owned(),
+                        finding.range,
+                    )
+                }),
+        );
+        diagnostics
+    }
+
+    /// Compiles the contract Yul IR into QiVM code.
+    /// The Yul IR is checked, lowered to the SSA IR and optimized in any case, while the bytecode
+    /// is only generated if the code generator is available.
+    pub fn compile(
+        self,
+        solc_version: &SolcVersion,
+        optimizer_settings: OptimizerSettings,
+        debug_config: &DebugConfig,
+    ) -> anyhow::Result<ContractBuild> {
+        let metadata_json = serde_json::json!({
+            "source_metadata": self.source_metadata,
+            "solc_version": solc_version.long,
+            "qi_version": Version::default().long,
+            "optimizer_settings": optimizer_settings,
+        });
+
+        debug_config.dump_yul(self.path.as_str(), self.ir.as_str())?;
+
+        let mut ir = self.ssa()?;
+        crate::yul::ir::passes::optimize(&mut ir, &optimizer_settings)?;
+        crate::yul::ir::verifier::verify(&ir)?;
+        debug_config.dump_ssa(self.path.as_str(), ir.to_string().as_str())?;
+
+        let code = self.codegen(&ir, &optimizer_settings, debug_config)?;
+
+        let build = ContractBuild::new(
+            self.path,
+            metadata_json,
+            self.abi,
+            self.method_identifiers,
+            self.storage_layout,
+        );
+        Ok(match code {
+            Some((bytecode, assembly, link_references)) => {
+                build.with_code(bytecode, assembly, link_references)
+            }
+            None => build,
+        })
+    }
+
+    /// Builds and verifies the SSA IR of the contract Yul object.
+    pub fn ssa(&self) -> anyhow::Result<IRObject> {
+        let object = self.object().map_err(|errors| {
+            anyhow::anyhow!(
+                "{}: invalid Yul IR: {}",
+                self.path,
+                errors
+                    .into_iter()
+                    .map(|error| error.message)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )
+        })?;
+        let analysis = crate::yul::semantic::analyze(&object);
+        if !analysis.errors.is_empty() {
+            anyhow::bail!(
+                "{}: invalid Yul IR: {}",
+                self.path,
+                analysis
+                    .errors
+                    .into_iter()
+                    .map(|error| error.message)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            );
+        }
+        let ir = crate::yul::ir::builder::build(&object, &analysis.symbols)?;
+        crate::yul::ir::verifier::verify(&ir)?;
+        Ok(ir)
+    }
+
+    /// Lowers the Yul SSA IR to the QiVM bytecode, its assembly listing, and the placeholders left
+    /// for the `linkersymbol` libraries, keyed by the library source path and name.
+    /// Returns nothing until the code generator is available, see `IS_CODEGEN_AVAILABLE`.
+    fn codegen(
+        &self,
+        _ir: &IRObject,
+        _optimizer_settings: &OptimizerSettings,
+        _debug_config: &DebugConfig,
+    ) -> anyhow::Result<Option<(Vec<u8>, String, LinkReferences)>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,350 @@
+//! The Solidity project.
+
+pub mod contract;
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+use crate::build::contract::Contract as ContractBuild;
+use crate::build::Build;
+use crate::cache::Cache;
+use crate::debug_config::DebugConfig;
+use crate::diagnostic::Diagnostic;
+use crate::error::Error;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::process::input::Input as ProcessInput;
+use crate::solc::version::Version as SolcVersion;
+use crate::yul::lint::Lint;
+
+use self::contract::Contract;
+
+/// The Solidity project.
+#[derive(Debug)]
+pub struct Project {
+    /// The `solc` compiler version.
+    pub solc_version: SolcVersion,
+    /// The project contracts, keyed by their full path.
+    pub contracts: BTreeMap<String, Contract>,
+}
+
+impl Project {
+    /// A shortcut constructor.
+    pub fn new(solc_version: SolcVersion, contracts: BTreeMap<String, Contract>) -> Self {
+        Self {
+            solc_version,
+            contracts,
+        }
+    }
+
+    /// Collects the contracts with non-empty Yul IR from the `solc` standard JSON output.
+    pub fn try_from_standard_json_output(
+        output: &SolcStandardJsonOutput,
+        solc_version: SolcVersion,
+    ) -> anyhow::Result<Self> {
+        let files = match output.contracts.as_ref() {
+            Some(files) => files,
+            None => return Ok(Self::new(solc_version, BTreeMap::new())),
+        };
+
+        let mut contracts = BTreeMap::new();
+        for (path, file) in files.iter() {
+            for (name, contract) in file.iter() {
+                let full_path = format!("{path}:{name}");
+
+                let ir_optimized = match contract.ir_optimized.to_owned() {
+                    Some(ir_optimized) => ir_optimized,
+                    None => continue,
+                };
+                if ir_optimized.is_empty() {
+                    continue;
+                }
+
+                let source_metadata = contract
+                    .metadata
+                    .to_owned()
+                    .ok_or_else(|| anyhow::anyhow!("Contract `{full_path}` metadata not found"))?;
+
+                let method_identifiers = contract
+                    .evm
+                    .as_ref()
+                    .and_then(|evm| evm.method_identifiers.to_owned());
+
+                contracts.insert(
+                    full_path.clone(),
+                    Contract::new(
+                        full_path,
+                        ir_optimized,
+                        source_metadata,
+                        contract.abi.to_owned(),
+                        method_identifiers,
+                        contract.storage_layout.to_owned(),
+                    ),
+                );
+            }
+        }
+
+        Ok(Self::new(solc_version, contracts))
+    }
+
+    /// Attaches the optimized Yul ASTs, keyed by the full contract path, to the contracts.
+    /// The contracts without one keep parsing their Yul IR text.
+    pub fn with_ir_optimized_asts(mut self, asts: BTreeMap<String, serde_json::Value>) -> Self {
+        for (path, ast) in asts.into_iter() {
+            if let Some(contract) = self.contracts.get_mut(path.as_str()) {
+                contract.ir_ast = Some(ast);
+            }
+        }
+        self
+    }
+
+    /// Checks the Yul IR of all contracts of the project, see `Contract::check`.
+    /// The Solidity `sources`, keyed by their source unit names, are used to map the findings back.
+    pub fn check(
+        &self,
+        sources: &BTreeMap<String, String>,
+        suppressed: &[Lint],
+    ) -> Vec<Diagnostic> {
+        self.contracts
+            .values()
+            .flat_map(|contract| contract.check(sources, suppressed))
+            .collect()
+    }
+
+    /// Checks that the contracts can be compiled to the QiVM bytecode, which the output `mode` requires.
+    /// The modes producing the bytecode call it before running `solc`, so they fail before any work is done.
+    pub fn ensure_codegen_available(mode: &str) -> anyhow::Result<()> {
+        if Contract::IS_CODEGEN_AVAILABLE {
+            return Ok(());
+        }
+        Err(Error::CodegenUnavailable {
+            mode: mode.to_owned(),
+        }
+        .into())
+    }
+
+    /// Compiles all contracts of the project.
+    /// A failure to compile one contract does not prevent the others from being compiled.
+    /// If the `cache` is given, the unchanged contracts are taken from it instead.
+    /// With the `parallel` feature, the contracts are compiled concurrently on the rayon thread pool.
+    /// Each contract is compiled on its own, so the workers share no state, and the results are
+    /// collected in the contract path order regardless of the completion order.
+    pub fn compile(
+        self,
+        optimizer_settings: OptimizerSettings,
+        debug_config: DebugConfig,
+        cache: Option<&Cache>,
+    ) -> Build {
+        #[cfg(feature = "parallel")]
+        let iterator = self.contracts.into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let iterator = self.contracts.into_iter();
+
+        let results = iterator
+            .map(|(path, contract)| {
+                let result = Self::compile_contract(
+                    contract,
+                    &self.solc_version,
+                    &optimizer_settings,
+                    &debug_config,
+                    cache,
+                );
+                (path, result)
+            })
+            .collect();
+        Build { results }
+    }
+
+    /// Compiles the contract, or takes it from the `cache` if it has not changed.
+    /// If the recursive process executable is set, the contract is compiled in a subprocess.
+    fn compile_contract(
+        contract: Contract,
+        solc_version: &SolcVersion,
+        optimizer_settings: &OptimizerSettings,
+        debug_config: &DebugConfig,
+        cache: Option<&Cache>,
+    ) -> anyhow::Result<ContractBuild> {
+        let key = match cache {
+            Some(_) => Some(Cache::contract_key(
+                &contract,
+                solc_version,
+                optimizer_settings,
+            )?),
+            None => None,
+        };
+        if let Some(build) = cache
+            .zip(key.as_deref())
+            .and_then(|(cache, key)| cache.get_contract(key))
+        {
+            return Ok(build);
+        }
+
+        let build = match crate::process::EXECUTABLE.get() {
+            Some(executable) => {
+                let input = ProcessInput::new(
+                    contract,
+                    solc_version.to_owned(),
+                    optimizer_settings.to_owned(),
+                    debug_config.to_owned(),
+                );
+                #[cfg(debug_assertions)]
+                debug_config.dump_stage_output(
+                    input.contract.path.as_str(),
+                    None,
+                    &serde_json::to_vec(&input)?,
+                )?;
+                crate::process::call(executable.as_path(), &input)?
+            }
+            None => contract.compile(solc_version, optimizer_settings.to_owned(), debug_config)?,
+        };
+
+        if let (Some(cache), Some(key)) = (cache, key) {
+            cache.put_contract(key.as_str(), &build)?;
+        }
+        Ok(build)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::atomic::Ordering;
+
+    use revive_solc_json_interface::SolcStandardJsonOutput;
+
+    use crate::build::Build;
+    use crate::cache::Cache;
+    use crate::debug_config::DebugConfig;
+    use crate::optimizer::settings::Settings as OptimizerSettings;
+    use crate::solc::version::Version as SolcVersion;
+
+    use super::Project;
+
+    /// The Yul IR of the Foundry `Counter` contract, written in the form `solc` optimizes it to.
+    pub const COUNTER_YUL: &str = include_str!("../../tests/fixtures/Counter.yul");
+
+    /// Returns the project of the `Counter` contracts defined in the source `paths`.
+    pub fn counter_project(paths: &[&str]) -> Project {
+        let contracts = paths
+            .iter()
+            .map(|path| {
+                let contract = serde_json::json!({
+                    "abi": [{ "type": "function", "name": "increment", "inputs": [], "outputs": [], "stateMutability": "nonpayable" }],
+                    "metadata": "{\"compiler\":{\"version\":\"0.8.28\"}}",
+                    "evm": { "methodIdentifiers": { "increment()": "d09de08a" } },
+                    "storageLayout": { "storage": [], "types": null },
+                    "irOptimized": COUNTER_YUL,
+                });
+                (path.to_string(), serde_json::json!({ "Counter": contract }))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        let output: SolcStandardJsonOutput =
+            serde_json::from_value(serde_json::json!({ "contracts": contracts }))
+                .expect("Always valid");
+        Project::try_from_standard_json_output(
+            &output,
+            SolcVersion::new_simple(semver::Version::new(0, 8, 28)),
+        )
+        .expect("Always valid")
+    }
+
+    /// Serializes the contract builds, replacing the errors with their messages.
+    fn results(build: &Build) -> Vec<(String, Result<serde_json::Value, String>)> {
+        build
+            .results
+            .iter()
+            .map(|(path, result)| {
+                let result = match result {
+                    Ok(build) => Ok(serde_json::to_value(build).expect("Always valid")),
+                    Err(error) => Err(error.to_string()),
+                };
+                (path.to_owned(), result)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reuses_the_cached_builds() {
+        let directory = std::env::temp_dir().join(format!("qic-cache-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(directory.as_path());
+        let cache = Cache::new(directory.clone());
+        let compile = |optimizer_settings: OptimizerSettings| {
+            let mut project = counter_project(&["src/Counter.sol", "test/Counter.sol"]);
+            project
+                .contracts
+                .get_mut("test/Counter.sol:Counter")
+                .expect("Always valid")
+                .ir = "object \"Broken\" { code { return(0, } }".to_owned();
+            project.compile(
+                optimizer_settings,
+                DebugConfig::new(None, false),
+                Some(&cache),
+            )
+        };
+        let counters = || {
+            (
+                cache.statistics.contract_hits.load(Ordering::Relaxed),
+                cache.statistics.contract_misses.load(Ordering::Relaxed),
+            )
+        };
+
+        let first = compile(OptimizerSettings::cycles());
+        assert!(first.results["src/Counter.sol:Counter"].is_ok());
+        assert!(first.results["test/Counter.sol:Counter"].is_err());
+        assert_eq!(counters(), (0, 2));
+
+        let second = compile(OptimizerSettings::cycles());
+        assert_eq!(results(&second), results(&first));
+        assert_eq!(counters(), (1, 3));
+
+        compile(OptimizerSettings::size());
+        assert_eq!(counters(), (1, 5));
+
+        std::fs::remove_dir_all(directory).expect("Always valid");
+    }
+
+    #[test]
+    fn compiles_the_contracts_independently() {
+        let paths = (0..16)
+            .map(|index| format!("src/{index:02}/Counter.sol"))
+            .collect::<Vec<_>>();
+        let paths = paths.iter().map(String::as_str).collect::<Vec<_>>();
+        let compile = || {
+            counter_project(paths.as_slice()).compile(
+                OptimizerSettings::cycles(),
+                DebugConfig::new(None, false),
+                None,
+            )
+        };
+
+        #[cfg(feature = "parallel")]
+        let build = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .expect("Always valid")
+            .install(compile);
+        #[cfg(not(feature = "parallel"))]
+        let build = compile();
+
+        let sequential = counter_project(paths.as_slice())
+            .contracts
+            .into_iter()
+            .map(|(path, contract)| {
+                let build = contract
+                    .compile(
+                        &SolcVersion::new_simple(semver::Version::new(0, 8, 28)),
+                        OptimizerSettings::cycles(),
+                        &DebugConfig::new(None, false),
+                    )
+                    .map_err(|error| error.to_string())
+                    .map(|build| serde_json::to_value(build).expect("Always valid"));
+                (path, build)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(results(&build), sequential);
+        assert!(sequential.iter().all(|(_, result)| result.is_ok()));
+    }
+}
@@ -0,0 +1,183 @@
+//! The `solc` call fixtures, recorded by `RecordingCompiler` and served by `ReplayCompiler`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use revive_solc_json_interface::SolcStandardJsonInput;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+
+/// The `solc` call, normalized so that equal calls have equal keys.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Request {
+    /// The `Compiler::standard_json` call.
+    StandardJson {
+        /// The standard JSON input, with the output selection flags sorted.
+        input: serde_json::Value,
+        /// The base path.
+        base_path: Option<String>,
+        /// The include paths.
+        include_paths: Vec<String>,
+        /// The allowed paths.
+        allow_paths: Option<String>,
+    },
+    /// The `Compiler::combined_json` call.
+    CombinedJson {
+        /// The input paths.
+        paths: Vec<PathBuf>,
+        /// The `--combined-json` argument.
+        argument: String,
+        /// The SHA-256 hashes of the input files contents.
+        contents: BTreeMap<PathBuf, String>,
+    },
+    /// The `Compiler::take_ir_optimized_asts` call following a `Compiler::standard_json` one.
+    IrOptimizedAsts {
+        /// The key of the `Compiler::standard_json` call.
+        standard_json: String,
+    },
+    /// The `Compiler::validate_yul` call.
+    ValidateYul {
+        /// The Yul file path.
+        path: PathBuf,
+        /// The SHA-256 hashes of the Yul file contents.
+        contents: BTreeMap<PathBuf, String>,
+    },
+    /// The `Compiler::version` call.
+    Version,
+}
+
+impl Request {
+    /// A shortcut constructor.
+    pub fn standard_json(
+        input: &SolcStandardJsonInput,
+        base_path: Option<String>,
+        include_paths: Vec<String>,
+        allow_paths: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let mut input = serde_json::to_value(input)?;
+        crate::cache::normalize_output_selection(&mut input);
+        Ok(Self::StandardJson {
+            input,
+            base_path,
+            include_paths,
+            allow_paths,
+        })
+    }
+
+    /// A shortcut constructor.
+    pub fn combined_json(paths: &[PathBuf], argument: &str) -> Self {
+        Self::CombinedJson {
+            paths: paths.to_vec(),
+            argument: argument.to_owned(),
+            contents: hash_contents(paths),
+        }
+    }
+
+    /// A shortcut constructor.
+    pub fn ir_optimized_asts(standard_json: &Self) -> anyhow::Result<Self> {
+        Ok(Self::IrOptimizedAsts {
+            standard_json: standard_json.key()?,
+        })
+    }
+
+    /// A shortcut constructor.
+    pub fn validate_yul(path: &Path) -> Self {
+        Self::ValidateYul {
+            path: path.to_owned(),
+            contents: hash_contents(&[path.to_owned()]),
+        }
+    }
+
+    /// Returns the fixture key, which is the hash of the request.
+    pub fn key(&self) -> anyhow::Result<String> {
+        Ok(hex::encode(sha2::Sha256::digest(serde_json::to_vec(self)?)))
+    }
+}
+
+/// The `solc` call result.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+    /// The successful call output.
+    Output(serde_json::Value),
+    /// The failed call error message.
+    Error(String),
+}
+
+/// The recorded `solc` call, stored as `<key>.json` in the fixtures directory.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Fixture {
+    /// The call.
+    pub request: Request,
+    /// The call result.
+    pub response: Response,
+}
+
+impl Fixture {
+    /// Records the call result.
+    pub fn record<T>(
+        directory: &Path,
+        request: Request,
+        result: &anyhow::Result<T>,
+    ) -> anyhow::Result<()>
+    where
+        T: Serialize,
+    {
+        let response = match result {
+            Ok(output) => Response::Output(serde_json::to_value(output)?),
+            Err(error) => Response::Error(format!("{error:#}")),
+        };
+        let path = Self::path(directory, &request)?;
+        let fixture = Self { request, response };
+        crate::build::write_file(
+            path.as_path(),
+            serde_json::to_vec_pretty(&fixture)?.as_slice(),
+            true,
+        )
+    }
+
+    /// Replays the call result.
+    pub fn replay<T>(directory: &Path, request: Request) -> anyhow::Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let path = Self::path(directory, &request)?;
+        let contents = std::fs::read(path.as_path()).map_err(|error| {
+            anyhow::anyhow!(
+                "No fixture {path:?} for the `solc` call {}: {error}. Record it with `RecordingCompiler`",
+                serde_json::to_string(&request).expect("Always valid"),
+            )
+        })?;
+        let fixture: Self = serde_json::from_slice(contents.as_slice())
+            .map_err(|error| anyhow::anyhow!("Fixture {path:?} parsing error: {error}"))?;
+        match fixture.response {
+            Response::Output(output) => serde_json::from_value(output)
+                .map_err(|error| anyhow::anyhow!("Fixture {path:?} output parsing error: {error}")),
+            Response::Error(error) => Err(anyhow::anyhow!(error)),
+        }
+    }
+
+    /// Returns the path of the fixture.
+    fn path(directory: &Path, request: &Request) -> anyhow::Result<PathBuf> {
+        Ok(directory.join(format!(
+            "{}.{}",
+            request.key()?,
+            revive_common::EXTENSION_JSON
+        )))
+    }
+}
+
+/// Hashes the contents of the readable files.
+/// The `combined_json` paths also include the remappings, which are skipped.
+fn hash_contents(paths: &[PathBuf]) -> BTreeMap<PathBuf, String> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let contents = std::fs::read(path).ok()?;
+            Some((path.to_owned(), hex::encode(sha2::Sha256::digest(contents))))
+        })
+        .collect()
+}
@@ -0,0 +1,84 @@
+//! The installed `solc` executables.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::pragma::VersionPragma;
+
+/// The installed `solc` executable, named `solc-<version>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Installation {
+    /// The version parsed from the executable name.
+    pub version: semver::Version,
+    /// The executable path.
+    pub executable: PathBuf,
+}
+
+impl Installation {
+    /// The executable name prefix.
+    pub const PREFIX: &'static str = "solc-";
+
+    /// Finds the supported `solc-<version>` executables in the `directories`, then in `${PATH}`.
+    /// If a version is found more than once, the first executable is used.
+    /// The result is sorted by version, from the newest one.
+    pub fn discover(directories: &[PathBuf]) -> Vec<Self> {
+        let path_directories = std::env::var_os("PATH")
+            .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let mut installations: Vec<Self> = Vec::new();
+        for directory in directories.iter().chain(path_directories.iter()) {
+            let Ok(entries) = std::fs::read_dir(directory) else {
+                continue;
+            };
+            let mut found = entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| Self::try_from_path(entry.path().as_path()))
+                .collect::<Vec<_>>();
+            found.sort_by(|a, b| a.executable.cmp(&b.executable));
+
+            for installation in found.into_iter() {
+                if installations
+                    .iter()
+                    .all(|existing| existing.version != installation.version)
+                {
+                    installations.push(installation);
+                }
+            }
+        }
+
+        installations.sort_by(|a, b| b.version.cmp(&a.version));
+        installations
+    }
+
+    /// Returns the newest installation satisfying all `pragmas`.
+    pub fn select<'a>(installations: &'a [Self], pragmas: &[VersionPragma]) -> Option<&'a Self> {
+        installations.iter().find(|installation| {
+            pragmas
+                .iter()
+                .all(|pragma| pragma.matches(&installation.version))
+        })
+    }
+
+    /// Recognizes a supported `solc-<version>` executable file.
+    fn try_from_path(path: &Path) -> Option<Self> {
+        if !path.is_file() {
+            return None;
+        }
+        let name = path.file_name()?.to_str()?;
+        let name = name
+            .strip_suffix(std::env::consts::EXE_SUFFIX)
+            .unwrap_or(name);
+        let version = name.strip_prefix(Self::PREFIX)?;
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let version = semver::Version::parse(version).ok()?;
+        if version < super::FIRST_SUPPORTED_VERSION || version > super::LAST_SUPPORTED_VERSION {
+            return None;
+        }
+
+        Some(Self {
+            version,
+            executable: path.to_owned(),
+        })
+    }
+}
@@ -1,11 +1,19 @@
 //! The Solidity compiler.
 
+pub mod fixture;
+pub mod installation;
+pub mod pragma;
+pub mod recording_compiler;
+pub mod replay_compiler;
 #[cfg(not(target_os = "emscripten"))]
 pub mod solc_compiler;
 #[cfg(target_os = "emscripten")]
 pub mod soljson_compiler;
+#[cfg(all(feature = "soljson", not(target_os = "emscripten")))]
+pub mod soljson_wasm_compiler;
 pub mod version;
 
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -24,6 +32,10 @@ pub const LAST_SUPPORTED_VERSION: semver::Version = semver::Version::new(0, 8, 2
 /// `--include-path` was introduced in solc `0.8.8` <https://github.com/ethereum/solidity/releases/tag/v0.8.8>
 pub const FIRST_INCLUDE_PATH_VERSION: semver::Version = semver::Version::new(0, 8, 8);
 
+/// The first version of `solc` whose `irOptimizedAst` output has the `nativeSrc` locations
+/// pointing into the optimized Yul IR text.
+pub const FIRST_IR_OPTIMIZED_AST_VERSION: semver::Version = semver::Version::new(0, 8, 26);
+
 /// The Solidity compiler.
 pub trait Compiler {
     /// Compiles the Solidity `--standard-json` input into Yul IR.
@@ -47,4 +59,52 @@ pub trait Compiler {
 
     /// The `solc --version` mini-parser.
     fn version(&mut self) -> anyhow::Result<Version>;
+
+    /// Takes the optimized Yul ASTs of the last `standard_json` call, keyed by the full contract path.
+    /// The compilers which do not request them from `solc` return none, so the Yul IR text is parsed instead.
+    fn take_ir_optimized_asts(&mut self) -> BTreeMap<String, serde_json::Value> {
+        BTreeMap::new()
+    }
+}
+
+/// Adds the `irOptimizedAst` output wherever the `irOptimized` one is selected in the standard JSON input.
+pub fn request_ir_optimized_ast(input: &mut serde_json::Value) {
+    let Some(files) = input
+        .pointer_mut("/settings/outputSelection")
+        .and_then(serde_json::Value::as_object_mut)
+    else {
+        return;
+    };
+    for contracts in files
+        .values_mut()
+        .filter_map(serde_json::Value::as_object_mut)
+    {
+        for flags in contracts
+            .values_mut()
+            .filter_map(serde_json::Value::as_array_mut)
+        {
+            if flags.iter().any(|flag| flag == "irOptimized") {
+                flags.push(serde_json::Value::from("irOptimizedAst"));
+            }
+        }
+    }
+}
+
+/// Collects the `irOptimizedAst` outputs from the standard JSON output, keyed by the full contract path.
+pub fn ir_optimized_asts(output: &serde_json::Value) -> BTreeMap<String, serde_json::Value> {
+    let mut asts = BTreeMap::new();
+    let Some(files) = output
+        .get("contracts")
+        .and_then(serde_json::Value::as_object)
+    else {
+        return asts;
+    };
+    for (path, contracts) in files.iter() {
+        for (name, contract) in contracts.as_object().into_iter().flatten() {
+            if let Some(ast) = contract.get("irOptimizedAst").filter(|ast| ast.is_object()) {
+                asts.insert(format!("{path}:{name}"), ast.to_owned());
+            }
+        }
+    }
+    asts
 }
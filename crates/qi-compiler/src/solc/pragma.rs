@@ -0,0 +1,113 @@
+//! The Solidity version pragma.
+
+use std::str::FromStr;
+
+use crate::imports::Token;
+
+/// The `pragma solidity` version requirement.
+/// The alternatives are separated by `||`, and each of them is a space-separated list of comparators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionPragma {
+    /// The source text of the requirement.
+    pub text: String,
+    /// The alternatives, any of which must be satisfied.
+    alternatives: Vec<semver::VersionReq>,
+}
+
+impl VersionPragma {
+    /// Extracts the version pragmas from the Solidity source code.
+    pub fn parse_all(source_code: &str) -> anyhow::Result<Vec<Self>> {
+        let tokens = crate::imports::tokenize(source_code);
+
+        let mut pragmas = Vec::new();
+        for (index, window) in tokens.windows(2).enumerate() {
+            let [(pragma, _), (solidity, solidity_range)] = window else {
+                continue;
+            };
+            if *pragma != Token::Word("pragma".to_owned())
+                || *solidity != Token::Word("solidity".to_owned())
+            {
+                continue;
+            }
+
+            let end = tokens[index + 2..]
+                .iter()
+                .find(|(token, _)| *token == Token::Semicolon)
+                .map(|(_, range)| range.start)
+                .unwrap_or(source_code.len());
+            pragmas.push(Self::from_str(&source_code[solidity_range.end..end])?);
+        }
+        Ok(pragmas)
+    }
+
+    /// Checks whether the `version` satisfies the requirement.
+    pub fn matches(&self, version: &semver::Version) -> bool {
+        self.alternatives
+            .iter()
+            .any(|alternative| alternative.matches(version))
+    }
+}
+
+impl FromStr for VersionPragma {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let text = string.trim().to_owned();
+
+        let mut alternatives = Vec::new();
+        for alternative in text.split("||") {
+            let mut comparators = Vec::new();
+            let mut operator = String::new();
+            for word in alternative.split_whitespace() {
+                if word.chars().all(|char| "<>=^~".contains(char)) {
+                    operator.push_str(word);
+                    continue;
+                }
+                // Unlike in Cargo, a bare version is an exact requirement in Solidity.
+                let word_operator = if operator.is_empty() && word.starts_with(char::is_numeric) {
+                    "="
+                } else {
+                    operator.as_str()
+                };
+                comparators.push(format!("{word_operator}{word}"));
+                operator.clear();
+            }
+            let requirement = semver::VersionReq::parse(comparators.join(", ").as_str())
+                .map_err(|error| anyhow::anyhow!("Invalid version pragma `{text}`: {error}"))?;
+            alternatives.push(requirement);
+        }
+
+        Ok(Self { text, alternatives })
+    }
+}
+
+impl std::fmt::Display for VersionPragma {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VersionPragma;
+
+    #[test]
+    fn matches_solidity_version_pragmas() {
+        let source_code = r#"
+            // pragma solidity 0.7.0;
+            pragma solidity >=0.8.4 <0.9.0;
+            pragma solidity ^0.8.0 || =0.7.6;
+            import "./pragma.sol";
+        "#;
+        let pragmas = VersionPragma::parse_all(source_code).unwrap();
+        assert_eq!(pragmas.len(), 2);
+        assert_eq!(pragmas[0].text, ">=0.8.4 <0.9.0");
+
+        let version = semver::Version::new(0, 8, 19);
+        assert!(pragmas.iter().all(|pragma| pragma.matches(&version)));
+
+        let exact = "0.8.19".parse::<VersionPragma>().unwrap();
+        assert!(exact.matches(&version));
+        assert!(!exact.matches(&semver::Version::new(0, 8, 20)));
+    }
+}
@@ -0,0 +1,100 @@
+//! The Solidity compiler call recorder.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use revive_solc_json_interface::combined_json::CombinedJson;
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+use crate::solc::version::Version;
+
+use super::fixture::Fixture;
+use super::fixture::Request;
+use super::Compiler;
+
+/// The Solidity compiler wrapper, saving every call of the `inner` compiler to a fixture file.
+/// The fixtures are served by `ReplayCompiler` without `solc`.
+pub struct RecordingCompiler<C: Compiler> {
+    /// The wrapped compiler.
+    pub inner: C,
+    /// The fixtures directory.
+    pub directory: PathBuf,
+    /// The optimized Yul ASTs of the last `standard_json` call.
+    ir_optimized_asts: BTreeMap<String, serde_json::Value>,
+}
+
+impl<C: Compiler> RecordingCompiler<C> {
+    /// A shortcut constructor.
+    pub fn new(inner: C, directory: PathBuf) -> Self {
+        Self {
+            inner,
+            directory,
+            ir_optimized_asts: BTreeMap::new(),
+        }
+    }
+}
+
+impl<C: Compiler> Compiler for RecordingCompiler<C> {
+    /// Compiles the Solidity `--standard-json` input into Yul IR.
+    fn standard_json(
+        &mut self,
+        input: SolcStandardJsonInput,
+        base_path: Option<String>,
+        include_paths: Vec<String>,
+        allow_paths: Option<String>,
+    ) -> anyhow::Result<SolcStandardJsonOutput> {
+        let request = Request::standard_json(
+            &input,
+            base_path.clone(),
+            include_paths.clone(),
+            allow_paths.clone(),
+        )?;
+        let ir_optimized_asts_request = Request::ir_optimized_asts(&request)?;
+        let result = self
+            .inner
+            .standard_json(input, base_path, include_paths, allow_paths);
+        Fixture::record(self.directory.as_path(), request, &result)?;
+
+        self.ir_optimized_asts = self.inner.take_ir_optimized_asts();
+        Fixture::record(
+            self.directory.as_path(),
+            ir_optimized_asts_request,
+            &Ok(self.ir_optimized_asts.clone()),
+        )?;
+        result
+    }
+
+    /// The `solc --combined-json abi,hashes...` mirror.
+    fn combined_json(
+        &self,
+        paths: &[PathBuf],
+        combined_json_argument: &str,
+    ) -> anyhow::Result<CombinedJson> {
+        let request = Request::combined_json(paths, combined_json_argument);
+        let result = self.inner.combined_json(paths, combined_json_argument);
+        Fixture::record(self.directory.as_path(), request, &result)?;
+        result
+    }
+
+    /// The `solc` Yul validator.
+    fn validate_yul(&self, path: &Path) -> anyhow::Result<()> {
+        let request = Request::validate_yul(path);
+        let result = self.inner.validate_yul(path);
+        Fixture::record(self.directory.as_path(), request, &result)?;
+        result
+    }
+
+    /// The `solc --version` mini-parser.
+    fn version(&mut self) -> anyhow::Result<Version> {
+        let result = self.inner.version();
+        Fixture::record(self.directory.as_path(), Request::Version, &result)?;
+        result
+    }
+
+    /// Takes the optimized Yul ASTs of the last `standard_json` call, keyed by the full contract path.
+    fn take_ir_optimized_asts(&mut self) -> BTreeMap<String, serde_json::Value> {
+        std::mem::take(&mut self.ir_optimized_asts)
+    }
+}
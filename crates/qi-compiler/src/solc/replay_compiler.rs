@@ -0,0 +1,268 @@
+//! The Solidity compiler call replayer.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use revive_solc_json_interface::combined_json::CombinedJson;
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+use crate::solc::version::Version;
+
+use super::fixture::Fixture;
+use super::fixture::Request;
+use super::Compiler;
+
+/// The Solidity compiler stand-in, serving the calls from the fixtures saved by `RecordingCompiler`.
+/// A call without a fixture fails, naming the missing fixture file.
+pub struct ReplayCompiler {
+    /// The fixtures directory.
+    pub directory: PathBuf,
+    /// The optimized Yul ASTs of the last `standard_json` call.
+    ir_optimized_asts: BTreeMap<String, serde_json::Value>,
+}
+
+impl ReplayCompiler {
+    /// A shortcut constructor.
+    pub fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            ir_optimized_asts: BTreeMap::new(),
+        }
+    }
+}
+
+impl Compiler for ReplayCompiler {
+    /// Compiles the Solidity `--standard-json` input into Yul IR.
+    fn standard_json(
+        &mut self,
+        input: SolcStandardJsonInput,
+        base_path: Option<String>,
+        include_paths: Vec<String>,
+        allow_paths: Option<String>,
+    ) -> anyhow::Result<SolcStandardJsonOutput> {
+        let request = Request::standard_json(&input, base_path, include_paths, allow_paths)?;
+        let ir_optimized_asts_request = Request::ir_optimized_asts(&request)?;
+        let output = Fixture::replay(self.directory.as_path(), request)?;
+        self.ir_optimized_asts =
+            Fixture::replay(self.directory.as_path(), ir_optimized_asts_request)?;
+        Ok(output)
+    }
+
+    /// The `solc --combined-json abi,hashes...` mirror.
+    fn combined_json(
+        &self,
+        paths: &[PathBuf],
+        combined_json_argument: &str,
+    ) -> anyhow::Result<CombinedJson> {
+        let request = Request::combined_json(paths, combined_json_argument);
+        Fixture::replay(self.directory.as_path(), request)
+    }
+
+    /// The `solc` Yul validator.
+    fn validate_yul(&self, path: &Path) -> anyhow::Result<()> {
+        Fixture::replay(self.directory.as_path(), Request::validate_yul(path))
+    }
+
+    /// The `solc --version` mini-parser.
+    fn version(&mut self) -> anyhow::Result<Version> {
+        Fixture::replay(self.directory.as_path(), Request::Version)
+    }
+
+    /// Takes the optimized Yul ASTs of the last `standard_json` call, keyed by the full contract path.
+    fn take_ir_optimized_asts(&mut self) -> BTreeMap<String, serde_json::Value> {
+        std::mem::take(&mut self.ir_optimized_asts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use revive_solc_json_interface::combined_json::CombinedJson;
+    use revive_solc_json_interface::SolcStandardJsonInput;
+    use revive_solc_json_interface::SolcStandardJsonInputLanguage;
+    use revive_solc_json_interface::SolcStandardJsonInputSettingsOptimizer;
+    use revive_solc_json_interface::SolcStandardJsonInputSettingsSelection;
+    use revive_solc_json_interface::SolcStandardJsonInputSource;
+    use revive_solc_json_interface::SolcStandardJsonOutput;
+
+    use crate::solc::recording_compiler::RecordingCompiler;
+    use crate::solc::version::Version;
+    use crate::solc::Compiler;
+
+    use super::ReplayCompiler;
+
+    /// The compiler accepting only the Yul files named `valid.yul`, which reports the standard JSON
+    /// sources as warnings, and outputs the combined JSON `bin` of a contract per file as its contents.
+    struct StubCompiler;
+
+    impl Compiler for StubCompiler {
+        fn standard_json(
+            &mut self,
+            input: SolcStandardJsonInput,
+            _base_path: Option<String>,
+            _include_paths: Vec<String>,
+            _allow_paths: Option<String>,
+        ) -> anyhow::Result<SolcStandardJsonOutput> {
+            let errors = input
+                .sources
+                .keys()
+                .map(|name| {
+                    let mut error = crate::build::standard_json_error(format!("Compiled {name}"));
+                    error.severity = "warning".to_owned();
+                    error
+                })
+                .collect::<Vec<_>>();
+            Ok(serde_json::from_value(serde_json::json!({
+                "contracts": {},
+                "sources": {},
+                "errors": errors,
+            }))?)
+        }
+
+        fn combined_json(
+            &self,
+            paths: &[PathBuf],
+            combined_json_argument: &str,
+        ) -> anyhow::Result<CombinedJson> {
+            let mut contracts = serde_json::Map::new();
+            for path in paths.iter() {
+                contracts.insert(
+                    format!("{}:A", path.to_string_lossy()),
+                    serde_json::json!({ combined_json_argument: hex::encode(std::fs::read(path)?) }),
+                );
+            }
+            Ok(serde_json::from_value(serde_json::json!({
+                "contracts": contracts,
+                "version": "0.8.28",
+            }))?)
+        }
+
+        fn validate_yul(&self, path: &Path) -> anyhow::Result<()> {
+            match path.file_name().and_then(|name| name.to_str()) {
+                Some("valid.yul") => Ok(()),
+                _ => anyhow::bail!("{path:?} is invalid"),
+            }
+        }
+
+        fn version(&mut self) -> anyhow::Result<Version> {
+            Ok(Version::new_simple(semver::Version::new(0, 8, 28)))
+        }
+
+        fn take_ir_optimized_asts(&mut self) -> BTreeMap<String, serde_json::Value> {
+            BTreeMap::from([(
+                "A.sol:A".to_owned(),
+                serde_json::json!({ "nodeType": "YulObject" }),
+            )])
+        }
+    }
+
+    #[test]
+    fn replays_recorded_calls() {
+        let directory = std::env::temp_dir().join(format!("qic-fixtures-{}", std::process::id()));
+
+        let mut recording = RecordingCompiler::new(StubCompiler, directory.clone());
+        recording.version().unwrap();
+        recording.validate_yul(Path::new("valid.yul")).unwrap();
+        recording
+            .validate_yul(Path::new("invalid.yul"))
+            .unwrap_err();
+
+        let mut replay = ReplayCompiler::new(directory.clone());
+        assert_eq!(
+            replay.version().unwrap().default,
+            semver::Version::new(0, 8, 28)
+        );
+        replay.validate_yul(Path::new("valid.yul")).unwrap();
+        assert_eq!(
+            replay
+                .validate_yul(Path::new("invalid.yul"))
+                .unwrap_err()
+                .to_string(),
+            "\"invalid.yul\" is invalid"
+        );
+        assert!(replay.validate_yul(Path::new("other.yul")).is_err());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    /// Returns the standard JSON input with the `A.sol` source.
+    fn standard_json_input(source: &str) -> SolcStandardJsonInput {
+        let mut input = SolcStandardJsonInput::try_from_paths(
+            SolcStandardJsonInputLanguage::Solidity,
+            None,
+            &[],
+            Vec::new(),
+            None,
+            SolcStandardJsonInputSettingsSelection::new_required(),
+            SolcStandardJsonInputSettingsOptimizer::new(
+                true,
+                None,
+                &semver::Version::new(0, 8, 28),
+                false,
+            ),
+            None,
+            None,
+        )
+        .unwrap();
+        input.sources.insert(
+            "A.sol".to_owned(),
+            SolcStandardJsonInputSource::from(source.to_owned()),
+        );
+        input
+    }
+
+    #[test]
+    fn replays_recorded_compilations() {
+        let directory =
+            std::env::temp_dir().join(format!("qic-fixtures-json-{}", std::process::id()));
+        std::fs::create_dir_all(directory.as_path()).unwrap();
+        let source_path = directory.join("A.sol");
+        std::fs::write(source_path.as_path(), "contract A {}").unwrap();
+        let paths = [source_path.clone()];
+
+        let mut recording = RecordingCompiler::new(StubCompiler, directory.clone());
+        let standard_json = recording
+            .standard_json(standard_json_input("contract A {}"), None, Vec::new(), None)
+            .unwrap();
+        let ir_optimized_asts = recording.take_ir_optimized_asts();
+        assert_eq!(ir_optimized_asts.len(), 1);
+        let combined_json = recording.combined_json(&paths, "bin").unwrap();
+
+        let mut replay = ReplayCompiler::new(directory.clone());
+        assert_eq!(
+            serde_json::to_value(
+                replay
+                    .standard_json(standard_json_input("contract A {}"), None, Vec::new(), None)
+                    .unwrap()
+            )
+            .unwrap(),
+            serde_json::to_value(standard_json).unwrap()
+        );
+        assert_eq!(replay.take_ir_optimized_asts(), ir_optimized_asts);
+        assert!(replay.take_ir_optimized_asts().is_empty());
+        assert_eq!(
+            serde_json::to_value(replay.combined_json(&paths, "bin").unwrap()).unwrap(),
+            serde_json::to_value(combined_json).unwrap()
+        );
+
+        assert!(replay
+            .standard_json(standard_json_input("contract B {}"), None, Vec::new(), None)
+            .unwrap_err()
+            .to_string()
+            .starts_with("No fixture"));
+        assert!(replay.combined_json(&paths, "abi").is_err());
+        std::fs::write(source_path.as_path(), "contract B {}").unwrap();
+        assert!(replay
+            .combined_json(&paths, "bin")
+            .unwrap_err()
+            .to_string()
+            .starts_with("No fixture"));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -1,5 +1,6 @@
 //! The Solidity compiler solc interface.
 
+use std::collections::BTreeMap;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
@@ -8,6 +9,7 @@ use revive_solc_json_interface::combined_json::CombinedJson;
 use revive_solc_json_interface::SolcStandardJsonInput;
 use revive_solc_json_interface::SolcStandardJsonOutput;
 
+use crate::error::Error;
 use crate::solc::version::Version;
 
 use super::Compiler;
@@ -16,6 +18,8 @@ use super::Compiler;
 pub struct SolcCompiler {
     /// The binary executable name.
     pub executable: String,
+    /// The optimized Yul ASTs of the last `standard_json` call.
+    ir_optimized_asts: BTreeMap<String, serde_json::Value>,
 }
 
 impl SolcCompiler {
@@ -27,12 +31,43 @@ impl SolcCompiler {
     /// uses `solc-<version>` format.
     pub fn new(executable: String) -> anyhow::Result<Self> {
         if let Err(error) = which::which(executable.as_str()) {
-            anyhow::bail!(
-                "The `{executable}` executable not found in ${{PATH}}: {}",
-                error
-            );
+            return Err(Error::SolcNotFound {
+                executable,
+                reason: error.to_string(),
+            }
+            .into());
+        }
+        Ok(Self {
+            executable,
+            ir_optimized_asts: BTreeMap::new(),
+        })
+    }
+
+    /// Creates the `solc` run failure error.
+    fn failed(&self, message: String) -> anyhow::Error {
+        Error::SolcFailed {
+            executable: self.executable.to_owned(),
+            message,
+        }
+        .into()
+    }
+
+    /// Creates the `solc` output parsing error.
+    fn output_parsing_error(&self, message: String) -> anyhow::Error {
+        Error::SolcOutputParsing {
+            executable: self.executable.to_owned(),
+            message,
+        }
+        .into()
+    }
+
+    /// Creates the `solc --version` output parsing error.
+    fn version_parsing_error(&self, message: &str) -> anyhow::Error {
+        Error::SolcVersionParsing {
+            executable: self.executable.to_owned(),
+            message: message.to_owned(),
         }
-        Ok(Self { executable })
+        .into()
     }
 }
 
@@ -45,6 +80,7 @@ impl Compiler for SolcCompiler {
         include_paths: Vec<String>,
         allow_paths: Option<String>,
     ) -> anyhow::Result<SolcStandardJsonOutput> {
+        self.ir_optimized_asts.clear();
         let version = self.version()?.validate(&include_paths)?.default;
 
         let mut command = std::process::Command::new(self.executable.as_str());
@@ -69,36 +105,41 @@ impl Compiler for SolcCompiler {
 
         let suppressed_warnings = input.suppressed_warnings.take().unwrap_or_default();
 
-        let input_json = serde_json::to_vec(&input).expect("Always valid");
+        let mut input_json = serde_json::to_value(&input).expect("Always valid");
+        let is_ir_optimized_ast_requested = version >= super::FIRST_IR_OPTIMIZED_AST_VERSION;
+        if is_ir_optimized_ast_requested {
+            super::request_ir_optimized_ast(&mut input_json);
+        }
+        let input_json = serde_json::to_vec(&input_json).expect("Always valid");
 
-        let process = command.spawn().map_err(|error| {
-            anyhow::anyhow!("{} subprocess spawning error: {:?}", self.executable, error)
-        })?;
+        let process = command
+            .spawn()
+            .map_err(|error| self.failed(format!("subprocess spawning error: {error:?}")))?;
         process
             .stdin
             .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("{} stdin getting error", self.executable))?
+            .ok_or_else(|| self.failed("stdin getting error".to_owned()))?
             .write_all(input_json.as_slice())
-            .map_err(|error| {
-                anyhow::anyhow!("{} stdin writing error: {:?}", self.executable, error)
-            })?;
+            .map_err(|error| self.failed(format!("stdin writing error: {error:?}")))?;
 
-        let output = process.wait_with_output().map_err(|error| {
-            anyhow::anyhow!("{} subprocess output error: {:?}", self.executable, error)
-        })?;
+        let output = process
+            .wait_with_output()
+            .map_err(|error| self.failed(format!("subprocess output error: {error:?}")))?;
         if !output.status.success() {
-            anyhow::bail!(
-                "{} error: {}",
-                self.executable,
-                String::from_utf8_lossy(output.stderr.as_slice()).to_string()
-            );
+            return Err(self.failed(String::from_utf8_lossy(output.stderr.as_slice()).to_string()));
         }
 
+        let ir_optimized_asts = if is_ir_optimized_ast_requested {
+            revive_common::deserialize_from_slice::<serde_json::Value>(output.stdout.as_slice())
+                .map(|json| super::ir_optimized_asts(&json))
+                .unwrap_or_default()
+        } else {
+            BTreeMap::new()
+        };
         let mut output: SolcStandardJsonOutput =
             revive_common::deserialize_from_slice(output.stdout.as_slice()).map_err(|error| {
-                anyhow::anyhow!(
-                    "{} subprocess output parsing error: {}\n{}",
-                    self.executable,
+                self.output_parsing_error(format!(
+                    "{}\n{}",
                     error,
                     revive_common::deserialize_from_slice::<serde_json::Value>(
                         output.stdout.as_slice()
@@ -107,10 +148,12 @@ impl Compiler for SolcCompiler {
                     .unwrap_or_else(
                         |_| String::from_utf8_lossy(output.stdout.as_slice()).to_string()
                     ),
-                )
+                ))
             })?;
         output.preprocess_ast(suppressed_warnings.as_slice())?;
 
+        self.ir_optimized_asts = ir_optimized_asts;
+
         Ok(output)
     }
 
@@ -139,9 +182,9 @@ impl Compiler for SolcCompiler {
         command.arg("--combined-json");
         command.arg(combined_json_flags.join(","));
 
-        let output = command.output().map_err(|error| {
-            anyhow::anyhow!("{} subprocess error: {:?}", self.executable, error)
-        })?;
+        let output = command
+            .output()
+            .map_err(|error| self.failed(format!("subprocess error: {error:?}")))?;
         if !output.status.success() {
             writeln!(
                 std::io::stdout(),
@@ -153,18 +196,13 @@ impl Compiler for SolcCompiler {
                 "{}",
                 String::from_utf8_lossy(output.stderr.as_slice())
             )?;
-            anyhow::bail!(
-                "{} error: {}",
-                self.executable,
-                String::from_utf8_lossy(output.stdout.as_slice()).to_string()
-            );
+            return Err(self.failed(String::from_utf8_lossy(output.stdout.as_slice()).to_string()));
         }
 
         let mut combined_json: CombinedJson =
             revive_common::deserialize_from_slice(output.stdout.as_slice()).map_err(|error| {
-                anyhow::anyhow!(
-                    "{} subprocess output parsing error: {}\n{}",
-                    self.executable,
+                self.output_parsing_error(format!(
+                    "{}\n{}",
                     error,
                     revive_common::deserialize_from_slice::<serde_json::Value>(
                         output.stdout.as_slice()
@@ -173,7 +211,7 @@ impl Compiler for SolcCompiler {
                     .unwrap_or_else(
                         |_| String::from_utf8_lossy(output.stdout.as_slice()).to_string()
                     ),
-                )
+                ))
             })?;
         for filtered_flag in filtered_flags.into_iter() {
             for (_path, contract) in combined_json.contracts.iter_mut() {
@@ -200,59 +238,49 @@ impl Compiler for SolcCompiler {
         command.arg("--strict-assembly");
         command.arg(path);
 
-        let output = command.output().map_err(|error| {
-            anyhow::anyhow!("{} subprocess error: {:?}", self.executable, error)
-        })?;
+        let output = command
+            .output()
+            .map_err(|error| self.failed(format!("subprocess error: {error:?}")))?;
         if !output.status.success() {
-            anyhow::bail!(
-                "{} error: {}",
-                self.executable,
-                String::from_utf8_lossy(output.stderr.as_slice()).to_string()
-            );
+            return Err(self.failed(String::from_utf8_lossy(output.stderr.as_slice()).to_string()));
         }
 
         Ok(())
     }
 
+    /// Takes the optimized Yul ASTs of the last `standard_json` call.
+    fn take_ir_optimized_asts(&mut self) -> BTreeMap<String, serde_json::Value> {
+        std::mem::take(&mut self.ir_optimized_asts)
+    }
+
     /// The `solc --version` mini-parser.
     fn version(&mut self) -> anyhow::Result<Version> {
         let mut command = std::process::Command::new(self.executable.as_str());
         command.arg("--version");
-        let output = command.output().map_err(|error| {
-            anyhow::anyhow!("{} subprocess error: {:?}", self.executable, error)
-        })?;
+        let output = command
+            .output()
+            .map_err(|error| self.failed(format!("subprocess error: {error:?}")))?;
         if !output.status.success() {
-            anyhow::bail!(
-                "{} error: {}",
-                self.executable,
-                String::from_utf8_lossy(output.stderr.as_slice()).to_string()
-            );
+            return Err(self.failed(String::from_utf8_lossy(output.stderr.as_slice()).to_string()));
         }
 
         let stdout = String::from_utf8_lossy(output.stdout.as_slice());
         let long = stdout
             .lines()
             .nth(1)
-            .ok_or_else(|| {
-                anyhow::anyhow!("{} version parsing: not enough lines", self.executable)
-            })?
+            .ok_or_else(|| self.version_parsing_error("not enough lines"))?
             .split(' ')
             .nth(1)
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "{} version parsing: not enough words in the 2nd line",
-                    self.executable
-                )
-            })?
+            .ok_or_else(|| self.version_parsing_error("not enough words in the 2nd line"))?
             .to_owned();
         let default: semver::Version = long
             .split('+')
             .next()
-            .ok_or_else(|| {
-                anyhow::anyhow!("{} version parsing: metadata dropping", self.executable)
-            })?
+            .ok_or_else(|| self.version_parsing_error("metadata dropping"))?
             .parse()
-            .map_err(|error| anyhow::anyhow!("{} version parsing: {}", self.executable, error))?;
+            .map_err(|error: semver::Error| {
+                self.version_parsing_error(error.to_string().as_str())
+            })?;
 
         let l2_revision: Option<semver::Version> = stdout
             .lines()
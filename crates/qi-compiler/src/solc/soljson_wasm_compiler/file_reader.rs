@@ -0,0 +1,106 @@
+//! The `soljson` import callback file reader.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+/// The file reader mirroring the `solc` executable import callback.
+/// The source unit names are looked up in the base path and in the include paths,
+/// and only the files in the allowed directories can be read.
+#[derive(Debug, Default, Clone)]
+pub struct FileReader {
+    /// The base path, or the current directory if empty.
+    base_path: PathBuf,
+    /// The include paths.
+    include_paths: Vec<PathBuf>,
+    /// The canonicalized allowed directories.
+    allowed_directories: Vec<PathBuf>,
+}
+
+impl FileReader {
+    /// A shortcut constructor.
+    /// The `allow_paths` are separated with commas, like in the `solc` command line.
+    pub fn new(
+        base_path: Option<String>,
+        include_paths: Vec<String>,
+        allow_paths: Option<String>,
+    ) -> Self {
+        let base_path = PathBuf::from(base_path.unwrap_or_default());
+        let include_paths = include_paths
+            .into_iter()
+            .map(PathBuf::from)
+            .collect::<Vec<_>>();
+
+        let allowed_directories = std::iter::once(base_path.clone())
+            .chain(include_paths.iter().cloned())
+            .chain(
+                allow_paths
+                    .iter()
+                    .flat_map(|paths| paths.split(','))
+                    .filter(|path| !path.is_empty())
+                    .map(PathBuf::from),
+            )
+            .filter_map(|path| {
+                let path = if path.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    path.as_path()
+                };
+                path.canonicalize().ok()
+            })
+            .collect();
+
+        Self {
+            base_path,
+            include_paths,
+            allowed_directories,
+        }
+    }
+
+    /// Reads the source unit, returning the error message passed back to `solc` on failure.
+    pub fn read(&self, source_unit_name: &str) -> Result<String, String> {
+        let source_unit_name = source_unit_name
+            .strip_prefix("file://")
+            .unwrap_or(source_unit_name);
+        let prefixes = std::iter::once(&self.base_path).chain(self.include_paths.iter());
+
+        let mut candidates = prefixes
+            .clone()
+            .filter_map(|prefix| prefix.join(source_unit_name).canonicalize().ok())
+            .collect::<Vec<_>>();
+        candidates.dedup();
+        let candidate = match candidates.as_slice() {
+            [] => {
+                let locations = prefixes
+                    .map(|prefix| format!("{:?}", prefix.to_string_lossy()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(format!(
+                    "File not found. Searched the following locations: {locations}."
+                ));
+            }
+            [candidate] => candidate,
+            candidates => {
+                let candidates = candidates
+                    .iter()
+                    .map(|candidate| format!("{:?}", candidate.to_string_lossy()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(format!(
+                    "Ambiguous import. Multiple matching files found inside base path and/or include paths: {candidates}."
+                ));
+            }
+        };
+
+        if !self
+            .allowed_directories
+            .iter()
+            .any(|directory| candidate.starts_with(directory))
+        {
+            return Err("File outside of allowed directories.".to_owned());
+        }
+        if !candidate.is_file() {
+            return Err("Not a valid file.".to_owned());
+        }
+        std::fs::read_to_string(candidate).map_err(|error| error.to_string())
+    }
+}
@@ -0,0 +1,209 @@
+//! The Emscripten JavaScript glue of the `soljson.js` builds.
+
+use std::collections::BTreeMap;
+
+use base64::Engine as _;
+
+/// The WebAssembly binary magic, as it is encoded in base64.
+const WASM_BASE64_MAGIC: &str = "AGFzbQ";
+
+/// The `soljson` build, split into the WebAssembly binary and the names used by the glue.
+/// Release builds minify the import and export names, so they are recovered from the glue code.
+#[derive(Debug)]
+pub struct Glue {
+    /// The WebAssembly binary.
+    pub wasm: Vec<u8>,
+    /// The C names of the imports, by the import field names.
+    imports: BTreeMap<String, String>,
+    /// The export names, by the C names.
+    exports: BTreeMap<String, String>,
+    /// The static constructors export name.
+    constructors: Option<String>,
+}
+
+impl Glue {
+    /// The static constructors export of the builds without the glue.
+    const CONSTRUCTORS: &'static str = "__wasm_call_ctors";
+
+    /// Reads the `soljson.js` or `soljson.wasm` file contents.
+    pub fn try_from_bytes(bytes: Vec<u8>) -> anyhow::Result<Self> {
+        if bytes.starts_with(b"\0asm") {
+            return Ok(Self {
+                wasm: bytes,
+                imports: BTreeMap::new(),
+                exports: BTreeMap::new(),
+                constructors: None,
+            });
+        }
+
+        let code = String::from_utf8(bytes)
+            .map_err(|error| anyhow::anyhow!("The glue code is not valid UTF-8: {error}"))?;
+        let start = code.find(WASM_BASE64_MAGIC).ok_or_else(|| {
+            anyhow::anyhow!("No embedded WebAssembly binary found. Only single-file `soljson.js` builds are supported")
+        })?;
+        let length = code[start..]
+            .find(['"', '\''])
+            .ok_or_else(|| anyhow::anyhow!("The embedded WebAssembly binary is not terminated"))?;
+        let wasm = base64::engine::general_purpose::STANDARD
+            .decode(&code[start..start + length])
+            .map_err(|error| {
+                anyhow::anyhow!("The embedded WebAssembly binary decoding error: {error}")
+            })?;
+
+        let glue = format!("{}{}", &code[..start], &code[start + length..]);
+        Ok(Self {
+            wasm,
+            imports: parse_imports(glue.as_str()),
+            exports: parse_exports(glue.as_str()),
+            constructors: parse_constructors(glue.as_str()),
+        })
+    }
+
+    /// Returns the C name of the import.
+    pub fn import_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.imports.get(name).map(String::as_str).unwrap_or(name)
+    }
+
+    /// Returns the export name of the C function.
+    pub fn export_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.exports.get(name).map(String::as_str).unwrap_or(name)
+    }
+
+    /// Returns the static constructors export name.
+    pub fn constructors_name(&self) -> &str {
+        self.constructors
+            .as_deref()
+            .unwrap_or_else(|| self.export_name(Self::CONSTRUCTORS))
+    }
+}
+
+/// Parses the import object, e.g. `var wasmImports={a:___assert_fail,b:_abort}`.
+fn parse_imports(glue: &str) -> BTreeMap<String, String> {
+    let mut imports = BTreeMap::new();
+    for object_name in ["wasmImports", "asmLibraryArg"] {
+        let Some(object) = find_object(glue, object_name) else {
+            continue;
+        };
+        for entry in object.split(',') {
+            let Some((key, value)) = entry.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().trim_matches(['"', '\'']);
+            let value = value.trim();
+            if is_identifier(key) && is_identifier(value) {
+                imports.insert(key.to_owned(), c_name(value).to_owned());
+            }
+        }
+    }
+    imports
+}
+
+/// Parses the export assignments, e.g. `var _solidity_compile=a=>(_solidity_compile=wasmExports["Z"])(a)`.
+fn parse_exports(glue: &str) -> BTreeMap<String, String> {
+    let mut exports = BTreeMap::new();
+    for (offset, _) in glue.match_indices('=') {
+        let identifier_start = glue[..offset]
+            .rfind(|char: char| !is_identifier_char(char))
+            .map(|index| index + 1)
+            .unwrap_or_default();
+        let identifier = &glue[identifier_start..offset];
+        if identifier.is_empty() || exports.contains_key(c_name(identifier)) {
+            continue;
+        }
+
+        let statement = &glue[offset + 1..];
+        let statement = &statement[..statement.find(';').unwrap_or(statement.len())];
+        if let Some(name) = find_export_access(statement) {
+            exports.insert(c_name(identifier).to_owned(), name.to_owned());
+        }
+    }
+    exports
+}
+
+/// Parses the static constructors registration, e.g. `addOnInit(wasmExports["Y"])`.
+fn parse_constructors(glue: &str) -> Option<String> {
+    ["addOnInit(", "__ATINIT__.unshift("]
+        .into_iter()
+        .filter_map(|call| {
+            let start = glue.find(call)? + call.len();
+            let arguments = &glue[start..];
+            let arguments = &arguments[..arguments.find(')')?];
+            find_export_access(arguments).map(str::to_owned)
+        })
+        .next()
+}
+
+/// Finds the first access to the export object, e.g. `wasmExports["Z"]` or `Module["asm"]["Z"]`.
+fn find_export_access(code: &str) -> Option<&str> {
+    ["wasmExports[\"", "wasmExports.", "asm\"][\"", "asm[\""]
+        .into_iter()
+        .filter_map(|prefix| {
+            let start = code.find(prefix)? + prefix.len();
+            let length = code[start..]
+                .find(|char: char| !is_identifier_char(char))
+                .unwrap_or(code.len() - start);
+            Some((start, &code[start..start + length]))
+        })
+        .filter(|(_, name)| !name.is_empty())
+        .min_by_key(|(start, _)| *start)
+        .map(|(_, name)| name)
+}
+
+/// Returns the contents of the object literal assigned to the variable.
+fn find_object<'a>(glue: &'a str, variable: &str) -> Option<&'a str> {
+    let pattern = format!("{variable}=");
+    let start = glue
+        .match_indices(pattern.as_str())
+        .map(|(offset, _)| offset)
+        .find(|offset| {
+            glue[..*offset]
+                .chars()
+                .next_back()
+                .is_none_or(|char| !is_identifier_char(char))
+                && glue[offset + pattern.len()..].trim_start().starts_with('{')
+        })?;
+    let object = &glue[start + pattern.len()..];
+    let object = &object[object.find('{')? + 1..];
+    Some(&object[..object.find('}')?])
+}
+
+/// Drops the underscore prepended by Emscripten to the C names.
+fn c_name(identifier: &str) -> &str {
+    identifier.strip_prefix('_').unwrap_or(identifier)
+}
+
+/// Checks whether the string is a JavaScript identifier.
+fn is_identifier(string: &str) -> bool {
+    !string.is_empty() && string.chars().all(is_identifier_char)
+}
+
+/// Checks whether the character can be a part of a JavaScript identifier.
+fn is_identifier_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || char == '_' || char == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Glue;
+
+    #[test]
+    fn recovers_minified_names() {
+        let glue = r#"var Module=typeof Module!="undefined"?Module:{};var wasmBinaryFile="data:application/octet-stream;base64,AGFzbQEAAAA=";
+            function ___cxa_throw(ptr,type,destructor){throw exceptionLast}
+            var wasmImports={a:___assert_fail,b:___cxa_throw,c:invoke_iii,"d":_emscripten_resize_heap};
+            function run(){var wasmExports=instance.exports;addOnInit(wasmExports["f"])}
+            var _solidity_compile=Module["_solidity_compile"]=(a0,a1,a2)=>(_solidity_compile=Module["_solidity_compile"]=wasmExports["h"])(a0,a1,a2);
+            var _setThrew=(a0,a1)=>(_setThrew=wasmExports["i"])(a0,a1);
+            var stackSave=()=>(stackSave=wasmExports.j)();"#;
+        let glue = Glue::try_from_bytes(glue.as_bytes().to_vec()).unwrap();
+
+        assert_eq!(glue.wasm, b"\0asm\x01\0\0\0");
+        assert_eq!(glue.import_name("b"), "__cxa_throw");
+        assert_eq!(glue.import_name("c"), "invoke_iii");
+        assert_eq!(glue.import_name("d"), "emscripten_resize_heap");
+        assert_eq!(glue.export_name("solidity_compile"), "h");
+        assert_eq!(glue.export_name("setThrew"), "i");
+        assert_eq!(glue.export_name("stackSave"), "j");
+        assert_eq!(glue.constructors_name(), "f");
+    }
+}
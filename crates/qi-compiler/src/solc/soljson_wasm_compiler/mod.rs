@@ -0,0 +1,108 @@
+//! The Solidity compiler solJson interface, running `soljson` in an embedded WebAssembly runtime.
+
+pub mod file_reader;
+pub mod glue;
+pub mod runtime;
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use revive_solc_json_interface::combined_json::CombinedJson;
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+use crate::solc::version::Version;
+
+use self::file_reader::FileReader;
+use self::glue::Glue;
+use self::runtime::Runtime;
+
+use super::Compiler;
+
+/// The Solidity compiler, loaded from a `soljson.js` or `soljson.wasm` build.
+/// Unlike `SoljsonCompiler`, it works on native targets and does not spawn processes.
+pub struct SoljsonWasmCompiler {
+    /// The build path.
+    pub path: PathBuf,
+    /// The instantiated build.
+    runtime: Runtime,
+}
+
+impl SoljsonWasmCompiler {
+    /// A shortcut constructor.
+    /// Compiles and instantiates the build, which takes a few seconds for the release builds.
+    pub fn new(path: PathBuf) -> anyhow::Result<Self> {
+        let bytes = std::fs::read(path.as_path())
+            .map_err(|error| anyhow::anyhow!("File {path:?} reading error: {error}"))?;
+        let glue = Glue::try_from_bytes(bytes)
+            .map_err(|error| anyhow::anyhow!("File {path:?} loading error: {error}"))?;
+        let runtime = Runtime::new(&glue)
+            .map_err(|error| anyhow::anyhow!("File {path:?} loading error: {error}"))?;
+        Ok(Self { path, runtime })
+    }
+}
+
+impl Compiler for SoljsonWasmCompiler {
+    /// Compiles the Solidity `--standard-json` input into Yul IR.
+    /// The imports are read from the base path and the include paths, like `solc` does.
+    fn standard_json(
+        &mut self,
+        mut input: SolcStandardJsonInput,
+        base_path: Option<String>,
+        include_paths: Vec<String>,
+        allow_paths: Option<String>,
+    ) -> anyhow::Result<SolcStandardJsonOutput> {
+        let version = self.version()?.validate(&include_paths)?.default;
+        input.normalize(&version);
+
+        let suppressed_warnings = input.suppressed_warnings.take().unwrap_or_default();
+
+        let input_json = serde_json::to_string(&input).expect("Always valid");
+        let file_reader = FileReader::new(base_path, include_paths, allow_paths);
+        let out = self.runtime.compile(input_json.as_str(), file_reader)?;
+        let mut output: SolcStandardJsonOutput =
+            revive_common::deserialize_from_slice(out.as_bytes()).map_err(|error| {
+                anyhow::anyhow!(
+                    "Soljson output parsing error: {}\n{}",
+                    error,
+                    revive_common::deserialize_from_slice::<serde_json::Value>(out.as_bytes())
+                        .map(|json| serde_json::to_string_pretty(&json).expect("Always valid"))
+                        .unwrap_or_else(|_| String::from_utf8_lossy(out.as_bytes()).to_string()),
+                )
+            })?;
+        output.preprocess_ast(suppressed_warnings.as_slice())?;
+
+        Ok(output)
+    }
+
+    /// The `solc --combined-json abi,hashes...` mirror.
+    fn combined_json(
+        &self,
+        _paths: &[PathBuf],
+        _combined_json_argument: &str,
+    ) -> anyhow::Result<CombinedJson> {
+        anyhow::bail!("Combined JSON output is not supported with solJson");
+    }
+
+    /// The `solc` Yul validator.
+    fn validate_yul(&self, _path: &Path) -> anyhow::Result<()> {
+        anyhow::bail!("Yul validation is not supported with solJson");
+    }
+
+    /// The `solc --version` mini-parser.
+    fn version(&mut self) -> anyhow::Result<Version> {
+        let version = self.runtime.version()?;
+        let long = version.clone();
+        let default: semver::Version = version
+            .split('+')
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Soljson version parsing: metadata dropping"))?
+            .parse()
+            .map_err(|error| anyhow::anyhow!("Soljson version parsing: {}", error))?;
+        let l2_revision: Option<semver::Version> = version
+            .split('-')
+            .nth(1)
+            .and_then(|version| version.parse().ok());
+        Ok(Version::new(long, default, l2_revision))
+    }
+}
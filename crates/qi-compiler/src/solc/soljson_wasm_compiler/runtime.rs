@@ -0,0 +1,586 @@
+//! The subset of the Emscripten runtime used by `soljson`.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use wasmtime::AsContext;
+use wasmtime::AsContextMut;
+use wasmtime::Caller;
+use wasmtime::Engine;
+use wasmtime::Extern;
+use wasmtime::ExternType;
+use wasmtime::Func;
+use wasmtime::FuncType;
+use wasmtime::Instance;
+use wasmtime::Memory;
+use wasmtime::Module;
+use wasmtime::Ref;
+use wasmtime::Store;
+use wasmtime::Table;
+use wasmtime::Val;
+use wasmtime::ValType;
+
+use super::file_reader::FileReader;
+use super::glue::Glue;
+
+/// The exports called by the runtime, with the alternative names used by Emscripten versions.
+const EXPORTS: [&[&str]; 12] = [
+    &["solidity_version"],
+    &["solidity_compile"],
+    &["solidity_alloc"],
+    &["solidity_free"],
+    &["solidity_reset"],
+    &["setThrew"],
+    &["stackSave", "emscripten_stack_get_current"],
+    &["stackRestore", "_emscripten_stack_restore"],
+    &["__cxa_can_catch"],
+    &["__cxa_is_pointer_type"],
+    &["__cxa_increment_exception_refcount"],
+    &["__cxa_decrement_exception_refcount"],
+];
+
+/// The size of the `__cxa_exception` header preceding the thrown object, since Emscripten 3.1.
+const EXCEPTION_HEADER_SIZE: u32 = 24;
+
+/// The `ENOSYS` error number.
+const ENOSYS: i32 = 52;
+
+/// The C++ exception or `longjmp` unwinding through the WebAssembly frames.
+/// It is caught by the nearest `invoke_*` import, just like the JavaScript exception in the glue.
+#[derive(Debug)]
+struct Unwind;
+
+impl std::fmt::Display for Unwind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "soljson threw an uncaught exception")
+    }
+}
+
+impl std::error::Error for Unwind {}
+
+/// The host state of the `soljson` instance.
+#[derive(Default)]
+pub struct State {
+    /// The linear memory.
+    memory: Option<Memory>,
+    /// The indirect function table.
+    table: Option<Table>,
+    /// The exports called by the runtime, by the first name of their `EXPORTS` entry.
+    exports: BTreeMap<&'static str, Func>,
+    /// The file reader of the current compilation.
+    file_reader: FileReader,
+    /// The last thrown exception.
+    exception_last: u32,
+    /// The exceptions being handled.
+    exceptions_caught: Vec<u32>,
+    /// The number of the exceptions not caught yet.
+    exceptions_uncaught: i32,
+    /// The high 32 bits of the legalized 64-bit values.
+    temp_ret0: i32,
+}
+
+/// The `soljson` instance.
+pub struct Runtime {
+    /// The store owning the instance.
+    store: Store<State>,
+    /// The import callback table index.
+    callback: i32,
+}
+
+impl Runtime {
+    /// The maximum WebAssembly stack size, large enough for the `solc` recursive descent.
+    const MAX_STACK_SIZE: usize = 4 * 1024 * 1024;
+
+    /// Compiles and instantiates the `soljson` build, running its static constructors.
+    pub fn new(glue: &Glue) -> anyhow::Result<Self> {
+        let mut config = wasmtime::Config::new();
+        config.wasm_backtrace(false);
+        config.max_wasm_stack(Self::MAX_STACK_SIZE);
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, glue.wasm.as_slice())
+            .map_err(|error| anyhow::anyhow!("soljson WebAssembly compiling error: {error}"))?;
+
+        let mut store = Store::new(&engine, State::default());
+        let mut imports = Vec::new();
+        for import in module.imports() {
+            let name = glue.import_name(import.name());
+            let import = match import.ty() {
+                ExternType::Func(func_type) => {
+                    Extern::Func(host_function(&mut store, name.to_owned(), func_type))
+                }
+                ExternType::Memory(memory_type) => {
+                    let memory = Memory::new(&mut store, memory_type)?;
+                    store.data_mut().memory = Some(memory);
+                    Extern::Memory(memory)
+                }
+                ExternType::Table(table_type) => {
+                    let table = Table::new(&mut store, table_type, Ref::Func(None))?;
+                    store.data_mut().table = Some(table);
+                    Extern::Table(table)
+                }
+                _ => anyhow::bail!("soljson import `{name}` is not supported"),
+            };
+            imports.push(import);
+        }
+        let instance = Instance::new(&mut store, &module, imports.as_slice())
+            .map_err(|error| anyhow::anyhow!("soljson instantiation error: {error}"))?;
+
+        let exports = instance.exports(&mut store).collect::<Vec<_>>();
+        let memory = exports
+            .iter()
+            .find_map(|export| export.clone().into_memory());
+        let table = exports
+            .iter()
+            .find_map(|export| export.clone().into_table());
+        let state = store.data_mut();
+        state.memory = state.memory.or(memory);
+        state.table = state.table.or(table);
+        if state.memory.is_none() || state.table.is_none() {
+            anyhow::bail!("soljson does not export its memory or function table");
+        }
+        for names in EXPORTS.into_iter() {
+            let func = names
+                .iter()
+                .find_map(|name| instance.get_func(&mut store, glue.export_name(name)));
+            if let Some(func) = func {
+                store.data_mut().exports.insert(names[0], func);
+            }
+        }
+
+        if let Some(constructors) = instance.get_func(&mut store, glue.constructors_name()) {
+            constructors.call(&mut store, &[], &mut [])?;
+        }
+
+        let callback = Func::wrap(&mut store, read_callback);
+        let callback = add_function(&mut store, callback)?;
+        Ok(Self { store, callback })
+    }
+
+    /// Returns the `solc` version.
+    pub fn version(&mut self) -> anyhow::Result<String> {
+        let pointer = call(&mut self.store, "solidity_version", &[])?;
+        read_string(&self.store, pointer)
+    }
+
+    /// Compiles the standard JSON input, resolving the imports with the `file_reader`.
+    pub fn compile(&mut self, input: &str, file_reader: FileReader) -> anyhow::Result<String> {
+        self.store.data_mut().file_reader = file_reader;
+
+        let input = allocate_string(&mut self.store, input)?;
+        let output = call(
+            &mut self.store,
+            "solidity_compile",
+            &[Val::I32(input as i32), Val::I32(self.callback), Val::I32(0)],
+        )?;
+        let output = read_string(&self.store, output);
+
+        call(&mut self.store, "solidity_free", &[Val::I32(input as i32)])?;
+        call(&mut self.store, "solidity_reset", &[])?;
+        output
+    }
+}
+
+/// The `solc` import callback, called with the callback kind and the source unit name.
+/// The file contents or the error message are allocated in the `soljson` memory.
+fn read_callback(
+    mut caller: Caller<'_, State>,
+    _context: i32,
+    kind: i32,
+    data: i32,
+    contents_pointer: i32,
+    error_pointer: i32,
+) -> anyhow::Result<()> {
+    let kind = read_string(&caller, kind as u32)?;
+    let data = read_string(&caller, data as u32)?;
+    let result = match kind.as_str() {
+        "source" => caller.data().file_reader.read(data.as_str()),
+        kind => Err(format!("Unsupported callback kind: {kind}")),
+    };
+
+    let (string, pointer) = match result {
+        Ok(contents) => (contents, contents_pointer),
+        Err(error) => (error, error_pointer),
+    };
+    let string = allocate_string(&mut caller, string.as_str())?;
+    write_u32(&mut caller, pointer as u32, string)
+}
+
+/// Creates the import, implemented with the Emscripten runtime function of the same name.
+/// The functions which `solc` is not expected to call trap.
+fn host_function(store: &mut Store<State>, name: String, func_type: FuncType) -> Func {
+    let results = func_type.results().collect::<Vec<_>>();
+    Func::new(store, func_type, move |mut caller, params, outputs| {
+        for (output, result) in outputs.iter_mut().zip(results.iter()) {
+            *output = default_value(result);
+        }
+        let output = emscripten_function(&mut caller, name.as_str(), params, outputs)?;
+        if let (Some(output), Some(value)) = (outputs.first_mut(), output) {
+            *output = Val::I32(value);
+        }
+        Ok(())
+    })
+}
+
+/// Runs the Emscripten runtime function, returning its `i32` result if it has one.
+fn emscripten_function(
+    caller: &mut Caller<'_, State>,
+    name: &str,
+    params: &[Val],
+    outputs: &mut [Val],
+) -> anyhow::Result<Option<i32>> {
+    let param =
+        |index: usize| -> u32 { params.get(index).and_then(Val::i32).unwrap_or_default() as u32 };
+
+    match name {
+        "emscripten_resize_heap" => {
+            let memory = memory(&*caller)?;
+            let size = memory.data_size(&caller) as u64;
+            let requested = param(0) as u64;
+            if requested <= size {
+                return Ok(Some(1));
+            }
+            let pages = (requested - size).div_ceil(memory.page_size(&caller));
+            Ok(Some(memory.grow(&mut *caller, pages).is_ok() as i32))
+        }
+        "emscripten_memcpy_big" | "emscripten_memcpy_js" | "_emscripten_memcpy_js" => {
+            let (destination, source, length) =
+                (param(0) as usize, param(1) as usize, param(2) as usize);
+            let memory = memory(&*caller)?;
+            let data = memory.data_mut(&mut *caller);
+            anyhow::ensure!(
+                source.max(destination) + length <= data.len(),
+                "soljson memory access out of bounds"
+            );
+            data.copy_within(source..source + length, destination);
+            Ok(None)
+        }
+        "abort" | "_abort_js" => anyhow::bail!("soljson aborted"),
+        "__assert_fail" => anyhow::bail!(
+            "soljson assertion `{}` failed at {}:{} in `{}`",
+            read_string(&*caller, param(0))?,
+            read_string(&*caller, param(1))?,
+            param(2),
+            read_string(&*caller, param(3))?,
+        ),
+        "fd_write" => {
+            let mut bytes = Vec::new();
+            for index in 0..param(2) {
+                let vector = param(1) + index * 8;
+                let (pointer, length) =
+                    (read_u32(&*caller, vector)?, read_u32(&*caller, vector + 4)?);
+                let mut buffer = vec![0; length as usize];
+                memory(&*caller)?.read(&*caller, pointer as usize, buffer.as_mut_slice())?;
+                bytes.extend(buffer);
+            }
+            // The standard output is reserved for the compiler output.
+            std::io::stderr().write_all(bytes.as_slice())?;
+            write_u32(&mut *caller, param(3), bytes.len() as u32)?;
+            Ok(Some(0))
+        }
+        "environ_sizes_get" => {
+            write_u32(&mut *caller, param(0), 0)?;
+            write_u32(&mut *caller, param(1), 0)?;
+            Ok(Some(0))
+        }
+        "environ_get" => Ok(Some(0)),
+        name if name.starts_with("fd_") => Ok(Some(ENOSYS)),
+        name if name.starts_with("__syscall_") => Ok(Some(-ENOSYS)),
+        "getTempRet0" => Ok(Some(caller.data().temp_ret0)),
+        "setTempRet0" => {
+            caller.data_mut().temp_ret0 = param(0) as i32;
+            Ok(None)
+        }
+        "llvm_eh_typeid_for" => Ok(Some(param(0) as i32)),
+        "__cxa_uncaught_exceptions" => Ok(Some(caller.data().exceptions_uncaught)),
+        "__cxa_throw" => {
+            let (exception, r#type, destructor) = (param(0), param(1), param(2));
+            let header = exception - EXCEPTION_HEADER_SIZE;
+            write_u32(&mut *caller, header + 16, 0)?;
+            write_u32(&mut *caller, header + 4, r#type)?;
+            write_u32(&mut *caller, header + 8, destructor)?;
+            let state = caller.data_mut();
+            state.exception_last = exception;
+            state.exceptions_uncaught += 1;
+            Err(Unwind.into())
+        }
+        "__resumeException" => {
+            let state = caller.data_mut();
+            if state.exception_last == 0 {
+                state.exception_last = param(0);
+            }
+            Err(Unwind.into())
+        }
+        "__cxa_rethrow" => {
+            let exception = caller
+                .data_mut()
+                .exceptions_caught
+                .pop()
+                .ok_or_else(|| anyhow::anyhow!("soljson rethrew without an exception"))?;
+            let header = exception - EXCEPTION_HEADER_SIZE;
+            if read_u8(&*caller, header + 13)? == 0 {
+                write_u8(&mut *caller, header + 13, 1)?;
+                write_u8(&mut *caller, header + 12, 0)?;
+                let state = caller.data_mut();
+                state.exceptions_caught.push(exception);
+                state.exceptions_uncaught += 1;
+            }
+            caller.data_mut().exception_last = exception;
+            Err(Unwind.into())
+        }
+        name if name.starts_with("__cxa_find_matching_catch_") => {
+            let exception = caller.data().exception_last;
+            let (r#type, exception) = find_matching_catch(caller, exception, params)?;
+            caller.data_mut().temp_ret0 = r#type as i32;
+            Ok(Some(exception as i32))
+        }
+        "__cxa_begin_catch" => {
+            let exception = param(0);
+            let header = exception - EXCEPTION_HEADER_SIZE;
+            if read_u8(&*caller, header + 12)? == 0 {
+                write_u8(&mut *caller, header + 12, 1)?;
+                caller.data_mut().exceptions_uncaught -= 1;
+            }
+            write_u8(&mut *caller, header + 13, 0)?;
+            caller.data_mut().exceptions_caught.push(exception);
+            call_optional(
+                &mut *caller,
+                "__cxa_increment_exception_refcount",
+                &[Val::I32(exception as i32)],
+            )?;
+
+            let r#type = read_u32(&*caller, header + 4)?;
+            let is_pointer = call(
+                &mut *caller,
+                "__cxa_is_pointer_type",
+                &[Val::I32(r#type as i32)],
+            )?;
+            let pointer = if is_pointer != 0 {
+                read_u32(&*caller, exception)?
+            } else {
+                match read_u32(&*caller, header + 16)? {
+                    0 => exception,
+                    adjusted => adjusted,
+                }
+            };
+            Ok(Some(pointer as i32))
+        }
+        "__cxa_end_catch" => {
+            call(&mut *caller, "setThrew", &[Val::I32(0), Val::I32(0)])?;
+            if let Some(exception) = caller.data_mut().exceptions_caught.pop() {
+                call_optional(
+                    &mut *caller,
+                    "__cxa_decrement_exception_refcount",
+                    &[Val::I32(exception as i32)],
+                )?;
+            }
+            caller.data_mut().exception_last = 0;
+            Ok(None)
+        }
+        "_emscripten_throw_longjmp" | "emscripten_longjmp" => Err(Unwind.into()),
+        name if name.starts_with("invoke_") => {
+            invoke(caller, params, outputs)?;
+            Ok(None)
+        }
+        name => anyhow::bail!("soljson called the unsupported import `{name}`"),
+    }
+}
+
+/// Calls the function from the table, catching the exceptions as the `invoke_*` glue does.
+/// The first parameter is the table index, and the rest are passed to the function.
+fn invoke(
+    caller: &mut Caller<'_, State>,
+    params: &[Val],
+    outputs: &mut [Val],
+) -> anyhow::Result<()> {
+    let index = params.first().and_then(Val::i32).unwrap_or_default() as u32;
+    let table = caller
+        .data()
+        .table
+        .ok_or_else(|| anyhow::anyhow!("soljson function table is not available"))?;
+    let function = table
+        .get(&mut *caller, index as u64)
+        .and_then(|function| function.as_func().flatten().copied())
+        .ok_or_else(|| anyhow::anyhow!("soljson called a null function pointer {index}"))?;
+
+    let defaults = outputs.to_vec();
+    let stack = call(&mut *caller, "stackSave", &[])?;
+    match function.call(&mut *caller, &params[1..], outputs) {
+        Ok(()) => Ok(()),
+        Err(error) if error.is::<Unwind>() => {
+            call(&mut *caller, "stackRestore", &[Val::I32(stack as i32)])?;
+            call(&mut *caller, "setThrew", &[Val::I32(1), Val::I32(0)])?;
+            outputs.clone_from_slice(defaults.as_slice());
+            Ok(())
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Finds the first of the catch clause `types` matching the thrown exception.
+/// Returns the matching type, or the thrown type if none matches, with the exception pointer.
+fn find_matching_catch(
+    caller: &mut Caller<'_, State>,
+    exception: u32,
+    types: &[Val],
+) -> anyhow::Result<(u32, u32)> {
+    if exception == 0 {
+        return Ok((0, 0));
+    }
+    let header = exception - EXCEPTION_HEADER_SIZE;
+    write_u32(&mut *caller, header + 16, exception)?;
+    let thrown_type = read_u32(&*caller, header + 4)?;
+    if thrown_type == 0 {
+        return Ok((0, exception));
+    }
+
+    for caught_type in types.iter().filter_map(Val::i32) {
+        if caught_type == 0 || caught_type as u32 == thrown_type {
+            break;
+        }
+        let can_catch = call(
+            &mut *caller,
+            "__cxa_can_catch",
+            &[
+                Val::I32(caught_type),
+                Val::I32(thrown_type as i32),
+                Val::I32((header + 16) as i32),
+            ],
+        )?;
+        if can_catch != 0 {
+            return Ok((caught_type as u32, exception));
+        }
+    }
+    Ok((thrown_type, exception))
+}
+
+/// Adds the function to the table, returning its index.
+/// The table is grown if possible, and the first empty slot is used otherwise.
+fn add_function(store: &mut Store<State>, function: Func) -> anyhow::Result<i32> {
+    let table = store.data().table.expect("Always exists");
+    if let Ok(index) = table.grow(&mut *store, 1, Ref::Func(Some(function))) {
+        return Ok(index as i32);
+    }
+
+    let index = (1..table.size(&*store))
+        .find(|index| {
+            table
+                .get(&mut *store, *index)
+                .is_some_and(|function| function.is_null())
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!("soljson function table has no space for the import callback")
+        })?;
+    table.set(&mut *store, index, Ref::Func(Some(function)))?;
+    Ok(index as i32)
+}
+
+/// Calls the export, returning its `i32` result, or zero if it has none.
+fn call(
+    mut store: impl AsContextMut<Data = State>,
+    name: &str,
+    params: &[Val],
+) -> anyhow::Result<u32> {
+    let function = store
+        .as_context()
+        .data()
+        .exports
+        .get(name)
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("soljson does not export `{name}`"))?;
+    let mut results = function
+        .ty(&store)
+        .results()
+        .map(|result| default_value(&result))
+        .collect::<Vec<_>>();
+    function.call(&mut store, params, results.as_mut_slice())?;
+    Ok(results.first().and_then(Val::i32).unwrap_or_default() as u32)
+}
+
+/// Calls the export if it exists.
+fn call_optional(
+    store: impl AsContextMut<Data = State>,
+    name: &str,
+    params: &[Val],
+) -> anyhow::Result<()> {
+    if store.as_context().data().exports.contains_key(name) {
+        call(store, name, params)?;
+    }
+    Ok(())
+}
+
+/// Copies the string to the memory allocated by `solc`, returning the pointer.
+fn allocate_string(
+    mut store: impl AsContextMut<Data = State>,
+    string: &str,
+) -> anyhow::Result<u32> {
+    let pointer = call(
+        &mut store,
+        "solidity_alloc",
+        &[Val::I32(string.len() as i32 + 1)],
+    )?;
+    let mut bytes = string.as_bytes().to_vec();
+    bytes.push(0);
+    memory(&store)?.write(&mut store, pointer as usize, bytes.as_slice())?;
+    Ok(pointer)
+}
+
+/// Reads the null-terminated string.
+fn read_string(store: impl AsContext<Data = State>, pointer: u32) -> anyhow::Result<String> {
+    let data = memory(&store)?.data(&store);
+    let bytes = data
+        .get(pointer as usize..)
+        .ok_or_else(|| anyhow::anyhow!("soljson memory access out of bounds"))?;
+    let length = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .ok_or_else(|| anyhow::anyhow!("soljson string is not terminated"))?;
+    Ok(String::from_utf8_lossy(&bytes[..length]).into_owned())
+}
+
+/// Reads the little-endian `u32`.
+fn read_u32(store: impl AsContext<Data = State>, address: u32) -> anyhow::Result<u32> {
+    let mut bytes = [0; 4];
+    memory(&store)?.read(&store, address as usize, &mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Writes the little-endian `u32`.
+fn write_u32(
+    mut store: impl AsContextMut<Data = State>,
+    address: u32,
+    value: u32,
+) -> anyhow::Result<()> {
+    memory(&store)?.write(&mut store, address as usize, &value.to_le_bytes())?;
+    Ok(())
+}
+
+/// Reads the byte.
+fn read_u8(store: impl AsContext<Data = State>, address: u32) -> anyhow::Result<u8> {
+    let mut bytes = [0; 1];
+    memory(&store)?.read(&store, address as usize, &mut bytes)?;
+    Ok(bytes[0])
+}
+
+/// Writes the byte.
+fn write_u8(
+    mut store: impl AsContextMut<Data = State>,
+    address: u32,
+    value: u8,
+) -> anyhow::Result<()> {
+    memory(&store)?.write(&mut store, address as usize, &[value])?;
+    Ok(())
+}
+
+/// Returns the linear memory.
+fn memory(store: impl AsContext<Data = State>) -> anyhow::Result<Memory> {
+    store
+        .as_context()
+        .data()
+        .memory
+        .ok_or_else(|| anyhow::anyhow!("soljson memory is not available"))
+}
+
+/// Returns the zero value of the type.
+fn default_value(r#type: &ValType) -> Val {
+    Val::default_for_ty(r#type).unwrap_or(Val::I32(0))
+}
@@ -3,6 +3,8 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::error::Error;
+
 /// The Solidity compiler version.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Version {
@@ -38,22 +40,21 @@ impl Version {
     }
 
     pub fn validate(self, include_paths: &[String]) -> anyhow::Result<Self> {
-        if self.default < super::FIRST_SUPPORTED_VERSION {
-            anyhow::bail!(
-                "`solc` versions <{} are not supported, found {}",
-                super::FIRST_SUPPORTED_VERSION,
-                self.default
-            );
-        }
-        if self.default > super::LAST_SUPPORTED_VERSION {
-            anyhow::bail!(
-                "`solc` versions >{} are not supported, found {}",
-                super::LAST_SUPPORTED_VERSION,
-                self.default
-            );
+        if self.default < super::FIRST_SUPPORTED_VERSION
+            || self.default > super::LAST_SUPPORTED_VERSION
+        {
+            return Err(Error::UnsupportedSolcVersion {
+                found: self.default,
+                first_supported: super::FIRST_SUPPORTED_VERSION,
+                last_supported: super::LAST_SUPPORTED_VERSION,
+            }
+            .into());
         }
         if !include_paths.is_empty() && self.default < super::FIRST_INCLUDE_PATH_VERSION {
-            anyhow::bail!("--include-path is not supported in solc {}", self.default);
+            return Err(Error::IncludePathUnsupported {
+                found: self.default,
+            }
+            .into());
         }
 
         Ok(self)
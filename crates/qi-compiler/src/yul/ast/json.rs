@@ -0,0 +1,536 @@
+//! The Yul tree deserialization from the `solc` `irOptimizedAst` JSON output.
+//! The `nativeSrc` locations point into the optimized Yul IR text, so the ranges match the ones
+//! of the text parser. The object, code and data nodes have no locations, so their ranges only
+//! cover the code block. `solc` does not emit the data names either, and the only data it generates
+//! is the contract metadata, so an unnamed data node is named `.metadata` like in the IR text.
+
+use rowan::TextRange;
+use rowan::TextSize;
+use serde::Deserialize;
+
+use super::*;
+
+/// The name `solc` gives to the metadata data node.
+pub const METADATA_NAME: &str = ".metadata";
+
+/// Deserializes the Yul object from the `solc` JSON AST.
+pub fn object(json: &serde_json::Value) -> anyhow::Result<Object> {
+    let node = ObjectNode::deserialize(json)?;
+    let ObjectNode::YulObject {
+        name,
+        code,
+        sub_objects,
+    } = node
+    else {
+        anyhow::bail!("Expected a `YulObject` node");
+    };
+    object_node(name, code, sub_objects)
+}
+
+/// The object or data node.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "nodeType")]
+enum ObjectNode {
+    /// The object.
+    YulObject {
+        /// The object name.
+        name: String,
+        /// The object code.
+        code: CodeNode,
+        /// The inner objects and data.
+        #[serde(default, rename = "subObjects")]
+        sub_objects: Vec<ObjectNode>,
+    },
+    /// The data.
+    YulData {
+        /// The data name, which `solc` does not emit.
+        #[serde(default)]
+        name: Option<String>,
+        /// The data bytes, hex-encoded.
+        value: String,
+    },
+}
+
+/// The object code node.
+#[derive(Debug, Deserialize)]
+struct CodeNode {
+    /// The code block.
+    block: Node,
+}
+
+/// The code node, as emitted by the `solc` `AsmJsonConverter`.
+/// The variants are named after the `nodeType` values.
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Deserialize)]
+#[serde(tag = "nodeType", rename_all_fields = "camelCase")]
+enum Node {
+    YulBlock {
+        native_src: String,
+        statements: Vec<Node>,
+    },
+    YulFunctionDefinition {
+        native_src: String,
+        name: String,
+        #[serde(default)]
+        parameters: Vec<Node>,
+        #[serde(default)]
+        return_variables: Vec<Node>,
+        body: Box<Node>,
+    },
+    YulVariableDeclaration {
+        native_src: String,
+        variables: Vec<Node>,
+        value: Option<Box<Node>>,
+    },
+    YulAssignment {
+        native_src: String,
+        variable_names: Vec<Node>,
+        value: Box<Node>,
+    },
+    YulIf {
+        native_src: String,
+        condition: Box<Node>,
+        body: Box<Node>,
+    },
+    YulExpressionStatement {
+        expression: Box<Node>,
+    },
+    YulSwitch {
+        native_src: String,
+        expression: Box<Node>,
+        cases: Vec<Node>,
+    },
+    YulCase {
+        native_src: String,
+        value: CaseValue,
+        body: Box<Node>,
+    },
+    YulForLoop {
+        native_src: String,
+        pre: Box<Node>,
+        condition: Box<Node>,
+        post: Box<Node>,
+        body: Box<Node>,
+    },
+    YulBreak {
+        native_src: String,
+    },
+    YulContinue {
+        native_src: String,
+    },
+    YulLeave {
+        native_src: String,
+    },
+    YulFunctionCall {
+        native_src: String,
+        function_name: Box<Node>,
+        arguments: Vec<Node>,
+    },
+    YulIdentifier {
+        native_src: String,
+        name: String,
+    },
+    YulTypedName {
+        native_src: String,
+        name: String,
+        #[serde(default)]
+        r#type: String,
+    },
+    YulLiteral {
+        native_src: String,
+        kind: String,
+        value: Option<String>,
+        hex_value: Option<String>,
+        #[serde(default)]
+        r#type: String,
+    },
+}
+
+/// The `switch` case value: a literal, or `"default"`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CaseValue {
+    /// The `default` case, with the `"default"` value.
+    Default(String),
+    /// The case literal.
+    Literal(Box<Node>),
+}
+
+fn object_node(
+    object_name: String,
+    code: CodeNode,
+    sub_objects: Vec<ObjectNode>,
+) -> anyhow::Result<Object> {
+    let block = block(code.block)?;
+    let range = block.range;
+    let mut objects = Vec::new();
+    let mut data = Vec::new();
+    for node in sub_objects.into_iter() {
+        match node {
+            ObjectNode::YulObject {
+                name,
+                code,
+                sub_objects,
+            } => objects.push(object_node(name, code, sub_objects)?),
+            ObjectNode::YulData { name, value } => {
+                let name = match name {
+                    Some(name) => name,
+                    None if data.iter().all(|data: &Data| data.name != METADATA_NAME) => {
+                        METADATA_NAME.to_owned()
+                    }
+                    None => anyhow::bail!("Object `{object_name}` has several unnamed data"),
+                };
+                data.push(Data {
+                    name,
+                    value: format!("hex\"{value}\""),
+                    range,
+                });
+            }
+        }
+    }
+    Ok(Object {
+        name: object_name,
+        code: Code { block, range },
+        objects,
+        data,
+        range,
+    })
+}
+
+fn block(node: Node) -> anyhow::Result<Block> {
+    let Node::YulBlock {
+        native_src,
+        statements,
+    } = node
+    else {
+        anyhow::bail!("Expected a `YulBlock` node");
+    };
+    Ok(Block {
+        statements: statements
+            .into_iter()
+            .map(statement)
+            .collect::<anyhow::Result<_>>()?,
+        range: range(native_src.as_str())?,
+    })
+}
+
+fn statement(node: Node) -> anyhow::Result<Statement> {
+    let statement = match node {
+        node @ Node::YulBlock { .. } => Statement::Block(block(node)?),
+        Node::YulFunctionDefinition {
+            native_src,
+            name,
+            parameters,
+            return_variables,
+            body,
+        } => {
+            let range = range(native_src.as_str())?;
+            let name_start = range.start() + TextSize::of("function ");
+            Statement::FunctionDefinition(FunctionDefinition {
+                name: Identifier {
+                    range: TextRange::at(name_start, TextSize::of(name.as_str())),
+                    name,
+                },
+                parameters: parameters
+                    .into_iter()
+                    .map(typed_identifier)
+                    .collect::<anyhow::Result<_>>()?,
+                returns: return_variables
+                    .into_iter()
+                    .map(typed_identifier)
+                    .collect::<anyhow::Result<_>>()?,
+                body: block(*body)?,
+                range,
+            })
+        }
+        Node::YulVariableDeclaration {
+            native_src,
+            variables,
+            value,
+        } => Statement::VariableDeclaration(VariableDeclaration {
+            variables: variables
+                .into_iter()
+                .map(typed_identifier)
+                .collect::<anyhow::Result<_>>()?,
+            value: value.map(|value| expression(*value)).transpose()?,
+            range: range(native_src.as_str())?,
+        }),
+        Node::YulAssignment {
+            native_src,
+            variable_names,
+            value,
+        } => Statement::Assignment(Assignment {
+            variables: variable_names
+                .into_iter()
+                .map(identifier)
+                .collect::<anyhow::Result<_>>()?,
+            value: expression(*value)?,
+            range: range(native_src.as_str())?,
+        }),
+        Node::YulIf {
+            native_src,
+            condition,
+            body,
+        } => Statement::If(If {
+            condition: expression(*condition)?,
+            body: block(*body)?,
+            range: range(native_src.as_str())?,
+        }),
+        Node::YulExpressionStatement {
+            expression: node, ..
+        } => Statement::Expression(expression(*node)?),
+        Node::YulSwitch {
+            native_src,
+            expression: node,
+            cases: case_nodes,
+        } => {
+            let mut cases = Vec::new();
+            let mut default = None;
+            for case in case_nodes.into_iter() {
+                let Node::YulCase {
+                    native_src,
+                    value,
+                    body,
+                } = case
+                else {
+                    anyhow::bail!("Expected a `YulCase` node");
+                };
+                match value {
+                    CaseValue::Default(value) if value == "default" => {
+                        default = Some(block(*body)?)
+                    }
+                    CaseValue::Default(value) => anyhow::bail!("Invalid case value `{value}`"),
+                    CaseValue::Literal(value) => cases.push(Case {
+                        value: literal(*value)?,
+                        body: block(*body)?,
+                        range: range(native_src.as_str())?,
+                    }),
+                }
+            }
+            Statement::Switch(Switch {
+                expression: expression(*node)?,
+                cases,
+                default,
+                range: range(native_src.as_str())?,
+            })
+        }
+        Node::YulForLoop {
+            native_src,
+            pre,
+            condition,
+            post,
+            body,
+        } => Statement::ForLoop(ForLoop {
+            initializer: block(*pre)?,
+            condition: expression(*condition)?,
+            finalizer: block(*post)?,
+            body: block(*body)?,
+            range: range(native_src.as_str())?,
+        }),
+        Node::YulBreak { native_src } => Statement::Break(range(native_src.as_str())?),
+        Node::YulContinue { native_src } => Statement::Continue(range(native_src.as_str())?),
+        Node::YulLeave { native_src } => Statement::Leave(range(native_src.as_str())?),
+        _ => anyhow::bail!("Expected a statement node"),
+    };
+    Ok(statement)
+}
+
+fn expression(node: Node) -> anyhow::Result<Expression> {
+    let expression = match node {
+        Node::YulFunctionCall {
+            native_src,
+            function_name,
+            arguments,
+        } => Expression::FunctionCall(FunctionCall {
+            name: identifier(*function_name)?,
+            arguments: arguments
+                .into_iter()
+                .map(expression)
+                .collect::<anyhow::Result<_>>()?,
+            range: range(native_src.as_str())?,
+        }),
+        node @ Node::YulIdentifier { .. } => Expression::Identifier(identifier(node)?),
+        node @ Node::YulLiteral { .. } => Expression::Literal(literal(node)?),
+        _ => anyhow::bail!("Expected an expression node"),
+    };
+    Ok(expression)
+}
+
+fn literal(node: Node) -> anyhow::Result<Literal> {
+    let Node::YulLiteral {
+        native_src,
+        kind,
+        value,
+        hex_value,
+        r#type,
+    } = node
+    else {
+        anyhow::bail!("Expected a `YulLiteral` node");
+    };
+    let range = range(native_src.as_str())?;
+    let (kind, value) = match (kind.as_str(), value) {
+        ("number", Some(value)) if value.starts_with("0x") => (LiteralKind::Hexadecimal, value),
+        ("number", Some(value)) => (LiteralKind::Decimal, value),
+        ("bool", Some(value)) => (LiteralKind::Boolean, value),
+        ("string", value) => {
+            let bytes = match hex_value {
+                Some(hex_value) => hex::decode(hex_value)?,
+                None => value.unwrap_or_default().into_bytes(),
+            };
+            (LiteralKind::String, quote(bytes.as_slice()))
+        }
+        (kind, _) => anyhow::bail!("Invalid literal kind `{kind}`"),
+    };
+    Ok(Literal {
+        kind,
+        value,
+        r#type: Some(r#type)
+            .filter(|name| !name.is_empty())
+            .map(|name| TypeName { name, range }),
+        range,
+    })
+}
+
+fn typed_identifier(node: Node) -> anyhow::Result<TypedIdentifier> {
+    let Node::YulTypedName {
+        native_src,
+        name,
+        r#type,
+    } = node
+    else {
+        anyhow::bail!("Expected a `YulTypedName` node");
+    };
+    let range = range(native_src.as_str())?;
+    Ok(TypedIdentifier {
+        identifier: Identifier { name, range },
+        r#type: Some(r#type)
+            .filter(|name| !name.is_empty())
+            .map(|name| TypeName { name, range }),
+        range,
+    })
+}
+
+fn identifier(node: Node) -> anyhow::Result<Identifier> {
+    let Node::YulIdentifier { native_src, name } = node else {
+        anyhow::bail!("Expected a `YulIdentifier` node");
+    };
+    Ok(Identifier {
+        name,
+        range: range(native_src.as_str())?,
+    })
+}
+
+/// Parses the `<start>:<length>:<source index>` location.
+fn range(native_src: &str) -> anyhow::Result<TextRange> {
+    let mut parts = native_src.split(':');
+    let mut next = || -> anyhow::Result<u32> {
+        Ok(parts
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Invalid location `{native_src}`"))?
+            .parse::<u32>()?)
+    };
+    let start = next()?;
+    let length = next()?;
+    Ok(TextRange::at(start.into(), length.into()))
+}
+
+/// Quotes the string bytes the way `solc` prints them.
+fn quote(bytes: &[u8]) -> String {
+    let mut quoted = String::from("\"");
+    for byte in bytes.iter() {
+        match byte {
+            b'\\' => quoted.push_str("\\\\"),
+            b'"' => quoted.push_str("\\\""),
+            b'\n' => quoted.push_str("\\n"),
+            b'\r' => quoted.push_str("\\r"),
+            b'\t' => quoted.push_str("\\t"),
+            byte if byte.is_ascii_graphic() || *byte == b' ' => quoted.push(*byte as char),
+            byte => quoted.push_str(format!("\\x{byte:02x}").as_str()),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::ast::Object;
+
+    /// Checks that the objects have the same names, code trees and data, and so do their inner objects.
+    /// The object, code and data ranges are not compared, as the JSON nodes have no locations.
+    fn assert_same_object(json: &Object, text: &Object) {
+        assert_eq!(json.name, text.name);
+        assert_eq!(json.code.block, text.code.block);
+        let data = |object: &Object| {
+            object
+                .data
+                .iter()
+                .map(|data| (data.name.clone(), data.value.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(data(json), data(text));
+        assert_eq!(json.objects.len(), text.objects.len());
+        for (json, text) in json.objects.iter().zip(text.objects.iter()) {
+            assert_same_object(json, text);
+        }
+    }
+
+    #[test]
+    fn names_the_metadata_data() {
+        let text = r#"object "A" {
+    code { }
+    object "A_deployed" {
+        code { }
+        data ".metadata" hex"a264"
+    }
+}
+"#;
+        let block = |start: usize| {
+            serde_json::json!({
+                "nodeType": "YulBlock",
+                "nativeSrc": format!("{}:3:0", start + "code ".len()),
+                "statements": [],
+            })
+        };
+        let mut json = serde_json::json!({
+            "nodeType": "YulObject",
+            "name": "A",
+            "code": { "nodeType": "YulCode", "block": block(text.find("code").expect("Always valid")) },
+            "subObjects": [{
+                "nodeType": "YulObject",
+                "name": "A_deployed",
+                "code": { "nodeType": "YulCode", "block": block(text.rfind("code").expect("Always valid")) },
+                "subObjects": [{ "nodeType": "YulData", "value": "a264" }],
+            }],
+        });
+        assert_same_object(
+            &Object::from_solc_json(&json).expect("Always valid"),
+            &Object::parse(text).expect("Always valid"),
+        );
+
+        let data = json
+            .pointer_mut("/subObjects/0/subObjects")
+            .expect("Always valid");
+        data.as_array_mut()
+            .expect("Always valid")
+            .push(serde_json::json!({ "nodeType": "YulData", "value": "00" }));
+        assert_eq!(
+            Object::from_solc_json(&json)
+                .expect_err("Always invalid")
+                .to_string(),
+            "Object `A_deployed` has several unnamed data"
+        );
+    }
+
+    #[test]
+    fn matches_the_text_frontend() {
+        let text = include_str!("../../../tests/fixtures/Counter.yul");
+        let json: serde_json::Value =
+            serde_json::from_str(include_str!("../../../tests/fixtures/Counter.json"))
+                .expect("Always valid");
+        assert_same_object(
+            &Object::from_solc_json(&json).expect("Always valid"),
+            &Object::parse(text).expect("Always valid"),
+        );
+    }
+}
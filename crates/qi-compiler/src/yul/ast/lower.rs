@@ -0,0 +1,409 @@
+//! The lowering of the `rowan` syntax tree to the typed syntax tree.
+
+use rowan::TextRange;
+
+use crate::yul::parser::ParseError;
+use crate::yul::SyntaxKind;
+use crate::yul::SyntaxNode;
+use crate::yul::SyntaxToken;
+
+use super::*;
+
+/// Parses the Yul object or block.
+pub fn parse_source(text: &str) -> Result<Source, Vec<ParseError>> {
+    let parse = crate::yul::parser::parse(text);
+    if !parse.errors().is_empty() {
+        return Err(parse.errors().to_vec());
+    }
+    let root = parse.syntax();
+    let source = match root.kind() {
+        SyntaxKind::OBJECT => object(&root).map(Source::Object),
+        _ => block(&root).map(Source::Block),
+    };
+    source.ok_or_else(|| malformed(&root))
+}
+
+/// Parses the Yul object.
+pub fn parse_object(text: &str) -> Result<Object, Vec<ParseError>> {
+    let root = parse_root(text, SyntaxKind::OBJECT, "Expected `object`")?;
+    object(&root).ok_or_else(|| malformed(&root))
+}
+
+/// Parses the bare Yul block.
+pub fn parse_block(text: &str) -> Result<Block, Vec<ParseError>> {
+    let root = parse_root(text, SyntaxKind::BLOCK, "Expected `{`")?;
+    block(&root).ok_or_else(|| malformed(&root))
+}
+
+/// Parses the code and checks the root node kind.
+fn parse_root(text: &str, kind: SyntaxKind, message: &str) -> Result<SyntaxNode, Vec<ParseError>> {
+    let parse = crate::yul::parser::parse(text);
+    if !parse.errors().is_empty() {
+        return Err(parse.errors().to_vec());
+    }
+    let root = parse.syntax();
+    if root.kind() != kind {
+        return Err(vec![ParseError {
+            message: format!("{message}."),
+            range: significant_range(&root),
+        }]);
+    }
+    Ok(root)
+}
+
+/// The error for the trees which cannot be produced by the parser without errors.
+fn malformed(root: &SyntaxNode) -> Vec<ParseError> {
+    vec![ParseError {
+        message: "Malformed syntax tree.".to_owned(),
+        range: root.text_range(),
+    }]
+}
+
+fn object(node: &SyntaxNode) -> Option<Object> {
+    Some(Object {
+        name: unquote(token(node, SyntaxKind::STRING_LITERAL)?.text()),
+        code: child(node, SyntaxKind::CODE).and_then(|code| {
+            Some(Code {
+                block: block(&child(&code, SyntaxKind::BLOCK)?)?,
+                range: code.text_range(),
+            })
+        })?,
+        objects: children(node, SyntaxKind::OBJECT)
+            .map(|node| object(&node))
+            .collect::<Option<_>>()?,
+        data: children(node, SyntaxKind::DATA)
+            .map(|node| data(&node))
+            .collect::<Option<_>>()?,
+        range: significant_range(node),
+    })
+}
+
+fn data(node: &SyntaxNode) -> Option<Data> {
+    let mut literals = tokens(node).filter(|token| {
+        matches!(
+            token.kind(),
+            SyntaxKind::STRING_LITERAL | SyntaxKind::HEX_LITERAL
+        )
+    });
+    Some(Data {
+        name: unquote(literals.next()?.text()),
+        value: literals.next()?.text().to_owned(),
+        range: node.text_range(),
+    })
+}
+
+fn block(node: &SyntaxNode) -> Option<Block> {
+    Some(Block {
+        statements: node
+            .children()
+            .map(|node| statement(&node))
+            .collect::<Option<_>>()?,
+        range: significant_range(node),
+    })
+}
+
+fn statement(node: &SyntaxNode) -> Option<Statement> {
+    let statement = match node.kind() {
+        SyntaxKind::BLOCK => Statement::Block(block(node)?),
+        SyntaxKind::FUNCTION_DEFINITION => {
+            Statement::FunctionDefinition(function_definition(node)?)
+        }
+        SyntaxKind::VARIABLE_DECLARATION => Statement::VariableDeclaration(VariableDeclaration {
+            variables: typed_identifier_list(&child(node, SyntaxKind::TYPED_IDENTIFIER_LIST)?)?,
+            value: match child(node, SyntaxKind::EXPRESSION) {
+                Some(node) => Some(expression(&node)?),
+                None => None,
+            },
+            range: node.text_range(),
+        }),
+        SyntaxKind::ASSIGNMENT => Statement::Assignment(Assignment {
+            variables: tokens(&child(node, SyntaxKind::IDENTIFIER_LIST)?)
+                .filter(|token| token.kind() == SyntaxKind::IDENT)
+                .map(|token| identifier(&token))
+                .collect(),
+            value: expression(&child(node, SyntaxKind::EXPRESSION)?)?,
+            range: node.text_range(),
+        }),
+        SyntaxKind::IF => Statement::If(If {
+            condition: expression(&child(node, SyntaxKind::EXPRESSION)?)?,
+            body: block(&child(node, SyntaxKind::BLOCK)?)?,
+            range: node.text_range(),
+        }),
+        SyntaxKind::EXPRESSION => Statement::Expression(expression(node)?),
+        SyntaxKind::SWITCH => Statement::Switch(Switch {
+            expression: expression(&child(node, SyntaxKind::EXPRESSION)?)?,
+            cases: children(node, SyntaxKind::CASE)
+                .map(|node| {
+                    Some(Case {
+                        value: literal(&child(&node, SyntaxKind::LITERAL)?)?,
+                        body: block(&child(&node, SyntaxKind::BLOCK)?)?,
+                        range: node.text_range(),
+                    })
+                })
+                .collect::<Option<_>>()?,
+            default: match child(node, SyntaxKind::DEFAULT) {
+                Some(node) => Some(block(&child(&node, SyntaxKind::BLOCK)?)?),
+                None => None,
+            },
+            range: node.text_range(),
+        }),
+        SyntaxKind::FOR_LOOP => {
+            let mut blocks = children(node, SyntaxKind::BLOCK);
+            Statement::ForLoop(ForLoop {
+                initializer: block(&blocks.next()?)?,
+                condition: expression(&child(node, SyntaxKind::EXPRESSION)?)?,
+                finalizer: block(&blocks.next()?)?,
+                body: block(&blocks.next()?)?,
+                range: node.text_range(),
+            })
+        }
+        SyntaxKind::BREAK_CONTINUE => match token(node, SyntaxKind::BREAK_KW) {
+            Some(_) => Statement::Break(node.text_range()),
+            None => Statement::Continue(node.text_range()),
+        },
+        SyntaxKind::LEAVE => Statement::Leave(node.text_range()),
+        _ => return None,
+    };
+    Some(statement)
+}
+
+fn function_definition(node: &SyntaxNode) -> Option<FunctionDefinition> {
+    let mut parameters = Vec::new();
+    let mut returns = Vec::new();
+    let mut is_after_arrow = false;
+    for element in node.children_with_tokens() {
+        match element.kind() {
+            SyntaxKind::ARROW => is_after_arrow = true,
+            SyntaxKind::TYPED_IDENTIFIER_LIST => {
+                let list = typed_identifier_list(element.as_node()?)?;
+                if is_after_arrow {
+                    returns = list;
+                } else {
+                    parameters = list;
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(FunctionDefinition {
+        name: identifier(&token(node, SyntaxKind::IDENT)?),
+        parameters,
+        returns,
+        body: block(&child(node, SyntaxKind::BLOCK)?)?,
+        range: node.text_range(),
+    })
+}
+
+fn typed_identifier_list(node: &SyntaxNode) -> Option<Vec<TypedIdentifier>> {
+    children(node, SyntaxKind::TYPED_IDENTIFIER)
+        .map(|node| {
+            Some(TypedIdentifier {
+                identifier: identifier(&token(&node, SyntaxKind::IDENT)?),
+                r#type: child(&node, SyntaxKind::TYPE_NAME).and_then(|node| type_name(&node)),
+                range: node.text_range(),
+            })
+        })
+        .collect()
+}
+
+fn expression(node: &SyntaxNode) -> Option<Expression> {
+    if let Some(call) = child(node, SyntaxKind::FUNCTION_CALL) {
+        return Some(Expression::FunctionCall(FunctionCall {
+            name: identifier(&token(&call, SyntaxKind::IDENT)?),
+            arguments: children(&call, SyntaxKind::EXPRESSION)
+                .map(|node| expression(&node))
+                .collect::<Option<_>>()?,
+            range: call.text_range(),
+        }));
+    }
+    if let Some(literal_node) = child(node, SyntaxKind::LITERAL) {
+        return Some(Expression::Literal(literal(&literal_node)?));
+    }
+    Some(Expression::Identifier(identifier(&token(
+        node,
+        SyntaxKind::IDENT,
+    )?)))
+}
+
+fn literal(node: &SyntaxNode) -> Option<Literal> {
+    let value = tokens(node).next()?;
+    let kind = match value.kind() {
+        SyntaxKind::DECIMAL_NUMBER => LiteralKind::Decimal,
+        SyntaxKind::HEX_NUMBER => LiteralKind::Hexadecimal,
+        SyntaxKind::STRING_LITERAL => LiteralKind::String,
+        SyntaxKind::HEX_LITERAL => LiteralKind::HexString,
+        SyntaxKind::TRUE_KW | SyntaxKind::FALSE_KW => LiteralKind::Boolean,
+        _ => return None,
+    };
+    Some(Literal {
+        kind,
+        value: value.text().to_owned(),
+        r#type: child(node, SyntaxKind::TYPE_NAME).and_then(|node| type_name(&node)),
+        range: node.text_range(),
+    })
+}
+
+fn type_name(node: &SyntaxNode) -> Option<TypeName> {
+    Some(TypeName {
+        name: token(node, SyntaxKind::IDENT)?.text().to_owned(),
+        range: node.text_range(),
+    })
+}
+
+fn identifier(token: &SyntaxToken) -> Identifier {
+    Identifier {
+        name: token.text().to_owned(),
+        range: token.text_range(),
+    }
+}
+
+/// Returns the child nodes of the kind.
+fn children(node: &SyntaxNode, kind: SyntaxKind) -> impl Iterator<Item = SyntaxNode> {
+    node.children().filter(move |node| node.kind() == kind)
+}
+
+/// Returns the first child node of the kind.
+fn child(node: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxNode> {
+    children(node, kind).next()
+}
+
+/// Returns the significant child tokens.
+fn tokens(node: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    node.children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !token.kind().is_trivia())
+}
+
+/// Returns the first significant child token of the kind.
+fn token(node: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
+    tokens(node).find(|token| token.kind() == kind)
+}
+
+/// Returns the range without the leading and trailing trivia, which only the root node holds.
+fn significant_range(node: &SyntaxNode) -> TextRange {
+    let mut significant = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !token.kind().is_trivia());
+    let Some(first) = significant.next() else {
+        return node.text_range();
+    };
+    let last = significant.last().unwrap_or_else(|| first.clone());
+    TextRange::new(first.text_range().start(), last.text_range().end())
+}
+
+/// Drops the quotes of the object and data names.
+fn unquote(literal: &str) -> String {
+    literal
+        .strip_prefix('"')
+        .and_then(|literal| literal.strip_suffix('"'))
+        .unwrap_or(literal)
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use rowan::TextRange;
+    use rowan::TextSize;
+
+    use crate::yul::ast::*;
+
+    #[test]
+    fn lowers_the_object() {
+        let text = r#"
+object "A" {
+    code {
+        function f(a, b:u256) -> c { c := add(a, b) }
+        switch f(1, 0x02:u256) case "x" { leave } default { }
+    }
+    object "A_deployed" { code { } }
+    data ".metadata" hex"ff"
+}
+"#;
+        let object = Object::parse(text).expect("Always valid");
+
+        assert_eq!(object.name, "A");
+        assert_eq!(object.objects.len(), 1);
+        assert_eq!(object.objects[0].name, "A_deployed");
+        assert_eq!(object.data.len(), 1);
+        assert_eq!(object.data[0].name, ".metadata");
+        assert_eq!(object.data[0].value, "hex\"ff\"");
+        assert_eq!(&text[object.range], text.trim());
+
+        let statements = object.code.block.statements.as_slice();
+        let Statement::FunctionDefinition(definition) = &statements[0] else {
+            panic!("Expected a function definition, found {:?}", statements[0]);
+        };
+        assert_eq!(definition.name.name, "f");
+        assert_eq!(definition.parameters.len(), 2);
+        assert_eq!(definition.parameters[0].r#type, None);
+        assert_eq!(
+            definition.parameters[1]
+                .r#type
+                .as_ref()
+                .map(|r#type| r#type.name.as_str()),
+            Some("u256")
+        );
+        assert_eq!(definition.returns.len(), 1);
+        assert!(matches!(
+            definition.body.statements.as_slice(),
+            [Statement::Assignment(_)]
+        ));
+
+        let Statement::Switch(switch) = &statements[1] else {
+            panic!("Expected a switch, found {:?}", statements[1]);
+        };
+        let Expression::FunctionCall(call) = &switch.expression else {
+            panic!("Expected a call, found {:?}", switch.expression);
+        };
+        let kinds = call
+            .arguments
+            .iter()
+            .map(|argument| match argument {
+                Expression::Literal(literal) => Some(literal.kind),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [Some(LiteralKind::Decimal), Some(LiteralKind::Hexadecimal)]
+        );
+        assert_eq!(switch.cases.len(), 1);
+        assert_eq!(switch.cases[0].value.kind, LiteralKind::String);
+        assert!(switch.default.is_some());
+    }
+
+    #[test]
+    fn lowers_the_loop_blocks_in_order() {
+        let block = Block::parse("{ for { let i := 0 } lt(i, 2) { i := add(i, 1) } { break } }")
+            .expect("Always valid");
+
+        let Statement::ForLoop(for_loop) = &block.statements[0] else {
+            panic!("Expected a loop, found {:?}", block.statements[0]);
+        };
+        assert!(matches!(
+            for_loop.initializer.statements.as_slice(),
+            [Statement::VariableDeclaration(_)]
+        ));
+        assert!(matches!(
+            for_loop.finalizer.statements.as_slice(),
+            [Statement::Assignment(_)]
+        ));
+        assert!(matches!(
+            for_loop.body.statements.as_slice(),
+            [Statement::Break(_)]
+        ));
+    }
+
+    #[test]
+    fn rejects_the_wrong_root() {
+        let errors = Object::parse("{ }").expect_err("Always invalid");
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Expected `object`.");
+        assert_eq!(
+            errors[0].range,
+            TextRange::new(TextSize::new(0), TextSize::new(3))
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! The typed Yul syntax tree.
+//! Unlike the lossless `rowan` tree, it keeps only the meaningful parts of the code and their ranges.
+
+pub mod json;
+pub mod lower;
+pub mod visit;
+
+use rowan::TextRange;
+
+use super::parser::ParseError;
+
+/// The Yul code: an object, or a bare block of code, such as a hand-written library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// The object.
+    Object(Object),
+    /// The bare block.
+    Block(Block),
+}
+
+impl Source {
+    /// Parses the Yul object or block.
+    pub fn parse(text: &str) -> Result<Self, Vec<ParseError>> {
+        lower::parse_source(text)
+    }
+}
+
+/// The Yul object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Object {
+    /// The object name, without the quotes.
+    pub name: String,
+    /// The object code.
+    pub code: Code,
+    /// The inner objects, e.g. the deployed code of the deploy code object.
+    pub objects: Vec<Object>,
+    /// The data sections.
+    pub data: Vec<Data>,
+    /// The object range.
+    pub range: TextRange,
+}
+
+impl Object {
+    /// Parses the Yul object.
+    pub fn parse(text: &str) -> Result<Self, Vec<ParseError>> {
+        lower::parse_object(text)
+    }
+
+    /// Deserializes the Yul object from the `solc` `irOptimizedAst` JSON output.
+    pub fn from_solc_json(json: &serde_json::Value) -> anyhow::Result<Self> {
+        json::object(json)
+    }
+}
+
+/// The object code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Code {
+    /// The code block.
+    pub block: Block,
+    /// The `code` range.
+    pub range: TextRange,
+}
+
+/// The object data section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Data {
+    /// The data name, without the quotes.
+    pub name: String,
+    /// The data value, as it is written: a string or a `hex"..."` literal.
+    pub value: String,
+    /// The data range.
+    pub range: TextRange,
+}
+
+/// The block of statements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    /// The statements.
+    pub statements: Vec<Statement>,
+    /// The block range, including the braces.
+    pub range: TextRange,
+}
+
+impl Block {
+    /// Parses the bare Yul block, e.g. a hand-written library.
+    pub fn parse(text: &str) -> Result<Self, Vec<ParseError>> {
+        lower::parse_block(text)
+    }
+}
+
+/// The statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    /// The nested block.
+    Block(Block),
+    /// The function definition.
+    FunctionDefinition(FunctionDefinition),
+    /// The `let` declaration.
+    VariableDeclaration(VariableDeclaration),
+    /// The `:=` assignment.
+    Assignment(Assignment),
+    /// The `if` statement.
+    If(If),
+    /// The expression statement, which must be a call of a function returning nothing.
+    Expression(Expression),
+    /// The `switch` statement.
+    Switch(Switch),
+    /// The `for` loop.
+    ForLoop(ForLoop),
+    /// The `break` statement.
+    Break(TextRange),
+    /// The `continue` statement.
+    Continue(TextRange),
+    /// The `leave` statement.
+    Leave(TextRange),
+}
+
+impl Statement {
+    /// Returns the statement range.
+    pub fn range(&self) -> TextRange {
+        match self {
+            Self::Block(inner) => inner.range,
+            Self::FunctionDefinition(inner) => inner.range,
+            Self::VariableDeclaration(inner) => inner.range,
+            Self::Assignment(inner) => inner.range,
+            Self::If(inner) => inner.range,
+            Self::Expression(inner) => inner.range(),
+            Self::Switch(inner) => inner.range,
+            Self::ForLoop(inner) => inner.range,
+            Self::Break(range) | Self::Continue(range) | Self::Leave(range) => *range,
+        }
+    }
+}
+
+/// The function definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionDefinition {
+    /// The function name.
+    pub name: Identifier,
+    /// The parameters.
+    pub parameters: Vec<TypedIdentifier>,
+    /// The return variables.
+    pub returns: Vec<TypedIdentifier>,
+    /// The function body.
+    pub body: Block,
+    /// The definition range.
+    pub range: TextRange,
+}
+
+/// The `let` declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableDeclaration {
+    /// The declared variables.
+    pub variables: Vec<TypedIdentifier>,
+    /// The initial value, if any.
+    pub value: Option<Expression>,
+    /// The declaration range.
+    pub range: TextRange,
+}
+
+/// The `:=` assignment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    /// The assigned variables.
+    pub variables: Vec<Identifier>,
+    /// The assigned value.
+    pub value: Expression,
+    /// The assignment range.
+    pub range: TextRange,
+}
+
+/// The `if` statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct If {
+    /// The condition.
+    pub condition: Expression,
+    /// The body.
+    pub body: Block,
+    /// The statement range.
+    pub range: TextRange,
+}
+
+/// The `switch` statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Switch {
+    /// The switched expression.
+    pub expression: Expression,
+    /// The cases.
+    pub cases: Vec<Case>,
+    /// The `default` body, if any.
+    pub default: Option<Block>,
+    /// The statement range.
+    pub range: TextRange,
+}
+
+/// The `switch` case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    /// The case value.
+    pub value: Literal,
+    /// The case body.
+    pub body: Block,
+    /// The case range.
+    pub range: TextRange,
+}
+
+/// The `for` loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForLoop {
+    /// The initialization block, whose variables are visible in the whole loop.
+    pub initializer: Block,
+    /// The condition.
+    pub condition: Expression,
+    /// The block executed after each iteration.
+    pub finalizer: Block,
+    /// The body.
+    pub body: Block,
+    /// The loop range.
+    pub range: TextRange,
+}
+
+/// The expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+    /// The function or builtin call.
+    FunctionCall(FunctionCall),
+    /// The variable.
+    Identifier(Identifier),
+    /// The literal.
+    Literal(Literal),
+}
+
+impl Expression {
+    /// Returns the expression range.
+    pub fn range(&self) -> TextRange {
+        match self {
+            Self::FunctionCall(inner) => inner.range,
+            Self::Identifier(inner) => inner.range,
+            Self::Literal(inner) => inner.range,
+        }
+    }
+}
+
+/// The function or builtin call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCall {
+    /// The function name.
+    pub name: Identifier,
+    /// The arguments.
+    pub arguments: Vec<Expression>,
+    /// The call range.
+    pub range: TextRange,
+}
+
+/// The identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identifier {
+    /// The name.
+    pub name: String,
+    /// The identifier range.
+    pub range: TextRange,
+}
+
+/// The identifier with an optional type, e.g. `x:u256`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedIdentifier {
+    /// The identifier.
+    pub identifier: Identifier,
+    /// The type, if any.
+    pub r#type: Option<TypeName>,
+    /// The typed identifier range.
+    pub range: TextRange,
+}
+
+/// The type name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeName {
+    /// The name.
+    pub name: String,
+    /// The type name range.
+    pub range: TextRange,
+}
+
+/// The literal kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiteralKind {
+    /// The decimal number, e.g. `42`.
+    Decimal,
+    /// The hexadecimal number, e.g. `0x2a`.
+    Hexadecimal,
+    /// The string, e.g. `"abc"`.
+    String,
+    /// The hex string, e.g. `hex"616263"`.
+    HexString,
+    /// The `true` or `false` literal.
+    Boolean,
+}
+
+/// The literal with an optional type, e.g. `42:u256`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Literal {
+    /// The literal kind.
+    pub kind: LiteralKind,
+    /// The literal, as it is written.
+    pub value: String,
+    /// The type, if any.
+    pub r#type: Option<TypeName>,
+    /// The literal range.
+    pub range: TextRange,
+}
@@ -0,0 +1,135 @@
+//! The typed Yul syntax tree traversal.
+//! The default visitor methods walk into the children, so the visitors only override the nodes they are interested in.
+
+use super::*;
+
+/// The typed Yul syntax tree visitor.
+pub trait Visitor {
+    /// Visits the object.
+    fn visit_object(&mut self, object: &Object) {
+        walk_object(self, object);
+    }
+
+    /// Visits the block.
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    /// Visits the statement.
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    /// Visits the function definition.
+    fn visit_function_definition(&mut self, definition: &FunctionDefinition) {
+        walk_function_definition(self, definition);
+    }
+
+    /// Visits the expression.
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    /// Visits the function call.
+    fn visit_function_call(&mut self, call: &FunctionCall) {
+        walk_function_call(self, call);
+    }
+
+    /// Visits the identifier, either declared or used.
+    fn visit_identifier(&mut self, _identifier: &Identifier) {}
+
+    /// Visits the literal.
+    fn visit_literal(&mut self, _literal: &Literal) {}
+}
+
+/// Walks the object code and the inner objects.
+pub fn walk_object<V: Visitor + ?Sized>(visitor: &mut V, object: &Object) {
+    visitor.visit_block(&object.code.block);
+    for object in object.objects.iter() {
+        visitor.visit_object(object);
+    }
+}
+
+/// Walks the block statements.
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &Block) {
+    for statement in block.statements.iter() {
+        visitor.visit_statement(statement);
+    }
+}
+
+/// Walks the statement children.
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::Block(block) => visitor.visit_block(block),
+        Statement::FunctionDefinition(definition) => visitor.visit_function_definition(definition),
+        Statement::VariableDeclaration(declaration) => {
+            for variable in declaration.variables.iter() {
+                visitor.visit_identifier(&variable.identifier);
+            }
+            if let Some(value) = declaration.value.as_ref() {
+                visitor.visit_expression(value);
+            }
+        }
+        Statement::Assignment(assignment) => {
+            for variable in assignment.variables.iter() {
+                visitor.visit_identifier(variable);
+            }
+            visitor.visit_expression(&assignment.value);
+        }
+        Statement::If(r#if) => {
+            visitor.visit_expression(&r#if.condition);
+            visitor.visit_block(&r#if.body);
+        }
+        Statement::Expression(expression) => visitor.visit_expression(expression),
+        Statement::Switch(switch) => {
+            visitor.visit_expression(&switch.expression);
+            for case in switch.cases.iter() {
+                visitor.visit_literal(&case.value);
+                visitor.visit_block(&case.body);
+            }
+            if let Some(default) = switch.default.as_ref() {
+                visitor.visit_block(default);
+            }
+        }
+        Statement::ForLoop(for_loop) => {
+            visitor.visit_block(&for_loop.initializer);
+            visitor.visit_expression(&for_loop.condition);
+            visitor.visit_block(&for_loop.finalizer);
+            visitor.visit_block(&for_loop.body);
+        }
+        Statement::Break(_) | Statement::Continue(_) | Statement::Leave(_) => {}
+    }
+}
+
+/// Walks the function signature and body.
+pub fn walk_function_definition<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    definition: &FunctionDefinition,
+) {
+    visitor.visit_identifier(&definition.name);
+    for variable in definition
+        .parameters
+        .iter()
+        .chain(definition.returns.iter())
+    {
+        visitor.visit_identifier(&variable.identifier);
+    }
+    visitor.visit_block(&definition.body);
+}
+
+/// Walks the expression children.
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::FunctionCall(call) => visitor.visit_function_call(call),
+        Expression::Identifier(identifier) => visitor.visit_identifier(identifier),
+        Expression::Literal(literal) => visitor.visit_literal(literal),
+    }
+}
+
+/// Walks the call arguments.
+/// The function name is not visited, since it may be a builtin.
+pub fn walk_function_call<V: Visitor + ?Sized>(visitor: &mut V, call: &FunctionCall) {
+    for argument in call.arguments.iter() {
+        visitor.visit_expression(argument);
+    }
+}
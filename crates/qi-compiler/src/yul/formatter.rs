@@ -0,0 +1,498 @@
+//! The Yul code formatter.
+//! It prints the lossless syntax tree with normalized indentation and spacing, keeping the comments,
+//! including the `@src` annotations, next to the code they precede or follow.
+
+use super::parser::ParseError;
+use super::SyntaxElement;
+use super::SyntaxKind;
+use super::SyntaxNode;
+use super::SyntaxToken;
+
+/// The indentation unit.
+const INDENT: &str = "    ";
+
+/// The line width, above which the argument lists are broken.
+const MAX_WIDTH: usize = 100;
+
+/// Formats the Yul object or block.
+/// The code with syntax errors is not formatted, and the errors are returned instead.
+pub fn format(text: &str) -> Result<String, Vec<ParseError>> {
+    let parse = super::parser::parse(text);
+    if !parse.errors().is_empty() {
+        return Err(parse.errors().to_vec());
+    }
+    Ok(root(&parse.syntax()))
+}
+
+/// Formats the root node, which also holds the comments before and after the code.
+fn root(node: &SyntaxNode) -> String {
+    let elements = node.children_with_tokens().collect::<Vec<_>>();
+    let first = elements
+        .iter()
+        .position(|element| !element.kind().is_trivia())
+        .unwrap_or(elements.len());
+    let last = elements
+        .iter()
+        .rposition(|element| !element.kind().is_trivia())
+        .map_or(first, |last| last + 1);
+
+    let mut output = items(elements[..first].iter().cloned(), 0);
+    if first < last {
+        if first > 0 && newlines_before(&elements, first) >= 2 {
+            output.push('\n');
+        }
+        let code = match node.kind() {
+            SyntaxKind::OBJECT => object(node, 0),
+            _ => block(node, 0),
+        };
+        output.push_str(code.as_str());
+        output.push('\n');
+    }
+    output.push_str(items(elements[last..].iter().cloned(), 0).as_str());
+    output
+}
+
+/// Formats the sequence of items, such as statements, each on its own line.
+/// The comments on the line of the previous item stay there, and the others get their own lines.
+/// The blank lines between the items are kept, but not more than one in a row.
+fn items(elements: impl Iterator<Item = SyntaxElement>, indent: usize) -> String {
+    let mut output = String::new();
+    let mut newlines = 0;
+    let mut is_first = true;
+    for element in elements {
+        match element {
+            SyntaxElement::Token(token) if token.kind() == SyntaxKind::WHITESPACE => {
+                newlines = token.text().matches('\n').count();
+                continue;
+            }
+            SyntaxElement::Token(token) if token.kind() == SyntaxKind::COMMENT => {
+                if !is_first && newlines == 0 {
+                    output.pop();
+                    output.push(' ');
+                } else {
+                    if !is_first && newlines >= 2 {
+                        output.push('\n');
+                    }
+                    output.push_str(INDENT.repeat(indent).as_str());
+                }
+                output.push_str(token.text());
+            }
+            SyntaxElement::Token(token) => {
+                if !is_first && newlines >= 2 {
+                    output.push('\n');
+                }
+                output.push_str(INDENT.repeat(indent).as_str());
+                output.push_str(token.text());
+            }
+            SyntaxElement::Node(node) => {
+                if !is_first && newlines >= 2 {
+                    output.push('\n');
+                }
+                output.push_str(INDENT.repeat(indent).as_str());
+                output.push_str(item(&node, indent).as_str());
+            }
+        }
+        output.push('\n');
+        newlines = 0;
+        is_first = false;
+    }
+    output
+}
+
+/// Formats the item starting at the indentation, without the final line break.
+fn item(node: &SyntaxNode, indent: usize) -> String {
+    let column = indent * INDENT.len();
+    match node.kind() {
+        SyntaxKind::OBJECT => object(node, indent),
+        SyntaxKind::CODE => format!("code {}", block(&child(node, SyntaxKind::BLOCK), indent)),
+        SyntaxKind::BLOCK => block(node, indent),
+        SyntaxKind::FUNCTION_DEFINITION => {
+            let body = child(node, SyntaxKind::BLOCK);
+            let signature = node
+                .children_with_tokens()
+                .take_while(|element| element.as_node() != Some(&body))
+                .collect::<Vec<_>>();
+            format!(
+                "{} {}",
+                join(signature.into_iter().flat_map(tokens), indent),
+                block(&body, indent)
+            )
+        }
+        SyntaxKind::VARIABLE_DECLARATION | SyntaxKind::ASSIGNMENT => {
+            let head = node
+                .children_with_tokens()
+                .take_while(|element| element.kind() != SyntaxKind::WALRUS)
+                .collect::<Vec<_>>();
+            let head = join(head.into_iter().flat_map(tokens), indent);
+            match node
+                .children()
+                .find(|node| node.kind() == SyntaxKind::EXPRESSION)
+            {
+                Some(value) => {
+                    let value = expression(&value, indent, column + head.len() + 4);
+                    format!("{head} := {value}")
+                }
+                None => head,
+            }
+        }
+        SyntaxKind::IF => format!(
+            "if {} {}",
+            expression(&child(node, SyntaxKind::EXPRESSION), indent, column + 3),
+            block(&child(node, SyntaxKind::BLOCK), indent)
+        ),
+        SyntaxKind::EXPRESSION => expression(node, indent, column),
+        SyntaxKind::SWITCH => switch(node, indent),
+        SyntaxKind::CASE => format!(
+            "case {} {}",
+            join(tokens(child(node, SyntaxKind::LITERAL).into()), indent),
+            block(&child(node, SyntaxKind::BLOCK), indent)
+        ),
+        SyntaxKind::DEFAULT => {
+            format!("default {}", block(&child(node, SyntaxKind::BLOCK), indent))
+        }
+        SyntaxKind::FOR_LOOP => {
+            let mut blocks = node
+                .children()
+                .filter(|node| node.kind() == SyntaxKind::BLOCK);
+            let mut part = |indent| {
+                let block_node = blocks.next().expect("Always exists");
+                one_line_block(&block_node, indent).unwrap_or_else(|| block(&block_node, indent))
+            };
+            let initializer = part(indent);
+            let condition = expression(&child(node, SyntaxKind::EXPRESSION), indent, column);
+            let finalizer = part(indent);
+            let body = block(&blocks.next().expect("Always exists"), indent);
+            format!("for {initializer} {condition} {finalizer} {body}")
+        }
+        _ => join(tokens(node.clone().into()), indent),
+    }
+}
+
+/// Formats the object, the nested objects and the data sections.
+fn object(node: &SyntaxNode, indent: usize) -> String {
+    let elements = node.children_with_tokens().collect::<Vec<_>>();
+    let open = elements
+        .iter()
+        .position(|element| element.kind() == SyntaxKind::BRACKET_CURLY_LEFT)
+        .expect("Always exists");
+    let close = elements
+        .iter()
+        .rposition(|element| element.kind() == SyntaxKind::BRACKET_CURLY_RIGHT)
+        .expect("Always exists");
+    let head = elements[..open]
+        .iter()
+        .filter(|element| !element.kind().is_trivia())
+        .cloned()
+        .flat_map(tokens);
+    format!(
+        "{} {{\n{}{}}}",
+        join(head, indent),
+        items(elements[open + 1..close].iter().cloned(), indent + 1),
+        INDENT.repeat(indent),
+    )
+}
+
+/// Formats the block, which starts on the current line and ends at the indentation.
+fn block(node: &SyntaxNode, indent: usize) -> String {
+    let elements = node.children_with_tokens().collect::<Vec<_>>();
+    let open = elements
+        .iter()
+        .position(|element| element.kind() == SyntaxKind::BRACKET_CURLY_LEFT)
+        .expect("Always exists");
+    let close = elements
+        .iter()
+        .rposition(|element| element.kind() == SyntaxKind::BRACKET_CURLY_RIGHT)
+        .expect("Always exists");
+    let inner = &elements[open + 1..close];
+    if inner
+        .iter()
+        .all(|element| element.kind() == SyntaxKind::WHITESPACE)
+    {
+        return "{ }".to_owned();
+    }
+    format!(
+        "{{\n{}{}}}",
+        items(inner.iter().cloned(), indent + 1),
+        INDENT.repeat(indent)
+    )
+}
+
+/// Formats the block with at most one simple statement and no comments on one line, e.g. `{ i := add(i, 1) }`.
+fn one_line_block(node: &SyntaxNode, indent: usize) -> Option<String> {
+    if node
+        .descendants_with_tokens()
+        .any(|element| element.kind() == SyntaxKind::COMMENT)
+    {
+        return None;
+    }
+    let mut statements = node.children();
+    let Some(statement) = statements.next() else {
+        return Some("{ }".to_owned());
+    };
+    if statements.next().is_some()
+        || !matches!(
+            statement.kind(),
+            SyntaxKind::VARIABLE_DECLARATION
+                | SyntaxKind::ASSIGNMENT
+                | SyntaxKind::EXPRESSION
+                | SyntaxKind::BREAK_CONTINUE
+                | SyntaxKind::LEAVE
+        )
+    {
+        return None;
+    }
+    let statement = item(&statement, indent);
+    if statement.contains('\n') {
+        return None;
+    }
+    Some(format!("{{ {statement} }}"))
+}
+
+/// Formats the `switch`, with the cases at its indentation.
+/// If every case fits on one line, the case bodies are aligned.
+fn switch(node: &SyntaxNode, indent: usize) -> String {
+    let column = indent * INDENT.len();
+    let expression_node = child(node, SyntaxKind::EXPRESSION);
+    let head = format!(
+        "switch {}",
+        expression(&expression_node, indent, column + 7)
+    );
+    let cases = node
+        .children_with_tokens()
+        .skip_while(|element| element.as_node() != Some(&expression_node))
+        .skip(1)
+        .collect::<Vec<_>>();
+
+    let aligned = cases
+        .iter()
+        .filter(|element| element.kind() != SyntaxKind::WHITESPACE)
+        .map(|element| {
+            let case = element.as_node()?;
+            let label = match case.kind() {
+                SyntaxKind::CASE => format!(
+                    "case {}",
+                    join(tokens(child(case, SyntaxKind::LITERAL).into()), indent)
+                ),
+                _ => "default".to_owned(),
+            };
+            let body = one_line_block(&child(case, SyntaxKind::BLOCK), indent)?;
+            Some((label, body))
+        })
+        .collect::<Option<Vec<_>>>();
+    if let Some(aligned) = aligned {
+        let width = aligned
+            .iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or_default();
+        let lines = aligned
+            .iter()
+            .map(|(label, body)| format!("{}{label:width$} {body}", INDENT.repeat(indent)))
+            .collect::<Vec<_>>();
+        if lines.iter().all(|line| line.chars().count() <= MAX_WIDTH) {
+            return format!("{head}\n{}", lines.join("\n"));
+        }
+    }
+
+    let cases = items(cases.into_iter(), indent);
+    format!("{head}\n{}", cases.trim_end_matches('\n'))
+}
+
+/// Formats the expression starting at the column.
+/// The function call is broken into one argument per line if it does not fit, or if it has comments.
+fn expression(node: &SyntaxNode, indent: usize, column: usize) -> String {
+    let flat = join(tokens(node.clone().into()), indent);
+    if !flat.contains('\n') && column + flat.chars().count() <= MAX_WIDTH {
+        return flat;
+    }
+    match node
+        .children()
+        .find(|node| node.kind() == SyntaxKind::FUNCTION_CALL)
+    {
+        Some(call) if call.children().next().is_some() => broken_call(&call, indent),
+        _ => flat,
+    }
+}
+
+/// Formats the function call with one argument per line, keeping the comments between the arguments.
+fn broken_call(node: &SyntaxNode, indent: usize) -> String {
+    let argument_indent = INDENT.repeat(indent + 1);
+    let elements = node.children_with_tokens().collect::<Vec<_>>();
+    let open = elements
+        .iter()
+        .position(|element| element.kind() == SyntaxKind::PARENTHESIS_LEFT)
+        .expect("Always exists");
+    let last_argument = elements
+        .iter()
+        .rposition(|element| element.kind() == SyntaxKind::EXPRESSION)
+        .expect("Always exists");
+
+    let mut output = join(elements[..=open].iter().cloned().flat_map(tokens), indent);
+    output.push('\n');
+    let mut newlines = 1;
+    let mut line = String::new();
+    for (index, element) in elements.iter().enumerate().skip(open + 1) {
+        match element.kind() {
+            SyntaxKind::WHITESPACE => {
+                newlines = element.to_string().matches('\n').count();
+                continue;
+            }
+            SyntaxKind::COMMENT if newlines == 0 && !line.is_empty() => {
+                line.push(' ');
+                line.push_str(element.to_string().as_str());
+            }
+            SyntaxKind::COMMENT => {
+                if !line.is_empty() {
+                    output.push_str(format!("{argument_indent}{line}\n").as_str());
+                }
+                line = element.to_string();
+            }
+            SyntaxKind::EXPRESSION => {
+                if !line.is_empty() {
+                    output.push_str(format!("{argument_indent}{line}\n").as_str());
+                }
+                line = expression(
+                    element.as_node().expect("Always exists"),
+                    indent + 1,
+                    argument_indent.len(),
+                );
+                if index < last_argument {
+                    line.push(',');
+                }
+            }
+            _ => {}
+        }
+        newlines = 0;
+    }
+    if !line.is_empty() {
+        output.push_str(format!("{argument_indent}{line}\n").as_str());
+    }
+    output.push_str(INDENT.repeat(indent).as_str());
+    output.push(')');
+    output
+}
+
+/// Joins the tokens on one line with the normalized spacing.
+/// The line comments are followed by a line break and the continuation indentation.
+fn join(tokens: impl IntoIterator<Item = SyntaxToken>, indent: usize) -> String {
+    let mut output = String::new();
+    let mut previous: Option<SyntaxToken> = None;
+    for token in tokens
+        .into_iter()
+        .filter(|token| token.kind() != SyntaxKind::WHITESPACE)
+    {
+        if let Some(previous) = previous.as_ref() {
+            if is_line_comment(previous) {
+                output.push('\n');
+                output.push_str(INDENT.repeat(indent + 1).as_str());
+            } else if is_spaced(previous.kind(), token.kind()) {
+                output.push(' ');
+            }
+        }
+        output.push_str(token.text());
+        previous = Some(token);
+    }
+    if previous.as_ref().is_some_and(is_line_comment) {
+        output.push('\n');
+        output.push_str(INDENT.repeat(indent).as_str());
+    }
+    output
+}
+
+/// Checks whether the tokens are separated with a space.
+fn is_spaced(previous: SyntaxKind, next: SyntaxKind) -> bool {
+    !matches!(
+        (previous, next),
+        (
+            _,
+            SyntaxKind::COMMA | SyntaxKind::PARENTHESIS_RIGHT | SyntaxKind::COLON
+        ) | (SyntaxKind::PARENTHESIS_LEFT | SyntaxKind::COLON, _)
+            | (SyntaxKind::IDENT, SyntaxKind::PARENTHESIS_LEFT)
+    )
+}
+
+/// Checks whether the token is a `//` comment, which must be followed by a line break.
+fn is_line_comment(token: &SyntaxToken) -> bool {
+    token.kind() == SyntaxKind::COMMENT && token.text().starts_with("//")
+}
+
+/// Returns the tokens of the element, including the nested ones.
+fn tokens(element: SyntaxElement) -> Vec<SyntaxToken> {
+    match element {
+        SyntaxElement::Token(token) => vec![token],
+        SyntaxElement::Node(node) => node
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .collect(),
+    }
+}
+
+/// Returns the child node of the kind, which the grammar guarantees for the trees without errors.
+fn child(node: &SyntaxNode, kind: SyntaxKind) -> SyntaxNode {
+    node.children()
+        .find(|node| node.kind() == kind)
+        .expect("Always exists")
+}
+
+/// Returns the number of line breaks before the element.
+fn newlines_before(elements: &[SyntaxElement], index: usize) -> usize {
+    index
+        .checked_sub(1)
+        .and_then(|index| elements.get(index))
+        .filter(|element| element.kind() == SyntaxKind::WHITESPACE)
+        .map_or(0, |element| element.to_string().matches('\n').count())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn formats_and_is_idempotent() {
+        let text = r#"/// @use-src 0:"A.sol"
+object "A_7" {
+code {
+  /// @src 0:10:20
+  function f(a,b:u256)->c{c:=add(a,b)}   // sum
+
+
+  let x:=f( 1,0x02 )
+  switch x case 0 { leave } case 0x0100 {x := 1} default { revert(0,0) }
+  for {let i := 0} lt(i,10) {i:=add(i,1)} {}
+  sstore(keccak256_storage_slot_of_the_mapping(0x0123456789abcdef, 0x0123456789abcdef0123456789abcdef),/* value */
+    // The flag.
+    1)
+}
+data ".metadata" hex"ff"
+}
+"#;
+        let expected = r#"/// @use-src 0:"A.sol"
+object "A_7" {
+    code {
+        /// @src 0:10:20
+        function f(a, b:u256) -> c {
+            c := add(a, b)
+        } // sum
+
+        let x := f(1, 0x02)
+        switch x
+        case 0      { leave }
+        case 0x0100 { x := 1 }
+        default     { revert(0, 0) }
+        for { let i := 0 } lt(i, 10) { i := add(i, 1) } { }
+        sstore(
+            keccak256_storage_slot_of_the_mapping(
+                0x0123456789abcdef,
+                0x0123456789abcdef0123456789abcdef
+            ), /* value */
+            // The flag.
+            1
+        )
+    }
+    data ".metadata" hex"ff"
+}
+"#;
+
+        let formatted = super::format(text).expect("Always valid");
+        assert_eq!(formatted, expected);
+        assert_eq!(super::format(formatted.as_str()).as_deref(), Ok(expected));
+    }
+}
@@ -0,0 +1,908 @@
+//! The Yul tree to SSA translation.
+//! The variables are renamed on the fly with the algorithm of Braun et al., "Simple and Efficient
+//! Construction of Static Single Assignment Form", where a block is sealed once all its
+//! predecessors are known. The unreachable blocks and the trivial block parameters are removed afterwards.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::yul::ast;
+use crate::yul::semantic::literal;
+use crate::yul::semantic::symbol_table::DeclarationId;
+use crate::yul::semantic::symbol_table::DeclarationKind;
+use crate::yul::semantic::symbol_table::Resolution;
+use crate::yul::semantic::symbol_table::SymbolTable;
+use crate::yul::u256::U256;
+
+use super::BasicBlock;
+use super::BlockId;
+use super::Edge;
+use super::Function;
+use super::FunctionId;
+use super::Instruction;
+use super::Object;
+use super::Operation;
+use super::Terminator;
+use super::Value;
+
+/// The name of the object code function in the dumps.
+pub const CODE_NAME: &str = "code";
+
+/// Builds the IR of the object and its inner objects.
+/// The object must be free of semantic errors, and `symbols` must be its analysis result.
+pub fn build(object: &ast::Object, symbols: &SymbolTable) -> anyhow::Result<Object> {
+    let mut definitions = Vec::new();
+    collect_functions(&object.code.block, &mut definitions);
+
+    let mut ids = HashMap::new();
+    for (index, definition) in definitions.iter().enumerate() {
+        ids.insert(declaration(symbols, &definition.name)?, FunctionId(index));
+    }
+
+    let code = FunctionBuilder::new(symbols, &ids).build(
+        CODE_NAME.to_owned(),
+        &[],
+        &[],
+        &object.code.block,
+    )?;
+
+    let mut names = HashSet::new();
+    let mut functions = Vec::with_capacity(definitions.len());
+    for definition in definitions.into_iter() {
+        let mut name = definition.name.name.clone();
+        let mut suffix = 0;
+        while !names.insert(name.clone()) {
+            suffix += 1;
+            name = format!("{}_{suffix}", definition.name.name);
+        }
+        functions.push(FunctionBuilder::new(symbols, &ids).build(
+            name,
+            definition.parameters.as_slice(),
+            definition.returns.as_slice(),
+            &definition.body,
+        )?);
+    }
+
+    let objects = object
+        .objects
+        .iter()
+        .map(|object| build(object, symbols))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(Object {
+        name: object.name.clone(),
+        code,
+        functions,
+        objects,
+    })
+}
+
+/// Collects the function definitions in the block, at any depth.
+fn collect_functions<'a>(
+    block: &'a ast::Block,
+    definitions: &mut Vec<&'a ast::FunctionDefinition>,
+) {
+    for statement in block.statements.iter() {
+        match statement {
+            ast::Statement::Block(block) => collect_functions(block, definitions),
+            ast::Statement::FunctionDefinition(definition) => {
+                definitions.push(definition);
+                collect_functions(&definition.body, definitions);
+            }
+            ast::Statement::If(r#if) => collect_functions(&r#if.body, definitions),
+            ast::Statement::Switch(switch) => {
+                for case in switch.cases.iter() {
+                    collect_functions(&case.body, definitions);
+                }
+                if let Some(default) = switch.default.as_ref() {
+                    collect_functions(default, definitions);
+                }
+            }
+            ast::Statement::ForLoop(for_loop) => {
+                collect_functions(&for_loop.initializer, definitions);
+                collect_functions(&for_loop.finalizer, definitions);
+                collect_functions(&for_loop.body, definitions);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the declaration the identifier is resolved to.
+fn declaration(
+    symbols: &SymbolTable,
+    identifier: &ast::Identifier,
+) -> anyhow::Result<DeclarationId> {
+    match symbols.resolve(identifier.range) {
+        Some(Resolution::Declaration(id)) => Ok(id),
+        _ => anyhow::bail!("Identifier `{}` is not resolved", identifier.name),
+    }
+}
+
+/// Returns the object, immutable or library name passed to the object access builtin at `index`.
+fn object_name(call: &ast::FunctionCall, index: usize) -> anyhow::Result<String> {
+    let Some(ast::Expression::Literal(literal)) = call.arguments.get(index) else {
+        anyhow::bail!(
+            "Function `{}` expects a string literal name",
+            call.name.name
+        );
+    };
+    literal::name(literal).map_err(|error| anyhow::anyhow!("{error}"))
+}
+
+/// The per-function SSA builder.
+struct FunctionBuilder<'a> {
+    /// The resolved symbols.
+    symbols: &'a SymbolTable,
+    /// The functions of the object.
+    functions: &'a HashMap<DeclarationId, FunctionId>,
+    /// The blocks.
+    blocks: Vec<BasicBlock>,
+    /// The predecessors of every block.
+    predecessors: Vec<Vec<BlockId>>,
+    /// Whether all the block predecessors are known.
+    sealed: Vec<bool>,
+    /// The parameters of the unsealed blocks, whose arguments are added when the blocks are sealed.
+    incomplete: HashMap<BlockId, Vec<(DeclarationId, Value)>>,
+    /// The current variable values at the end of every block.
+    definitions: HashMap<(BlockId, DeclarationId), Value>,
+    /// The block the code is appended to, or `None` after a jump, `leave` or a terminating builtin.
+    current: Option<BlockId>,
+    /// The `continue` and `break` targets of the enclosing loops.
+    loops: Vec<(BlockId, BlockId)>,
+    /// The block all the function returns go through.
+    exit: BlockId,
+    /// The next value number.
+    next_value: u32,
+}
+
+impl<'a> FunctionBuilder<'a> {
+    /// A shortcut constructor.
+    fn new(symbols: &'a SymbolTable, functions: &'a HashMap<DeclarationId, FunctionId>) -> Self {
+        Self {
+            symbols,
+            functions,
+            blocks: Vec::new(),
+            predecessors: Vec::new(),
+            sealed: Vec::new(),
+            incomplete: HashMap::new(),
+            definitions: HashMap::new(),
+            current: None,
+            loops: Vec::new(),
+            exit: BlockId(0),
+            next_value: 0,
+        }
+    }
+
+    /// Builds the function with the body.
+    fn build(
+        mut self,
+        name: String,
+        parameters: &[ast::TypedIdentifier],
+        returns: &[ast::TypedIdentifier],
+        body: &ast::Block,
+    ) -> anyhow::Result<Function> {
+        let entry = self.create_block();
+        self.seal(entry);
+        self.exit = self.create_block();
+        self.current = Some(entry);
+
+        let mut parameter_values = Vec::with_capacity(parameters.len());
+        for parameter in parameters.iter() {
+            let id = declaration(self.symbols, &parameter.identifier)?;
+            let value = self.new_value();
+            self.definitions.insert((entry, id), value);
+            parameter_values.push(value);
+        }
+        let mut return_ids = Vec::with_capacity(returns.len());
+        for variable in returns.iter() {
+            let id = declaration(self.symbols, &variable.identifier)?;
+            let value = self.constant(entry, U256::ZERO);
+            self.definitions.insert((entry, id), value);
+            return_ids.push(id);
+        }
+
+        self.block(body)?;
+        if let Some(block) = self.current {
+            self.jump(block, self.exit);
+        }
+        self.seal(self.exit);
+        let terminator = if self.predecessors[self.exit.0].is_empty() {
+            Terminator::Unreachable
+        } else {
+            Terminator::Return(
+                return_ids
+                    .into_iter()
+                    .map(|id| self.read(id, self.exit))
+                    .collect(),
+            )
+        };
+        self.blocks[self.exit.0].terminator = Some(terminator);
+
+        let mut function = Function {
+            name,
+            parameters: parameter_values,
+            returns: returns.len(),
+            blocks: self.blocks,
+        };
+        remove_unreachable_blocks(&mut function);
+        remove_trivial_parameters(&mut function);
+        Ok(function)
+    }
+
+    /// Builds the block statements, skipping the unreachable ones.
+    fn block(&mut self, block: &ast::Block) -> anyhow::Result<()> {
+        for statement in block.statements.iter() {
+            if self.current.is_none() {
+                break;
+            }
+            self.statement(statement)?;
+        }
+        Ok(())
+    }
+
+    /// Builds the statement.
+    fn statement(&mut self, statement: &ast::Statement) -> anyhow::Result<()> {
+        match statement {
+            ast::Statement::Block(block) => self.block(block)?,
+            ast::Statement::FunctionDefinition(_) => {}
+            ast::Statement::VariableDeclaration(declaration) => {
+                let values = match declaration.value.as_ref() {
+                    Some(value) => self.expression(value)?,
+                    None => {
+                        let block = self.current()?;
+                        declaration
+                            .variables
+                            .iter()
+                            .map(|_| self.constant(block, U256::ZERO))
+                            .collect()
+                    }
+                };
+                for (variable, value) in declaration.variables.iter().zip(values) {
+                    self.write(&variable.identifier, value)?;
+                }
+            }
+            ast::Statement::Assignment(assignment) => {
+                let values = self.expression(&assignment.value)?;
+                for (variable, value) in assignment.variables.iter().zip(values) {
+                    self.write(variable, value)?;
+                }
+            }
+            ast::Statement::If(r#if) => {
+                let condition = self.single(&r#if.condition)?;
+                let block = self.current()?;
+                let body = self.create_block();
+                let join = self.create_block();
+                self.terminate(
+                    block,
+                    Terminator::Branch {
+                        condition,
+                        then: Edge::new(body),
+                        r#else: Edge::new(join),
+                    },
+                );
+                self.seal(body);
+                self.current = Some(body);
+                self.block(&r#if.body)?;
+                if let Some(block) = self.current {
+                    self.jump(block, join);
+                }
+                self.enter(join);
+            }
+            ast::Statement::Expression(expression) => {
+                self.expression(expression)?;
+            }
+            ast::Statement::Switch(switch) => {
+                let value = self.single(&switch.expression)?;
+                let block = self.current()?;
+                let mut cases = Vec::with_capacity(switch.cases.len());
+                for case in switch.cases.iter() {
+                    let value = literal::evaluate(&case.value)
+                        .map_err(|error| anyhow::anyhow!("{error}"))?
+                        .to_u256();
+                    cases.push((value, self.create_block(), &case.body));
+                }
+                let default = switch
+                    .default
+                    .as_ref()
+                    .map(|body| (self.create_block(), body));
+                let join = self.create_block();
+                self.terminate(
+                    block,
+                    Terminator::Switch {
+                        value,
+                        cases: cases
+                            .iter()
+                            .map(|(value, target, _)| (*value, Edge::new(*target)))
+                            .collect(),
+                        default: Edge::new(default.map(|(target, _)| target).unwrap_or(join)),
+                    },
+                );
+                for (target, body) in cases
+                    .into_iter()
+                    .map(|(_, target, body)| (target, body))
+                    .chain(default)
+                {
+                    self.seal(target);
+                    self.current = Some(target);
+                    self.block(body)?;
+                    if let Some(block) = self.current {
+                        self.jump(block, join);
+                    }
+                }
+                self.enter(join);
+            }
+            ast::Statement::ForLoop(for_loop) => {
+                self.block(&for_loop.initializer)?;
+                let Some(block) = self.current else {
+                    return Ok(());
+                };
+                let header = self.create_block();
+                let body = self.create_block();
+                let post = self.create_block();
+                let exit = self.create_block();
+                self.jump(block, header);
+
+                self.current = Some(header);
+                let condition = self.single(&for_loop.condition)?;
+                let block = self.current()?;
+                self.terminate(
+                    block,
+                    Terminator::Branch {
+                        condition,
+                        then: Edge::new(body),
+                        r#else: Edge::new(exit),
+                    },
+                );
+
+                self.seal(body);
+                self.current = Some(body);
+                self.loops.push((post, exit));
+                self.block(&for_loop.body)?;
+                self.loops.pop();
+                if let Some(block) = self.current {
+                    self.jump(block, post);
+                }
+
+                self.seal(post);
+                if !self.predecessors[post.0].is_empty() {
+                    self.current = Some(post);
+                    self.block(&for_loop.finalizer)?;
+                    if let Some(block) = self.current {
+                        self.jump(block, header);
+                    }
+                }
+                self.seal(header);
+                self.enter(exit);
+            }
+            ast::Statement::Break(_) | ast::Statement::Continue(_) => {
+                let block = self.current()?;
+                let Some((post, exit)) = self.loops.last().copied() else {
+                    anyhow::bail!("Loop control statement outside of a loop body");
+                };
+                let target = match statement {
+                    ast::Statement::Break(_) => exit,
+                    _ => post,
+                };
+                self.jump(block, target);
+            }
+            ast::Statement::Leave(_) => {
+                let block = self.current()?;
+                self.jump(block, self.exit);
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the expression and returns its values.
+    /// The call arguments are evaluated right to left, as the Yul specification requires.
+    fn expression(&mut self, expression: &ast::Expression) -> anyhow::Result<Vec<Value>> {
+        match expression {
+            ast::Expression::FunctionCall(call) => {
+                let resolution = self.symbols.resolve(call.name.range);
+                let name_parameter = match resolution {
+                    Some(Resolution::Builtin(builtin)) => builtin.name_parameter(),
+                    _ => None,
+                };
+                let mut arguments = call
+                    .arguments
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .filter(|(index, _)| Some(*index) != name_parameter)
+                    .map(|(_, argument)| self.single(argument))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                arguments.reverse();
+
+                let (operation, returns) = match resolution {
+                    Some(Resolution::Builtin(builtin)) => match name_parameter {
+                        Some(index) => {
+                            let name = object_name(call, index)?;
+                            (Operation::ObjectAccess { builtin, name }, builtin.returns)
+                        }
+                        None => (Operation::Builtin(builtin), builtin.returns),
+                    },
+                    Some(Resolution::Declaration(id)) => {
+                        let Some(function) = self.functions.get(&id).copied() else {
+                            anyhow::bail!("Variable `{}` cannot be called", call.name.name);
+                        };
+                        let DeclarationKind::Function { returns, .. } =
+                            self.symbols.declaration(id).kind
+                        else {
+                            anyhow::bail!("Variable `{}` cannot be called", call.name.name);
+                        };
+                        (Operation::Call(function), returns)
+                    }
+                    None => anyhow::bail!("Identifier `{}` is not resolved", call.name.name),
+                };
+                let is_terminating =
+                    matches!(operation, Operation::Builtin(builtin) if builtin.is_terminating());
+                let results = (0..returns).map(|_| self.new_value()).collect::<Vec<_>>();
+                let block = self.current()?;
+                self.blocks[block.0].instructions.push(Instruction {
+                    results: results.clone(),
+                    operation,
+                    arguments,
+                });
+                if is_terminating {
+                    self.terminate(block, Terminator::Unreachable);
+                }
+                Ok(results)
+            }
+            ast::Expression::Identifier(identifier) => {
+                let id = declaration(self.symbols, identifier)?;
+                let block = self.current()?;
+                Ok(vec![self.read(id, block)])
+            }
+            ast::Expression::Literal(literal) => {
+                let value = literal::evaluate(literal)
+                    .map_err(|error| anyhow::anyhow!("{error}"))?
+                    .to_u256();
+                let block = self.current()?;
+                Ok(vec![self.constant(block, value)])
+            }
+        }
+    }
+
+    /// Builds the expression which must return a single value.
+    fn single(&mut self, expression: &ast::Expression) -> anyhow::Result<Value> {
+        match self.expression(expression)?.as_slice() {
+            [value] => Ok(*value),
+            values => anyhow::bail!("Expected 1 value, but got {}", values.len()),
+        }
+    }
+
+    /// Returns the current block.
+    fn current(&self) -> anyhow::Result<BlockId> {
+        self.current
+            .ok_or_else(|| anyhow::anyhow!("Expression in unreachable code"))
+    }
+
+    /// Assigns the value to the variable in the current block.
+    fn write(&mut self, variable: &ast::Identifier, value: Value) -> anyhow::Result<()> {
+        let id = declaration(self.symbols, variable)?;
+        let block = self.current()?;
+        self.definitions.insert((block, id), value);
+        Ok(())
+    }
+
+    /// Returns the variable value at the end of the block.
+    fn read(&mut self, variable: DeclarationId, block: BlockId) -> Value {
+        if let Some(value) = self.definitions.get(&(block, variable)) {
+            return *value;
+        }
+
+        let value = if !self.sealed[block.0] {
+            let value = self.add_parameter(block);
+            self.incomplete
+                .entry(block)
+                .or_default()
+                .push((variable, value));
+            value
+        } else {
+            match self.predecessors[block.0].as_slice() {
+                [] => self.constant(block, U256::ZERO),
+                [predecessor] => {
+                    let predecessor = *predecessor;
+                    self.read(variable, predecessor)
+                }
+                _ => {
+                    let value = self.add_parameter(block);
+                    self.definitions.insert((block, variable), value);
+                    self.add_arguments(variable, block);
+                    value
+                }
+            }
+        };
+        self.definitions.insert((block, variable), value);
+        value
+    }
+
+    /// Passes the variable values from the predecessors to the latest parameter of the block.
+    fn add_arguments(&mut self, variable: DeclarationId, block: BlockId) {
+        for predecessor in self.predecessors[block.0].clone().into_iter() {
+            let value = self.read(variable, predecessor);
+            let terminator = self.blocks[predecessor.0]
+                .terminator
+                .as_mut()
+                .expect("Always terminated");
+            for edge in terminator.edges_mut() {
+                if edge.target == block {
+                    edge.arguments.push(value);
+                }
+            }
+        }
+    }
+
+    /// Marks all the block predecessors as known, completing the parameters.
+    fn seal(&mut self, block: BlockId) {
+        for (variable, _) in self.incomplete.remove(&block).unwrap_or_default() {
+            self.add_arguments(variable, block);
+        }
+        self.sealed[block.0] = true;
+    }
+
+    /// Seals the block and continues the code there, unless the block has no predecessors.
+    fn enter(&mut self, block: BlockId) {
+        self.seal(block);
+        if self.predecessors[block.0].is_empty() {
+            self.blocks[block.0].terminator = Some(Terminator::Unreachable);
+            self.current = None;
+        } else {
+            self.current = Some(block);
+        }
+    }
+
+    /// Terminates the block with the jump to the target.
+    fn jump(&mut self, block: BlockId, target: BlockId) {
+        self.terminate(block, Terminator::Jump(Edge::new(target)));
+    }
+
+    /// Terminates the block, registering it as a predecessor of the targets.
+    /// The edge arguments are only added when the targets are sealed.
+    fn terminate(&mut self, block: BlockId, terminator: Terminator) {
+        for edge in terminator.edges() {
+            self.predecessors[edge.target.0].push(block);
+        }
+        self.blocks[block.0].terminator = Some(terminator);
+        self.current = None;
+    }
+
+    /// Appends the constant to the block.
+    fn constant(&mut self, block: BlockId, value: U256) -> Value {
+        let result = self.new_value();
+        self.blocks[block.0].instructions.push(Instruction {
+            results: vec![result],
+            operation: Operation::Constant(value),
+            arguments: Vec::new(),
+        });
+        result
+    }
+
+    /// Appends the parameter to the block.
+    fn add_parameter(&mut self, block: BlockId) -> Value {
+        let value = self.new_value();
+        self.blocks[block.0].parameters.push(value);
+        value
+    }
+
+    /// Creates the empty block.
+    fn create_block(&mut self) -> BlockId {
+        self.blocks.push(BasicBlock::default());
+        self.predecessors.push(Vec::new());
+        self.sealed.push(false);
+        BlockId(self.blocks.len() - 1)
+    }
+
+    /// Returns the new value.
+    fn new_value(&mut self) -> Value {
+        let value = Value(self.next_value);
+        self.next_value += 1;
+        value
+    }
+}
+
+/// Removes the blocks unreachable from the entry one, renumbering the rest.
+pub fn remove_unreachable_blocks(function: &mut Function) {
+    let mut reachable = vec![false; function.blocks.len()];
+    let mut worklist = vec![BlockId(0)];
+    while let Some(block) = worklist.pop() {
+        if std::mem::replace(&mut reachable[block.0], true) {
+            continue;
+        }
+        for edge in function.blocks[block.0]
+            .terminator
+            .iter()
+            .flat_map(Terminator::edges)
+        {
+            worklist.push(edge.target);
+        }
+    }
+
+    let mut numbers = Vec::with_capacity(function.blocks.len());
+    let mut next = 0;
+    for is_reachable in reachable.iter() {
+        numbers.push(BlockId(next));
+        next += *is_reachable as usize;
+    }
+    let blocks = std::mem::take(&mut function.blocks);
+    function.blocks = blocks
+        .into_iter()
+        .zip(reachable)
+        .filter_map(|(block, is_reachable)| is_reachable.then_some(block))
+        .collect();
+    for block in function.blocks.iter_mut() {
+        for edge in block.terminator.iter_mut().flat_map(Terminator::edges_mut) {
+            edge.target = numbers[edge.target.0];
+        }
+    }
+}
+
+/// Removes the block parameters which always get the same value, or themselves.
+/// Removing a parameter may make the others trivial, so it is repeated until nothing changes.
+pub fn remove_trivial_parameters(function: &mut Function) {
+    loop {
+        let predecessors = function.predecessors();
+        let mut trivial = None;
+        'search: for (index, block) in function.blocks.iter().enumerate() {
+            for (position, parameter) in block.parameters.iter().enumerate() {
+                let mut unique = None;
+                let mut is_trivial = true;
+                for predecessor in predecessors[index].iter() {
+                    let terminator = function.blocks[predecessor.0]
+                        .terminator
+                        .as_ref()
+                        .expect("Always terminated");
+                    for edge in terminator.edges() {
+                        if edge.target.0 != index {
+                            continue;
+                        }
+                        let argument = edge.arguments[position];
+                        if argument == *parameter || Some(argument) == unique {
+                            continue;
+                        }
+                        if unique.is_some() {
+                            is_trivial = false;
+                        }
+                        unique = Some(argument);
+                    }
+                }
+                if is_trivial && let Some(unique) = unique {
+                    trivial = Some((BlockId(index), position, *parameter, unique));
+                    break 'search;
+                }
+            }
+        }
+        let Some((block, position, parameter, replacement)) = trivial else {
+            break;
+        };
+
+        function.blocks[block.0].parameters.remove(position);
+        let count = function.blocks[block.0].parameters.len();
+        for predecessor in predecessors[block.0].iter() {
+            let terminator = function.blocks[predecessor.0]
+                .terminator
+                .as_mut()
+                .expect("Always terminated");
+            for edge in terminator.edges_mut() {
+                // a predecessor jumping to the block in several ways is listed several times
+                if edge.target == block && edge.arguments.len() > count {
+                    edge.arguments.remove(position);
+                }
+            }
+        }
+        replace_uses(function, parameter, replacement);
+    }
+}
+
+/// Replaces the uses of the value in the function.
+pub fn replace_uses(function: &mut Function, value: Value, replacement: Value) {
+    for block in function.blocks.iter_mut() {
+        for instruction in block.instructions.iter_mut() {
+            for argument in instruction.arguments.iter_mut() {
+                if *argument == value {
+                    *argument = replacement;
+                }
+            }
+        }
+        if let Some(terminator) = block.terminator.as_mut() {
+            for used in terminator.uses_mut() {
+                if *used == value {
+                    *used = replacement;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::ast::Object;
+    use crate::yul::ir::verifier;
+    use crate::yul::ir::BlockId;
+    use crate::yul::ir::Edge;
+    use crate::yul::ir::Terminator;
+    use crate::yul::semantic;
+    use crate::yul::u256::U256;
+
+    /// Returns the kind of the terminator, or `none` for a block under construction.
+    fn terminator_kind(terminator: Option<&Terminator>) -> &'static str {
+        match terminator {
+            Some(Terminator::Jump(_)) => "jump",
+            Some(Terminator::Branch { .. }) => "branch",
+            Some(Terminator::Switch { .. }) => "switch",
+            Some(Terminator::Return(_)) => "return",
+            Some(Terminator::Unreachable) => "unreachable",
+            None => "none",
+        }
+    }
+
+    #[test]
+    fn builds_ssa_with_block_parameters() {
+        let yul = r#"object "A" {
+    code {
+        let s := 0
+        for { let i := 0 } lt(i, calldataload(0)) { i := add(i, 1) } {
+            if eq(i, 7) { continue }
+            if eq(i, 9) { break }
+            s := add(s, i)
+        }
+        switch f(s)
+        case 0 { revert(0, 0) }
+        default { s := 1 }
+        sstore(0, s)
+        function f(a) -> r {
+            r := a
+            if iszero(a) { leave }
+            r := not(a)
+        }
+    }
+}
+"#;
+        let object = Object::parse(yul).expect("Always valid");
+        let analysis = semantic::analyze(&object);
+        assert!(analysis.errors.is_empty());
+        let mut ir = super::build(&object, &analysis.symbols).expect("Always valid");
+        verifier::verify(&ir).expect("Always valid");
+
+        let code = &ir.code;
+        assert_eq!(
+            code.blocks
+                .iter()
+                .map(|block| block.parameters.len())
+                .collect::<Vec<_>>(),
+            vec![0, 0, 2, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            code.blocks
+                .iter()
+                .map(|block| terminator_kind(block.terminator.as_ref()))
+                .collect::<Vec<_>>(),
+            vec![
+                "jump",
+                "return",
+                "branch",
+                "branch",
+                "jump",
+                "switch",
+                "jump",
+                "branch",
+                "jump",
+                "jump",
+                "unreachable",
+                "jump",
+                "jump",
+            ]
+        );
+        let predecessors = code.predecessors();
+        assert_eq!(predecessors[2], vec![BlockId(0), BlockId(4)]);
+        assert_eq!(predecessors[4], vec![BlockId(6), BlockId(9)]);
+        let Some(Terminator::Switch { cases, default, .. }) = code.blocks[5].terminator.as_ref()
+        else {
+            panic!("Expected a switch");
+        };
+        assert_eq!(
+            cases
+                .iter()
+                .map(|(value, edge)| (*value, edge.target))
+                .collect::<Vec<_>>(),
+            vec![(U256::ZERO, BlockId(10))]
+        );
+        assert_eq!(default.target, BlockId(11));
+
+        let [function] = ir.functions.as_slice() else {
+            panic!("Expected one function");
+        };
+        assert_eq!((function.parameters.len(), function.returns), (1, 1));
+        assert_eq!(function.blocks.len(), 4);
+        let exit = &function.blocks[1];
+        assert_eq!(exit.parameters.len(), 1);
+        assert_eq!(
+            exit.terminator,
+            Some(Terminator::Return(exit.parameters.clone()))
+        );
+        assert_eq!(
+            function.blocks[2].terminator,
+            Some(Terminator::Jump(Edge {
+                target: BlockId(1),
+                arguments: function.parameters.clone(),
+            }))
+        );
+
+        ir.code.blocks[2].parameters.pop();
+        assert_eq!(
+            verifier::verify(&ir)
+                .expect_err("Always invalid")
+                .to_string(),
+            "Object `A`, function `code`: bb0 passes 2 arguments to bb2, which has 1 parameters"
+        );
+    }
+
+    #[test]
+    fn keeps_object_access_names_symbolic() {
+        let yul = r#"object "A" {
+    code {
+        let size := datasize("TransparentUpgradeableProxy_1234_deployed")
+        datacopy(0, dataoffset("TransparentUpgradeableProxy_1234_deployed"), size)
+        setimmutable(0, "implementation", linkersymbol("contracts/Library.sol:Library"))
+        return(0, size)
+    }
+    object "TransparentUpgradeableProxy_1234_deployed" {
+        code { sstore(0, loadimmutable("implementation")) }
+    }
+}
+"#;
+        let object = Object::parse(yul).expect("Always valid");
+        let analysis = semantic::analyze(&object);
+        assert!(analysis.errors.is_empty());
+        let ir = super::build(&object, &analysis.symbols).expect("Always valid");
+        verifier::verify(&ir).expect("Always valid");
+        assert_eq!(
+            ir.to_string(),
+            r#"object "A" {
+    code {
+    bb0:
+        %0 = datasize("TransparentUpgradeableProxy_1234_deployed")
+        %1 = dataoffset("TransparentUpgradeableProxy_1234_deployed")
+        %2 = 0x0
+        datacopy(%2, %1, %0)
+        %3 = linkersymbol("contracts/Library.sol:Library")
+        %4 = 0x0
+        setimmutable(%4, "implementation", %3)
+        %5 = 0x0
+        return(%5, %0)
+        unreachable
+    }
+    object "TransparentUpgradeableProxy_1234_deployed" {
+        code {
+        bb0:
+            %0 = loadimmutable("implementation")
+            %1 = 0x0
+            sstore(%1, %0)
+            jump bb1
+        bb1:
+            return
+        }
+    }
+}
+"#
+        );
+
+        let object = Object::parse(
+            r#"object "B" { code { pop(datasize(0x20)) pop(loadimmutable(calldataload(0))) } }"#,
+        )
+        .expect("Always valid");
+        let errors = semantic::analyze(&object)
+            .errors
+            .into_iter()
+            .map(|error| error.message)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                "Expected a string literal name.".to_owned(),
+                "Expected a string literal name.".to_owned(),
+            ]
+        );
+    }
+}
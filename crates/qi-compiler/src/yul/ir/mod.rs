@@ -0,0 +1,378 @@
+//! The Yul mid-level IR: a control-flow graph of basic blocks per function, in the SSA form.
+//! The `phi` nodes are the basic block parameters, which the jumping edges pass the arguments to.
+//! The Yul builtins are kept as operations, so the analyses and optimizations can rely on their meaning.
+
+pub mod builder;
+pub mod passes;
+pub mod verifier;
+
+use crate::yul::semantic::builtin::Builtin;
+use crate::yul::u256::U256;
+
+/// The SSA value, defined once by a function parameter, a block parameter or an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Value(pub u32);
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "%{}", self.0)
+    }
+}
+
+/// The basic block index in its function, the entry block being `bb0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlockId(pub usize);
+
+impl std::fmt::Display for BlockId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bb{}", self.0)
+    }
+}
+
+/// The function index in its object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FunctionId(pub usize);
+
+/// The instruction operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    /// The constant, from a literal or a variable declared without a value.
+    Constant(U256),
+    /// The builtin call.
+    Builtin(&'static Builtin),
+    /// The object access builtin call, whose name argument is kept apart from the value arguments.
+    ObjectAccess {
+        /// The builtin, such as `datasize` or `setimmutable`.
+        builtin: &'static Builtin,
+        /// The object, immutable or library name.
+        name: String,
+    },
+    /// The user-defined function call.
+    Call(FunctionId),
+}
+
+/// The instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// The defined values.
+    pub results: Vec<Value>,
+    /// The operation.
+    pub operation: Operation,
+    /// The arguments, in the Yul order. They are evaluated right to left before the instruction.
+    pub arguments: Vec<Value>,
+}
+
+/// The control-flow edge, passing the arguments to the target block parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    /// The target block.
+    pub target: BlockId,
+    /// The target block parameter values.
+    pub arguments: Vec<Value>,
+}
+
+impl Edge {
+    /// A shortcut constructor.
+    pub fn new(target: BlockId) -> Self {
+        Self {
+            target,
+            arguments: Vec::new(),
+        }
+    }
+}
+
+/// The basic block terminator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Terminator {
+    /// The unconditional jump.
+    Jump(Edge),
+    /// The jump to `then` if the condition is non-zero, and to `else` otherwise.
+    Branch {
+        /// The condition.
+        condition: Value,
+        /// The edge taken if the condition is non-zero.
+        then: Edge,
+        /// The edge taken if the condition is zero.
+        r#else: Edge,
+    },
+    /// The jump to the case matching the value, or to the default one.
+    Switch {
+        /// The switched value.
+        value: Value,
+        /// The case values and edges.
+        cases: Vec<(U256, Edge)>,
+        /// The default edge, which is the code after the `switch` if there is no `default` case.
+        default: Edge,
+    },
+    /// The function return with the values of the return variables, or the end of the object code.
+    Return(Vec<Value>),
+    /// The end of the block nothing follows, such as a terminating builtin call.
+    Unreachable,
+}
+
+impl Terminator {
+    /// Returns the outgoing edges.
+    pub fn edges(&self) -> Vec<&Edge> {
+        match self {
+            Self::Jump(edge) => vec![edge],
+            Self::Branch { then, r#else, .. } => vec![then, r#else],
+            Self::Switch { cases, default, .. } => cases
+                .iter()
+                .map(|(_, edge)| edge)
+                .chain(std::iter::once(default))
+                .collect(),
+            Self::Return(_) | Self::Unreachable => Vec::new(),
+        }
+    }
+
+    /// Returns the outgoing edges.
+    pub fn edges_mut(&mut self) -> Vec<&mut Edge> {
+        match self {
+            Self::Jump(edge) => vec![edge],
+            Self::Branch { then, r#else, .. } => vec![then, r#else],
+            Self::Switch { cases, default, .. } => cases
+                .iter_mut()
+                .map(|(_, edge)| edge)
+                .chain(std::iter::once(default))
+                .collect(),
+            Self::Return(_) | Self::Unreachable => Vec::new(),
+        }
+    }
+
+    /// Returns the used values, including the edge arguments.
+    pub fn uses(&self) -> Vec<Value> {
+        let mut uses = match self {
+            Self::Branch { condition, .. } => vec![*condition],
+            Self::Switch { value, .. } => vec![*value],
+            Self::Return(values) => values.clone(),
+            Self::Jump(_) | Self::Unreachable => Vec::new(),
+        };
+        for edge in self.edges() {
+            uses.extend(edge.arguments.iter().copied());
+        }
+        uses
+    }
+
+    /// Returns the used values, including the edge arguments.
+    pub fn uses_mut(&mut self) -> Vec<&mut Value> {
+        let (mut uses, edges) = match self {
+            Self::Jump(edge) => (Vec::new(), vec![edge]),
+            Self::Branch {
+                condition,
+                then,
+                r#else,
+            } => (vec![condition], vec![then, r#else]),
+            Self::Switch {
+                value,
+                cases,
+                default,
+            } => (
+                vec![value],
+                cases
+                    .iter_mut()
+                    .map(|(_, edge)| edge)
+                    .chain(std::iter::once(default))
+                    .collect(),
+            ),
+            Self::Return(values) => (values.iter_mut().collect(), Vec::new()),
+            Self::Unreachable => (Vec::new(), Vec::new()),
+        };
+        for edge in edges {
+            uses.extend(edge.arguments.iter_mut());
+        }
+        uses
+    }
+}
+
+/// The basic block.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BasicBlock {
+    /// The parameters, which are the `phi` nodes.
+    pub parameters: Vec<Value>,
+    /// The instructions.
+    pub instructions: Vec<Instruction>,
+    /// The terminator, which only a block under construction lacks.
+    pub terminator: Option<Terminator>,
+}
+
+/// The function, or the code of an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    /// The name, unique in the object.
+    pub name: String,
+    /// The parameters.
+    pub parameters: Vec<Value>,
+    /// The number of returned values.
+    pub returns: usize,
+    /// The basic blocks, the entry one first.
+    pub blocks: Vec<BasicBlock>,
+}
+
+impl Function {
+    /// Returns the predecessors of every block.
+    pub fn predecessors(&self) -> Vec<Vec<BlockId>> {
+        let mut predecessors = vec![Vec::new(); self.blocks.len()];
+        for (index, block) in self.blocks.iter().enumerate() {
+            for edge in block.terminator.iter().flat_map(Terminator::edges) {
+                if let Some(predecessors) = predecessors.get_mut(edge.target.0) {
+                    predecessors.push(BlockId(index));
+                }
+            }
+        }
+        predecessors
+    }
+}
+
+/// The object, with its code, functions and inner objects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Object {
+    /// The object name.
+    pub name: String,
+    /// The object code.
+    pub code: Function,
+    /// The functions defined in the code, at any depth.
+    pub functions: Vec<Function>,
+    /// The inner objects.
+    pub objects: Vec<Object>,
+}
+
+impl std::fmt::Display for Object {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, "")
+    }
+}
+
+impl Object {
+    /// Writes the object dump with the given indentation.
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, indent: &str) -> std::fmt::Result {
+        let inner = format!("{indent}    ");
+        writeln!(f, "{indent}object \"{}\" {{", self.name)?;
+        writeln!(f, "{inner}code {{")?;
+        self.write_blocks(f, &self.code, inner.as_str())?;
+        writeln!(f, "{inner}}}")?;
+        for function in self.functions.iter() {
+            write!(f, "{inner}function @{}(", function.name)?;
+            write_values(f, function.parameters.as_slice())?;
+            writeln!(f, ") -> {} {{", function.returns)?;
+            self.write_blocks(f, function, inner.as_str())?;
+            writeln!(f, "{inner}}}")?;
+        }
+        for object in self.objects.iter() {
+            object.write(f, inner.as_str())?;
+        }
+        writeln!(f, "{indent}}}")
+    }
+
+    /// Writes the function blocks with the given indentation.
+    fn write_blocks(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        function: &Function,
+        indent: &str,
+    ) -> std::fmt::Result {
+        for (index, block) in function.blocks.iter().enumerate() {
+            write!(f, "{indent}{}", BlockId(index))?;
+            if !block.parameters.is_empty() {
+                write!(f, "(")?;
+                write_values(f, block.parameters.as_slice())?;
+                write!(f, ")")?;
+            }
+            writeln!(f, ":")?;
+            for instruction in block.instructions.iter() {
+                write!(f, "{indent}    ")?;
+                if !instruction.results.is_empty() {
+                    write_values(f, instruction.results.as_slice())?;
+                    write!(f, " = ")?;
+                }
+                match &instruction.operation {
+                    Operation::Constant(value) => write!(f, "{value:#x}")?,
+                    Operation::Builtin(builtin) => {
+                        write!(f, "{}(", builtin.name)?;
+                        write_values(f, instruction.arguments.as_slice())?;
+                        write!(f, ")")?;
+                    }
+                    Operation::ObjectAccess { builtin, name } => {
+                        let index = builtin.name_parameter().unwrap_or_default();
+                        let (before, after) = instruction
+                            .arguments
+                            .split_at(index.min(instruction.arguments.len()));
+                        write!(f, "{}(", builtin.name)?;
+                        write_values(f, before)?;
+                        if !before.is_empty() {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{name:?}")?;
+                        if !after.is_empty() {
+                            write!(f, ", ")?;
+                        }
+                        write_values(f, after)?;
+                        write!(f, ")")?;
+                    }
+                    Operation::Call(id) => {
+                        write!(f, "call @{}(", self.functions[id.0].name)?;
+                        write_values(f, instruction.arguments.as_slice())?;
+                        write!(f, ")")?;
+                    }
+                }
+                writeln!(f)?;
+            }
+            write!(f, "{indent}    ")?;
+            match block.terminator.as_ref() {
+                Some(Terminator::Jump(edge)) => write!(f, "jump {edge}")?,
+                Some(Terminator::Branch {
+                    condition,
+                    then,
+                    r#else,
+                }) => write!(f, "branch {condition}, {then}, {else}")?,
+                Some(Terminator::Switch {
+                    value,
+                    cases,
+                    default,
+                }) => {
+                    write!(f, "switch {value} [")?;
+                    for (index, (case, edge)) in cases.iter().enumerate() {
+                        if index > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{case:#x}: {edge}")?;
+                    }
+                    write!(f, "], default {default}")?;
+                }
+                Some(Terminator::Return(values)) => {
+                    write!(f, "return")?;
+                    if !values.is_empty() {
+                        write!(f, " ")?;
+                        write_values(f, values.as_slice())?;
+                    }
+                }
+                Some(Terminator::Unreachable) => write!(f, "unreachable")?,
+                None => write!(f, "<unterminated>")?,
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Edge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.target)?;
+        if !self.arguments.is_empty() {
+            write!(f, "(")?;
+            write_values(f, self.arguments.as_slice())?;
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the comma-separated values.
+fn write_values(f: &mut std::fmt::Formatter<'_>, values: &[Value]) -> std::fmt::Result {
+    for (index, value) in values.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{value}")?;
+    }
+    Ok(())
+}
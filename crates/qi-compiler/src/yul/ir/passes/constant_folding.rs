@@ -0,0 +1,300 @@
+//! The constant folding over the 256-bit EVM arithmetic.
+//! The pure builtins with constant arguments become constants, and the branches and switches on
+//! constants become jumps, leaving the other targets to the unreachable block removal.
+
+use crate::yul::ir::Function;
+use crate::yul::ir::Operation;
+use crate::yul::ir::Terminator;
+use crate::yul::u256::U256;
+
+use super::is_pure;
+
+/// Folds the constants in the function.
+pub fn run(function: &mut Function) {
+    let mut constants = super::constants(function);
+    for block in super::reverse_postorder(function).into_iter() {
+        let block = &mut function.blocks[block.0];
+        for instruction in block.instructions.iter_mut() {
+            let Operation::Builtin(builtin) = instruction.operation else {
+                continue;
+            };
+            if !is_pure(builtin) {
+                continue;
+            }
+            let Some(arguments) = instruction
+                .arguments
+                .iter()
+                .map(|argument| constants.get(argument).copied())
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+            let Some(value) = evaluate(builtin.name, arguments.as_slice()) else {
+                continue;
+            };
+            instruction.operation = Operation::Constant(value);
+            instruction.arguments.clear();
+            constants.insert(instruction.results[0], value);
+        }
+
+        let target = match block.terminator.as_ref() {
+            Some(Terminator::Branch {
+                condition,
+                then,
+                r#else,
+            }) => match constants.get(condition) {
+                Some(condition) if condition.is_zero() => r#else.clone(),
+                Some(_) => then.clone(),
+                None => continue,
+            },
+            Some(Terminator::Switch {
+                value,
+                cases,
+                default,
+            }) => match constants.get(value) {
+                Some(value) => cases
+                    .iter()
+                    .find(|(case, _)| case == value)
+                    .map(|(_, edge)| edge)
+                    .unwrap_or(default)
+                    .clone(),
+                None => continue,
+            },
+            _ => continue,
+        };
+        block.terminator = Some(Terminator::Jump(target));
+    }
+}
+
+/// Evaluates the pure builtin with the EVM semantics, where the division by zero gives zero.
+pub fn evaluate(name: &str, arguments: &[U256]) -> Option<U256> {
+    let value = match (name, arguments) {
+        ("add", [a, b]) => a.wrapping_add(b),
+        ("sub", [a, b]) => a.wrapping_sub(b),
+        ("mul", [a, b]) => a.wrapping_mul(b),
+        ("div", [a, b]) => a
+            .div_rem(b)
+            .map(|(quotient, _)| quotient)
+            .unwrap_or_default(),
+        ("mod", [a, b]) => a
+            .div_rem(b)
+            .map(|(_, remainder)| remainder)
+            .unwrap_or_default(),
+        ("sdiv", [a, b]) => {
+            let quotient = absolute(a)
+                .div_rem(&absolute(b))
+                .map(|(quotient, _)| quotient)
+                .unwrap_or_default();
+            if a.is_negative() != b.is_negative() {
+                quotient.wrapping_neg()
+            } else {
+                quotient
+            }
+        }
+        ("smod", [a, b]) => {
+            let remainder = absolute(a)
+                .div_rem(&absolute(b))
+                .map(|(_, remainder)| remainder)
+                .unwrap_or_default();
+            if a.is_negative() {
+                remainder.wrapping_neg()
+            } else {
+                remainder
+            }
+        }
+        ("exp", [base, exponent]) => {
+            let mut result = U256::ONE;
+            for bit in (0..exponent.bits()).rev() {
+                result = result.wrapping_mul(&result);
+                if exponent.bit(bit) {
+                    result = result.wrapping_mul(base);
+                }
+            }
+            result
+        }
+        ("not", [a]) => !*a,
+        ("lt", [a, b]) => U256::from(a < b),
+        ("gt", [a, b]) => U256::from(a > b),
+        ("slt", [a, b]) => U256::from(signed_less(a, b)),
+        ("sgt", [a, b]) => U256::from(signed_less(b, a)),
+        ("eq", [a, b]) => U256::from(a == b),
+        ("iszero", [a]) => U256::from(a.is_zero()),
+        ("and", [a, b]) => *a & *b,
+        ("or", [a, b]) => *a | *b,
+        ("xor", [a, b]) => *a ^ *b,
+        ("byte", [index, value]) => match index.as_u64() {
+            Some(index) if index < 32 => (*value >> (248 - index as u32 * 8)) & U256::from(0xff),
+            _ => U256::ZERO,
+        },
+        ("shl", [shift, value]) => *value << shift_amount(shift),
+        ("shr", [shift, value]) => *value >> shift_amount(shift),
+        ("sar", [shift, value]) => {
+            if value.is_negative() {
+                !(!*value >> shift_amount(shift))
+            } else {
+                *value >> shift_amount(shift)
+            }
+        }
+        ("addmod" | "mulmod", [_, _, modulus]) if modulus.is_zero() => U256::ZERO,
+        ("addmod", [a, b, modulus]) => add_mod(&reduce(a, modulus), &reduce(b, modulus), modulus),
+        ("mulmod", [a, b, modulus]) => {
+            let a = reduce(a, modulus);
+            let mut result = U256::ZERO;
+            for bit in (0..b.bits()).rev() {
+                result = add_mod(&result, &result, modulus);
+                if b.bit(bit) {
+                    result = add_mod(&result, &a, modulus);
+                }
+            }
+            result
+        }
+        ("signextend", [size, value]) => match size.as_u64() {
+            Some(size) if size < 31 => {
+                let bit = size as u32 * 8 + 7;
+                let mask = (U256::ONE << (bit + 1)).wrapping_sub(&U256::ONE);
+                if value.bit(bit) {
+                    *value | !mask
+                } else {
+                    *value & mask
+                }
+            }
+            _ => *value,
+        },
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Returns the absolute value of the two's complement signed integer.
+fn absolute(value: &U256) -> U256 {
+    if value.is_negative() {
+        value.wrapping_neg()
+    } else {
+        *value
+    }
+}
+
+/// Whether `a < b` as two's complement signed integers.
+fn signed_less(a: &U256, b: &U256) -> bool {
+    match (a.is_negative(), b.is_negative()) {
+        (true, false) => true,
+        (false, true) => false,
+        _ => a < b,
+    }
+}
+
+/// Returns the shift amount, where anything above 255 shifts all the bits out.
+fn shift_amount(shift: &U256) -> u32 {
+    shift
+        .as_u64()
+        .filter(|shift| *shift < 256)
+        .map(|shift| shift as u32)
+        .unwrap_or(256)
+}
+
+/// Returns the value modulo the non-zero `modulus`.
+fn reduce(value: &U256, modulus: &U256) -> U256 {
+    value
+        .div_rem(modulus)
+        .map(|(_, remainder)| remainder)
+        .expect("Always non-zero")
+}
+
+/// Computes `(a + b) % modulus` for `a` and `b` below the `modulus`, without losing the carry.
+fn add_mod(a: &U256, b: &U256, modulus: &U256) -> U256 {
+    let (sum, is_overflow) = a.overflowing_add(b);
+    if is_overflow || sum >= *modulus {
+        sum.wrapping_sub(modulus)
+    } else {
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::u256::U256;
+
+    /// Returns the word with the hexadecimal value.
+    fn word(hex: &str) -> U256 {
+        let bytes = hex::decode(format!("{hex:0>64}")).expect("Always valid");
+        U256::from_be_bytes(bytes.try_into().expect("Always 32 bytes"))
+    }
+
+    #[test]
+    fn evaluates_the_edge_cases_like_the_evm() {
+        let max = "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+        let min = "8000000000000000000000000000000000000000000000000000000000000000";
+        let minus = |value: &str| word(value).wrapping_neg();
+        let cases = [
+            ("div", vec![word(max), word("0")], word("0")),
+            ("mod", vec![word(max), word("0")], word("0")),
+            ("sdiv", vec![word(min), word(max)], word(min)),
+            ("sdiv", vec![word(min), word("0")], word("0")),
+            ("sdiv", vec![minus("7"), word("2")], minus("3")),
+            ("sdiv", vec![word("7"), minus("2")], minus("3")),
+            ("smod", vec![word(min), word(max)], word("0")),
+            ("smod", vec![minus("7"), word("3")], minus("1")),
+            ("smod", vec![word("7"), minus("3")], word("1")),
+            ("smod", vec![minus("7"), word("0")], word("0")),
+            ("signextend", vec![word("0"), word("ff")], word(max)),
+            ("signextend", vec![word("0"), word("7f")], word("7f")),
+            ("signextend", vec![word("0"), word("17f")], word("7f")),
+            ("signextend", vec![word("1"), word("ff80")], minus("80")),
+            (
+                "signextend",
+                vec![
+                    word("1e"),
+                    word("0080000000000000000000000000000000000000000000000000000000000000"),
+                ],
+                word("ff80000000000000000000000000000000000000000000000000000000000000"),
+            ),
+            ("signextend", vec![word("1f"), word(min)], word(min)),
+            ("signextend", vec![word(max), word("ff")], word("ff")),
+            (
+                "sar",
+                vec![word("1"), word(min)],
+                word("c000000000000000000000000000000000000000000000000000000000000000"),
+            ),
+            ("sar", vec![word("ff"), word(min)], word(max)),
+            ("sar", vec![word("100"), word(min)], word(max)),
+            ("sar", vec![word(max), word("1")], word("0")),
+            ("sar", vec![word("4"), minus("10")], word(max)),
+            ("shl", vec![word("ff"), word("1")], word(min)),
+            ("shl", vec![word("100"), word("1")], word("0")),
+            ("shr", vec![word("100"), word(max)], word("0")),
+            ("byte", vec![word("0"), word(min)], word("80")),
+            ("byte", vec![word("20"), word(max)], word("0")),
+            ("addmod", vec![word(max), word(max), minus("2")], word("2")),
+            ("addmod", vec![word(max), word("2"), word("3")], word("2")),
+            ("addmod", vec![word(max), word(max), word(max)], word("0")),
+            ("addmod", vec![word("1"), word("2"), word("0")], word("0")),
+            (
+                "mulmod",
+                vec![word(max), word(max), word("3039")],
+                word("13b"),
+            ),
+            ("mulmod", vec![word(max), word(max), minus("2")], word("1")),
+            ("mulmod", vec![word(min), word("2"), word(max)], word("1")),
+            (
+                "mulmod",
+                vec![word(max), minus("2"), word(min).wrapping_add(&U256::ONE)],
+                word("c"),
+            ),
+            ("mulmod", vec![word(max), word(max), word("0")], word("0")),
+            (
+                "exp",
+                vec![word("3"), word(max)],
+                word("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaab"),
+            ),
+            ("exp", vec![word("2"), word("100")], word("0")),
+            ("exp", vec![word("0"), word("0")], word("1")),
+        ];
+        for (name, arguments, expected) in cases.iter() {
+            assert_eq!(
+                super::evaluate(name, arguments.as_slice()),
+                Some(*expected),
+                "{name}({arguments:x?})"
+            );
+        }
+    }
+}
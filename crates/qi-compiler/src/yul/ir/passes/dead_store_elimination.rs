@@ -0,0 +1,82 @@
+//! The dead store elimination.
+//! Each block is scanned backwards, collecting the locations the later stores overwrite before
+//! anything may read them: the memory words at constant offsets and the storage slots. An earlier
+//! store to such a location is removed. Everything is considered read at the end of the block.
+
+use std::collections::HashSet;
+
+use crate::yul::ir::Function;
+use crate::yul::ir::Operation;
+use crate::yul::u256::U256;
+
+use super::Slot;
+
+/// Removes the dead stores in the function.
+pub fn run(function: &mut Function) {
+    let constants = super::constants(function);
+
+    for block in function.blocks.iter_mut() {
+        let mut words = HashSet::<U256>::new();
+        let mut slots = HashSet::<Slot>::new();
+        let mut dead = vec![false; block.instructions.len()];
+
+        for (index, instruction) in block.instructions.iter().enumerate().rev() {
+            let builtin = match instruction.operation {
+                Operation::Builtin(builtin) | Operation::ObjectAccess { builtin, .. } => builtin,
+                Operation::Call(_) => {
+                    words.clear();
+                    slots.clear();
+                    continue;
+                }
+                Operation::Constant(_) => continue,
+            };
+            let offset = instruction
+                .arguments
+                .first()
+                .and_then(|argument| constants.get(argument));
+            match builtin.name {
+                "mstore" => match offset {
+                    Some(offset) if words.contains(offset) => dead[index] = true,
+                    Some(offset) => {
+                        words.insert(*offset);
+                    }
+                    None => {}
+                },
+                "mstore8" => {
+                    if let Some(offset) = offset
+                        && words.iter().any(|word| {
+                            word <= offset && offset.wrapping_sub(word) < U256::from(32)
+                        })
+                    {
+                        dead[index] = true;
+                    }
+                }
+                "sstore" => {
+                    let slot = Slot::new(instruction.arguments[0], &constants);
+                    if !slots.insert(slot) {
+                        dead[index] = true;
+                    }
+                }
+                "sload" => match Slot::new(instruction.arguments[0], &constants) {
+                    slot @ Slot::Constant(_) => {
+                        slots.retain(|known| matches!(known, Slot::Constant(_)) && *known != slot)
+                    }
+                    Slot::Value(_) => slots.clear(),
+                },
+                _ => {
+                    if super::reads_memory(builtin) {
+                        words.clear();
+                    }
+                    if super::calls_out(builtin) || builtin.name == "staticcall" {
+                        slots.clear();
+                    }
+                }
+            }
+        }
+
+        let mut dead = dead.into_iter();
+        block
+            .instructions
+            .retain(|_| !dead.next().expect("Always as many as instructions"));
+    }
+}
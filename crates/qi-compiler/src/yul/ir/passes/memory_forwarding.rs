@@ -0,0 +1,119 @@
+//! The memory store-to-load forwarding.
+//! The words written by `mstore` and read by `mload` at constant offsets are tracked along the
+//! chains of blocks with a single predecessor, so a later `mload` of the same word takes the known
+//! value, and `keccak256` over the words known to be constants becomes a constant.
+
+use std::collections::HashMap;
+
+use sha3::Digest;
+
+use crate::yul::ir::Function;
+use crate::yul::ir::Operation;
+use crate::yul::ir::Value;
+use crate::yul::u256::U256;
+
+use super::Substitution;
+
+/// The known memory words, keyed by their constant offsets.
+type Memory = HashMap<U256, Value>;
+
+/// Forwards the memory values in the function.
+pub fn run(function: &mut Function) {
+    let mut constants = super::constants(function);
+    let predecessors = function.predecessors();
+    let mut states: Vec<Option<Memory>> = vec![None; function.blocks.len()];
+    let mut substitution = Substitution::default();
+
+    for block in super::reverse_postorder(function).into_iter() {
+        let mut memory = super::single_predecessor(predecessors.as_slice(), block)
+            .and_then(|predecessor| states[predecessor.0].clone())
+            .unwrap_or_default();
+
+        for instruction in function.blocks[block.0].instructions.iter_mut() {
+            let arguments = instruction
+                .arguments
+                .iter()
+                .map(|argument| substitution.resolve(*argument))
+                .collect::<Vec<_>>();
+            let offset = arguments
+                .first()
+                .and_then(|argument| constants.get(argument))
+                .copied();
+            match (&instruction.operation, offset) {
+                (Operation::Builtin(builtin), Some(offset)) if builtin.name == "mstore" => {
+                    memory.retain(|word, _| !overlaps(word, &offset, 32));
+                    memory.insert(offset, arguments[1]);
+                }
+                (Operation::Builtin(builtin), Some(offset)) if builtin.name == "mstore8" => {
+                    memory.retain(|word, _| !overlaps(word, &offset, 1));
+                }
+                (Operation::Builtin(builtin), Some(offset)) if builtin.name == "mload" => {
+                    let result = instruction.results[0];
+                    match memory.get(&offset) {
+                        Some(value) => substitution.insert(result, *value),
+                        None => {
+                            memory.insert(offset, result);
+                        }
+                    }
+                }
+                (Operation::Builtin(builtin), Some(offset)) if builtin.name == "keccak256" => {
+                    let Some(length) = constants.get(&arguments[1]).and_then(U256::as_u64) else {
+                        continue;
+                    };
+                    let Some(bytes) = bytes(&memory, &constants, &substitution, offset, length)
+                    else {
+                        continue;
+                    };
+                    let hash =
+                        U256::from_be_bytes(sha3::Keccak256::digest(bytes.as_slice()).into());
+                    instruction.operation = Operation::Constant(hash);
+                    instruction.arguments.clear();
+                    constants.insert(instruction.results[0], hash);
+                }
+                (Operation::Builtin(builtin) | Operation::ObjectAccess { builtin, .. }, _)
+                    if matches!(builtin.name, "mstore" | "mstore8")
+                        || super::writes_memory(builtin) =>
+                {
+                    memory.clear();
+                }
+                (Operation::Call(_), _) => memory.clear(),
+                _ => {}
+            }
+        }
+        states[block.0] = Some(memory);
+    }
+
+    substitution.apply(function);
+}
+
+/// Whether the known word at `word` overlaps the `length` bytes written at `offset`.
+fn overlaps(word: &U256, offset: &U256, length: u64) -> bool {
+    if word <= offset {
+        offset.wrapping_sub(word) < U256::from(32)
+    } else {
+        word.wrapping_sub(offset) < U256::from(length)
+    }
+}
+
+/// Returns the memory bytes, if they are all known constants.
+fn bytes(
+    memory: &Memory,
+    constants: &HashMap<Value, U256>,
+    substitution: &Substitution,
+    offset: U256,
+    length: u64,
+) -> Option<Vec<u8>> {
+    if length > memory.len() as u64 * 32 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(length as usize);
+    let mut word = offset;
+    while (bytes.len() as u64) < length {
+        let value = memory.get(&word)?;
+        let value = constants.get(&substitution.resolve(*value))?;
+        bytes.extend_from_slice(&value.to_be_bytes());
+        word = word.wrapping_add(&U256::from(32));
+    }
+    bytes.truncate(length as usize);
+    Some(bytes)
+}
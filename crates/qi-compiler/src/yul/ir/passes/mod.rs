@@ -0,0 +1,509 @@
+//! The Yul-aware optimization passes over the SSA IR.
+//! Unlike LLVM, they know the meaning of the EVM builtins, so they can evaluate them, forward the
+//! memory and storage values, and remove the stores nothing reads. Each pass is toggled by the
+//! optimizer settings, and the verifier runs after each of them if `verify each` is enabled.
+
+pub mod constant_folding;
+pub mod dead_store_elimination;
+pub mod memory_forwarding;
+pub mod sload_elimination;
+pub mod unused_function_removal;
+
+use std::collections::HashMap;
+
+use crate::optimizer::settings::yul_pass::YulPass;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::yul::semantic::builtin::Builtin;
+use crate::yul::u256::U256;
+
+use super::builder;
+use super::verifier;
+use super::BlockId;
+use super::Function;
+use super::Object;
+use super::Operation;
+use super::Terminator;
+use super::Value;
+
+/// The order the passes run in. The constant folding runs again after the forwarding passes,
+/// since the forwarded values may be constants.
+const PIPELINE: [YulPass; 6] = [
+    YulPass::ConstantFolding,
+    YulPass::MemoryForwarding,
+    YulPass::SloadElimination,
+    YulPass::ConstantFolding,
+    YulPass::DeadStoreElimination,
+    YulPass::UnusedFunctionRemoval,
+];
+
+/// Runs the enabled passes on the object and its inner objects.
+pub fn optimize(object: &mut Object, settings: &OptimizerSettings) -> anyhow::Result<()> {
+    for pass in PIPELINE.into_iter() {
+        if !settings.is_yul_pass_enabled(pass) {
+            continue;
+        }
+        run(object, pass);
+        if settings.is_verify_each_enabled {
+            verifier::verify(object)
+                .map_err(|error| anyhow::anyhow!("After the `{pass}` pass: {error}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs the pass on the object and its inner objects.
+fn run(object: &mut Object, pass: YulPass) {
+    let run_function: fn(&mut Function) = match pass {
+        YulPass::ConstantFolding => constant_folding::run,
+        YulPass::MemoryForwarding => memory_forwarding::run,
+        YulPass::SloadElimination => sload_elimination::run,
+        YulPass::DeadStoreElimination => dead_store_elimination::run,
+        YulPass::UnusedFunctionRemoval => |_| {},
+    };
+    if pass == YulPass::UnusedFunctionRemoval {
+        unused_function_removal::run(object);
+    }
+    let is_msize_used = is_msize_used(object);
+    for function in std::iter::once(&mut object.code).chain(object.functions.iter_mut()) {
+        run_function(function);
+        remove_unused_instructions(function, is_msize_used);
+    }
+    for object in object.objects.iter_mut() {
+        run(object, pass);
+    }
+}
+
+/// The value replacements, applied to the function at once.
+#[derive(Debug, Default)]
+struct Substitution {
+    /// The replacements, which may be replaced themselves.
+    replacements: HashMap<Value, Value>,
+}
+
+impl Substitution {
+    /// Replaces the uses of the value.
+    fn insert(&mut self, value: Value, replacement: Value) {
+        let replacement = self.resolve(replacement);
+        if replacement != value {
+            self.replacements.insert(value, replacement);
+        }
+    }
+
+    /// Returns the final replacement of the value.
+    fn resolve(&self, mut value: Value) -> Value {
+        while let Some(replacement) = self.replacements.get(&value) {
+            value = *replacement;
+        }
+        value
+    }
+
+    /// Applies the replacements to the function.
+    fn apply(&self, function: &mut Function) {
+        if self.replacements.is_empty() {
+            return;
+        }
+        for block in function.blocks.iter_mut() {
+            for instruction in block.instructions.iter_mut() {
+                for argument in instruction.arguments.iter_mut() {
+                    *argument = self.resolve(*argument);
+                }
+            }
+            if let Some(terminator) = block.terminator.as_mut() {
+                for value in terminator.uses_mut() {
+                    *value = self.resolve(*value);
+                }
+            }
+        }
+    }
+}
+
+/// The storage slot, the same if it is the same SSA value or equal constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Slot {
+    /// The slot known at compile time.
+    Constant(U256),
+    /// The slot computed at runtime, which may be any other one.
+    Value(Value),
+}
+
+impl Slot {
+    /// Returns the slot the value refers to.
+    fn new(value: Value, constants: &HashMap<Value, U256>) -> Self {
+        match constants.get(&value) {
+            Some(constant) => Self::Constant(*constant),
+            None => Self::Value(value),
+        }
+    }
+}
+
+/// Returns the values of the constants in the function.
+fn constants(function: &Function) -> HashMap<Value, U256> {
+    function
+        .blocks
+        .iter()
+        .flat_map(|block| block.instructions.iter())
+        .filter_map(|instruction| match instruction.operation {
+            Operation::Constant(value) => Some((instruction.results[0], value)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the blocks in the reverse postorder, where every block but a loop header comes after its predecessors.
+fn reverse_postorder(function: &Function) -> Vec<BlockId> {
+    let mut order = Vec::with_capacity(function.blocks.len());
+    let mut is_visited = vec![false; function.blocks.len()];
+    let mut stack = vec![(BlockId(0), 0)];
+    is_visited[0] = true;
+    while let Some((block, next)) = stack.pop() {
+        let edges = function.blocks[block.0]
+            .terminator
+            .as_ref()
+            .map(Terminator::edges)
+            .unwrap_or_default();
+        match edges.get(next) {
+            Some(edge) => {
+                stack.push((block, next + 1));
+                if !std::mem::replace(&mut is_visited[edge.target.0], true) {
+                    stack.push((edge.target, 0));
+                }
+            }
+            None => order.push(block),
+        }
+    }
+    order.reverse();
+    order
+}
+
+/// Returns the block the state at the start of the block comes from, which is its only
+/// predecessor. Such a predecessor dominates the block and comes before it in the reverse postorder.
+fn single_predecessor(predecessors: &[Vec<BlockId>], block: BlockId) -> Option<BlockId> {
+    match predecessors[block.0].as_slice() {
+        [predecessor] if *predecessor != block => Some(*predecessor),
+        _ => None,
+    }
+}
+
+/// Whether the object code uses `msize`, which makes the memory size observable.
+fn is_msize_used(object: &Object) -> bool {
+    std::iter::once(&object.code)
+        .chain(object.functions.iter())
+        .flat_map(|function| function.blocks.iter())
+        .flat_map(|block| block.instructions.iter())
+        .any(|instruction| {
+            matches!(instruction.operation, Operation::Builtin(builtin) if builtin.name == "msize")
+        })
+}
+
+/// Whether the builtin only computes its result from the arguments.
+fn is_pure(builtin: &Builtin) -> bool {
+    matches!(
+        builtin.name,
+        "add"
+            | "sub"
+            | "mul"
+            | "div"
+            | "sdiv"
+            | "mod"
+            | "smod"
+            | "exp"
+            | "not"
+            | "lt"
+            | "gt"
+            | "slt"
+            | "sgt"
+            | "eq"
+            | "iszero"
+            | "and"
+            | "or"
+            | "xor"
+            | "byte"
+            | "shl"
+            | "shr"
+            | "sar"
+            | "addmod"
+            | "mulmod"
+            | "signextend"
+    )
+}
+
+/// Whether the builtin call can be removed if its result is unused.
+/// The memory reads expand the memory, which only `msize` can tell.
+fn is_removable(builtin: &Builtin, is_msize_used: bool) -> bool {
+    is_pure(builtin)
+        || (!is_msize_used && matches!(builtin.name, "mload" | "keccak256"))
+        || matches!(
+            builtin.name,
+            "sload"
+                | "tload"
+                | "msize"
+                | "gas"
+                | "pc"
+                | "address"
+                | "balance"
+                | "selfbalance"
+                | "caller"
+                | "callvalue"
+                | "calldataload"
+                | "calldatasize"
+                | "codesize"
+                | "extcodesize"
+                | "extcodehash"
+                | "returndatasize"
+                | "chainid"
+                | "basefee"
+                | "blobbasefee"
+                | "origin"
+                | "gasprice"
+                | "blockhash"
+                | "blobhash"
+                | "coinbase"
+                | "timestamp"
+                | "number"
+                | "difficulty"
+                | "prevrandao"
+                | "gaslimit"
+                | "datasize"
+                | "dataoffset"
+                | "loadimmutable"
+                | "linkersymbol"
+                | "memoryguard"
+        )
+}
+
+/// Whether the builtin writes to the memory at the offsets other than its first argument.
+fn writes_memory(builtin: &Builtin) -> bool {
+    matches!(
+        builtin.name,
+        "mcopy"
+            | "calldatacopy"
+            | "codecopy"
+            | "extcodecopy"
+            | "returndatacopy"
+            | "datacopy"
+            | "setimmutable"
+            | "call"
+            | "callcode"
+            | "delegatecall"
+            | "staticcall"
+    )
+}
+
+/// Whether the builtin reads from the memory.
+fn reads_memory(builtin: &Builtin) -> bool {
+    matches!(
+        builtin.name,
+        "mload"
+            | "keccak256"
+            | "mcopy"
+            | "return"
+            | "revert"
+            | "log0"
+            | "log1"
+            | "log2"
+            | "log3"
+            | "log4"
+            | "create"
+            | "create2"
+            | "call"
+            | "callcode"
+            | "delegatecall"
+            | "staticcall"
+    )
+}
+
+/// Whether the builtin may change the storage other than by `sstore`, by calling back into the contract.
+fn calls_out(builtin: &Builtin) -> bool {
+    matches!(
+        builtin.name,
+        "call" | "callcode" | "delegatecall" | "create" | "create2"
+    )
+}
+
+/// Removes the instructions whose results are unused and which have no side effects.
+/// Removing an instruction may leave its arguments unused, so it is repeated until nothing changes.
+fn remove_unused_instructions(function: &mut Function, is_msize_used: bool) {
+    builder::remove_unreachable_blocks(function);
+    builder::remove_trivial_parameters(function);
+    loop {
+        let mut uses = HashMap::<Value, usize>::new();
+        for block in function.blocks.iter() {
+            for instruction in block.instructions.iter() {
+                for argument in instruction.arguments.iter() {
+                    *uses.entry(*argument).or_default() += 1;
+                }
+            }
+            for value in block.terminator.iter().flat_map(Terminator::uses) {
+                *uses.entry(value).or_default() += 1;
+            }
+        }
+
+        let mut is_changed = false;
+        for block in function.blocks.iter_mut() {
+            block.instructions.retain(|instruction| {
+                let is_removable = match instruction.operation {
+                    Operation::Constant(_) => true,
+                    Operation::Builtin(builtin) | Operation::ObjectAccess { builtin, .. } => {
+                        is_removable(builtin, is_msize_used)
+                    }
+                    Operation::Call(_) => false,
+                };
+                let is_unused = !instruction.results.is_empty()
+                    && instruction
+                        .results
+                        .iter()
+                        .all(|result| !uses.contains_key(result));
+                is_changed |= is_removable && is_unused;
+                !(is_removable && is_unused)
+            });
+        }
+        if !is_changed {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::optimizer::settings::yul_pass::YulPass;
+    use crate::optimizer::settings::Settings as OptimizerSettings;
+    use crate::yul::ast::Object;
+    use crate::yul::ir::builder;
+    use crate::yul::ir::Instruction;
+    use crate::yul::ir::Operation;
+    use crate::yul::ir::Terminator;
+    use crate::yul::ir::Value;
+    use crate::yul::semantic;
+
+    /// Whether the instruction calls the builtin.
+    fn is_builtin(instruction: &Instruction, name: &str) -> bool {
+        matches!(instruction.operation, Operation::Builtin(builtin) if builtin.name == name)
+    }
+
+    #[test]
+    fn optimizes_with_builtin_semantics() {
+        let yul = r#"object "A" {
+    code {
+        mstore(0, 0x20)
+        mstore(0, calldataload(0))
+        mstore(32, 7)
+        let hash := keccak256(0x20, 32)
+        sstore(hash, mload(0))
+        sstore(1, sdiv(sub(0, 6), 4))
+        sstore(1, mulmod(not(0), not(0), 7))
+        sstore(2, sar(1, shl(255, 1)))
+        sstore(3, signextend(0, 0xff))
+        sstore(5, sdiv(sub(0, 7), 2))
+        if iszero(eq(sload(1), sload(1))) { g() }
+        sstore(4, keccak256(0, 0))
+        return(0, mload(32))
+        function g() { revert(0, 0) }
+    }
+}
+"#;
+        let object = Object::parse(yul).expect("Always valid");
+        let analysis = semantic::analyze(&object);
+        let ir = builder::build(&object, &analysis.symbols).expect("Always valid");
+
+        let mut settings = OptimizerSettings::cycles();
+        settings.is_verify_each_enabled = true;
+        let mut optimized = ir.clone();
+        super::optimize(&mut optimized, &settings).expect("Always valid");
+
+        let code = &optimized.code;
+        let constants = super::constants(code);
+        let instructions = code
+            .blocks
+            .iter()
+            .flat_map(|block| block.instructions.iter())
+            .collect::<Vec<_>>();
+        let builtins = instructions
+            .iter()
+            .filter_map(|instruction| match instruction.operation {
+                Operation::Builtin(builtin) => Some(builtin.name),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            builtins,
+            vec![
+                "calldataload",
+                "mstore",
+                "mstore",
+                "sstore",
+                "sstore",
+                "sstore",
+                "sstore",
+                "sstore",
+                "sstore",
+                "mload",
+                "return"
+            ],
+            "the overwritten stores, the forwarded reads and the pure builtins must be removed"
+        );
+        assert!(
+            optimized.functions.is_empty(),
+            "the function called in the folded branch must be removed"
+        );
+        assert!(
+            code.blocks
+                .iter()
+                .all(|block| !matches!(block.terminator, Some(Terminator::Branch { .. }))),
+            "the always false branch must fold to a jump"
+        );
+
+        let calldata = instructions
+            .iter()
+            .find(|instruction| is_builtin(instruction, "calldataload"))
+            .expect("Always present")
+            .results[0];
+        let constant = |value: &Value| match constants.get(value) {
+            Some(constant) => format!("{constant:#x}"),
+            None => value.to_string(),
+        };
+        let stores = instructions
+            .iter()
+            .filter(|instruction| is_builtin(instruction, "sstore"))
+            .map(|instruction| {
+                (
+                    constant(&instruction.arguments[0]),
+                    constant(&instruction.arguments[1]),
+                )
+            })
+            .collect::<Vec<_>>();
+        let stored = |slot: &str, value: &str| (slot.to_owned(), value.to_owned());
+        assert_eq!(
+            stores,
+            vec![
+                stored(
+                    "0xa66cc928b5edb82af9bd49922954155ab7b0942694bea4ce44661d9a8736c688",
+                    calldata.to_string().as_str()
+                ),
+                stored("0x1", "0x1"),
+                stored(
+                    "0x2",
+                    "0xc000000000000000000000000000000000000000000000000000000000000000"
+                ),
+                stored(
+                    "0x3",
+                    "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+                ),
+                stored(
+                    "0x5",
+                    "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffd"
+                ),
+                stored(
+                    "0x4",
+                    "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+                ),
+            ]
+        );
+
+        for pass in YulPass::ALL.into_iter() {
+            settings.disable_yul_pass(pass);
+        }
+        let mut unoptimized = ir.clone();
+        super::optimize(&mut unoptimized, &settings).expect("Always valid");
+        assert_eq!(unoptimized, ir);
+    }
+}
@@ -0,0 +1,64 @@
+//! The redundant `sload` elimination.
+//! The values of the storage slots loaded or stored are tracked along the chains of blocks with a
+//! single predecessor, so a later `sload` of the same slot takes the known value. A store to a slot
+//! which is not a known constant may change any other one, and the calls which may re-enter the
+//! contract forget everything.
+
+use std::collections::HashMap;
+
+use crate::yul::ir::Function;
+use crate::yul::ir::Operation;
+use crate::yul::ir::Value;
+
+use super::Slot;
+use super::Substitution;
+
+/// Eliminates the redundant storage loads in the function.
+pub fn run(function: &mut Function) {
+    let constants = super::constants(function);
+    let predecessors = function.predecessors();
+    let mut states: Vec<Option<HashMap<Slot, Value>>> = vec![None; function.blocks.len()];
+    let mut substitution = Substitution::default();
+
+    for block in super::reverse_postorder(function).into_iter() {
+        let mut storage = super::single_predecessor(predecessors.as_slice(), block)
+            .and_then(|predecessor| states[predecessor.0].clone())
+            .unwrap_or_default();
+
+        for instruction in function.blocks[block.0].instructions.iter() {
+            let arguments = instruction
+                .arguments
+                .iter()
+                .map(|argument| substitution.resolve(*argument))
+                .collect::<Vec<_>>();
+            match instruction.operation {
+                Operation::Builtin(builtin) if builtin.name == "sload" => {
+                    let slot = Slot::new(arguments[0], &constants);
+                    let result = instruction.results[0];
+                    match storage.get(&slot) {
+                        Some(value) => substitution.insert(result, *value),
+                        None => {
+                            storage.insert(slot, result);
+                        }
+                    }
+                }
+                Operation::Builtin(builtin) if builtin.name == "sstore" => {
+                    let slot = Slot::new(arguments[0], &constants);
+                    match slot {
+                        Slot::Constant(_) => storage.retain(|known, _| {
+                            matches!(known, Slot::Constant(_)) && *known != slot
+                        }),
+                        Slot::Value(_) => storage.clear(),
+                    }
+                    storage.insert(slot, arguments[1]);
+                }
+                Operation::Builtin(builtin) if super::calls_out(builtin) => storage.clear(),
+                Operation::Call(_) => storage.clear(),
+                _ => {}
+            }
+        }
+        states[block.0] = Some(storage);
+    }
+
+    substitution.apply(function);
+}
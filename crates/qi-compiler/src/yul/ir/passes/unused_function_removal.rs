@@ -0,0 +1,50 @@
+//! The unused function removal.
+//! The functions not called from the object code, directly or through other functions, are
+//! removed, and the calls are renumbered.
+
+use crate::yul::ir::FunctionId;
+use crate::yul::ir::Object;
+use crate::yul::ir::Operation;
+
+/// Removes the unused functions of the object, but not of its inner objects.
+pub fn run(object: &mut Object) {
+    let mut is_used = vec![false; object.functions.len()];
+    let mut worklist = vec![&object.code];
+    while let Some(function) = worklist.pop() {
+        for instruction in function
+            .blocks
+            .iter()
+            .flat_map(|block| block.instructions.iter())
+        {
+            if let Operation::Call(id) = instruction.operation
+                && !std::mem::replace(&mut is_used[id.0], true)
+            {
+                worklist.push(&object.functions[id.0]);
+            }
+        }
+    }
+
+    let mut numbers = Vec::with_capacity(is_used.len());
+    let mut next = 0;
+    for is_used in is_used.iter() {
+        numbers.push(FunctionId(next));
+        next += *is_used as usize;
+    }
+    let functions = std::mem::take(&mut object.functions);
+    object.functions = functions
+        .into_iter()
+        .zip(is_used)
+        .filter_map(|(function, is_used)| is_used.then_some(function))
+        .collect();
+    for function in std::iter::once(&mut object.code).chain(object.functions.iter_mut()) {
+        for instruction in function
+            .blocks
+            .iter_mut()
+            .flat_map(|block| block.instructions.iter_mut())
+        {
+            if let Operation::Call(id) = instruction.operation {
+                instruction.operation = Operation::Call(numbers[id.0]);
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! The IR verifier.
+//! It checks the structure the passes rely on: every block is terminated and reachable, the edges
+//! pass as many arguments as the target has parameters, every value is defined once before its
+//! uses, and the calls match the builtin and function signatures.
+
+use std::collections::HashMap;
+
+use super::BlockId;
+use super::Function;
+use super::Object;
+use super::Operation;
+use super::Terminator;
+use super::Value;
+
+/// Verifies the object and its inner objects.
+pub fn verify(object: &Object) -> anyhow::Result<()> {
+    for function in std::iter::once(&object.code).chain(object.functions.iter()) {
+        verify_function(object, function).map_err(|error| {
+            anyhow::anyhow!(
+                "Object `{}`, function `{}`: {error}",
+                object.name,
+                function.name
+            )
+        })?;
+    }
+    for object in object.objects.iter() {
+        verify(object)?;
+    }
+    Ok(())
+}
+
+/// Verifies the function of the object.
+fn verify_function(object: &Object, function: &Function) -> anyhow::Result<()> {
+    if function.blocks.is_empty() {
+        anyhow::bail!("no entry block");
+    }
+    let predecessors = function.predecessors();
+    if !predecessors[0].is_empty() {
+        anyhow::bail!("the entry block {} has predecessors", BlockId(0));
+    }
+    if !function.blocks[0].parameters.is_empty() {
+        anyhow::bail!("the entry block {} has parameters", BlockId(0));
+    }
+
+    let mut definitions = HashMap::new();
+    let mut define = |value: Value, block: usize, position: usize| match definitions
+        .insert(value, (block, position))
+    {
+        Some(_) => anyhow::bail!("{value} is defined more than once"),
+        None => Ok(()),
+    };
+    for parameter in function.parameters.iter() {
+        define(*parameter, 0, 0)?;
+    }
+    for (index, block) in function.blocks.iter().enumerate() {
+        let Some(terminator) = block.terminator.as_ref() else {
+            anyhow::bail!("{} is not terminated", BlockId(index));
+        };
+        for parameter in block.parameters.iter() {
+            define(*parameter, index, 0)?;
+        }
+        for (position, instruction) in block.instructions.iter().enumerate() {
+            for result in instruction.results.iter() {
+                define(*result, index, position + 1)?;
+            }
+        }
+
+        for edge in terminator.edges() {
+            let Some(target) = function.blocks.get(edge.target.0) else {
+                anyhow::bail!("{} jumps to the missing {}", BlockId(index), edge.target);
+            };
+            if edge.arguments.len() != target.parameters.len() {
+                anyhow::bail!(
+                    "{} passes {} arguments to {}, which has {} parameters",
+                    BlockId(index),
+                    edge.arguments.len(),
+                    edge.target,
+                    target.parameters.len()
+                );
+            }
+        }
+        if let Terminator::Return(values) = terminator
+            && values.len() != function.returns
+        {
+            anyhow::bail!(
+                "{} returns {} values, but the function returns {}",
+                BlockId(index),
+                values.len(),
+                function.returns
+            );
+        }
+    }
+
+    let dominators = dominators(function, predecessors.as_slice())?;
+    for (index, block) in function.blocks.iter().enumerate() {
+        let check = |value: &Value, position: usize| -> anyhow::Result<()> {
+            let Some((definition_block, definition_position)) = definitions.get(value).copied()
+            else {
+                anyhow::bail!("{} uses the undefined {value}", BlockId(index));
+            };
+            let is_dominated = if definition_block == index {
+                definition_position < position
+            } else {
+                dominators[index][definition_block]
+            };
+            if !is_dominated {
+                anyhow::bail!(
+                    "{} uses {value}, whose definition does not dominate the use",
+                    BlockId(index)
+                );
+            }
+            Ok(())
+        };
+
+        for (position, instruction) in block.instructions.iter().enumerate() {
+            for argument in instruction.arguments.iter() {
+                check(argument, position + 1)?;
+            }
+            let (parameters, returns) = match instruction.operation {
+                Operation::Constant(_) => (0, 1),
+                Operation::Builtin(builtin) => (builtin.parameters, builtin.returns),
+                Operation::ObjectAccess { builtin, .. } => {
+                    if builtin.name_parameter().is_none() {
+                        anyhow::bail!(
+                            "{} passes a name to `{}`, which takes none",
+                            BlockId(index),
+                            builtin.name
+                        );
+                    }
+                    (builtin.parameters - 1, builtin.returns)
+                }
+                Operation::Call(id) => {
+                    let Some(callee) = object.functions.get(id.0) else {
+                        anyhow::bail!("{} calls the missing function #{}", BlockId(index), id.0);
+                    };
+                    (callee.parameters.len(), callee.returns)
+                }
+            };
+            if instruction.arguments.len() != parameters || instruction.results.len() != returns {
+                anyhow::bail!(
+                    "{} has an instruction with {} arguments and {} results, but {} and {} are expected",
+                    BlockId(index),
+                    instruction.arguments.len(),
+                    instruction.results.len(),
+                    parameters,
+                    returns
+                );
+            }
+        }
+        for value in block
+            .terminator
+            .as_ref()
+            .expect("Always terminated")
+            .uses()
+            .iter()
+        {
+            check(value, block.instructions.len() + 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes the dominators of every block, where `result[block][dominator]` tells whether it dominates the block.
+/// All the blocks must be reachable from the entry one.
+fn dominators(
+    function: &Function,
+    predecessors: &[Vec<BlockId>],
+) -> anyhow::Result<Vec<Vec<bool>>> {
+    let count = function.blocks.len();
+    let mut is_reachable = vec![false; count];
+    let mut worklist = vec![BlockId(0)];
+    while let Some(block) = worklist.pop() {
+        if std::mem::replace(&mut is_reachable[block.0], true) {
+            continue;
+        }
+        if let Some(terminator) = function.blocks[block.0].terminator.as_ref() {
+            worklist.extend(terminator.edges().into_iter().map(|edge| edge.target));
+        }
+    }
+    if let Some(block) = is_reachable.iter().position(|is_reachable| !is_reachable) {
+        anyhow::bail!("{} is unreachable", BlockId(block));
+    }
+
+    let mut dominators = vec![vec![true; count]; count];
+    dominators[0] = (0..count).map(|index| index == 0).collect();
+
+    let mut is_changed = true;
+    while is_changed {
+        is_changed = false;
+        for block in 1..count {
+            let mut intersection = vec![true; count];
+            for predecessor in predecessors[block].iter() {
+                for (result, dominates) in intersection
+                    .iter_mut()
+                    .zip(dominators[predecessor.0].iter())
+                {
+                    *result &= *dominates;
+                }
+            }
+            intersection[block] = true;
+            if intersection != dominators[block] {
+                dominators[block] = intersection;
+                is_changed = true;
+            }
+        }
+    }
+    Ok(dominators)
+}
@@ -0,0 +1,214 @@
+//! The lint detection visitor.
+
+use std::collections::HashSet;
+
+use rowan::TextRange;
+
+use crate::yul::ast::visit;
+use crate::yul::ast::visit::Visitor;
+use crate::yul::ast::Block;
+use crate::yul::ast::Expression;
+use crate::yul::ast::FunctionCall;
+use crate::yul::ast::Literal;
+use crate::yul::ast::LiteralKind;
+use crate::yul::ast::Object;
+use crate::yul::ast::Statement;
+
+use super::Finding;
+use super::Lint;
+
+/// The gas stipend of `transfer` and `send`.
+const STIPEND: u64 = 2300;
+
+/// The lint detection visitor.
+#[derive(Debug, Default)]
+pub struct Linter {
+    /// The findings.
+    findings: Vec<Finding>,
+    /// The `extcodesize` calls `solc` generates to check the callee of the external calls.
+    generated_checks: HashSet<TextRange>,
+    /// The `gas()` calls forwarding all the remaining gas to the external calls.
+    forwarded_gas: HashSet<TextRange>,
+    /// The variables holding the gas stipend.
+    stipend_variables: HashSet<String>,
+}
+
+impl Linter {
+    /// Checks the object and its inner objects.
+    pub fn check(object: &Object) -> Vec<Finding> {
+        let mut linter = Self::default();
+        linter.visit_object(object);
+        linter.findings
+    }
+
+    /// Adds the finding.
+    fn report(&mut self, lint: Lint, range: TextRange) {
+        self.findings.push(Finding { lint, range });
+    }
+
+    /// Returns the address checked by the `if iszero(extcodesize(<address>))` condition.
+    fn checked_address(statement: &Statement) -> Option<(&FunctionCall, &Expression)> {
+        let Statement::If(r#if) = statement else {
+            return None;
+        };
+        let iszero = call(&r#if.condition, "iszero")?;
+        let extcodesize = call(iszero.arguments.first()?, "extcodesize")?;
+        Some((extcodesize, extcodesize.arguments.first()?))
+    }
+
+    /// Checks whether the statements call the `address`, like `solc` does after checking it.
+    fn is_called(statements: &[Statement], address: &Expression) -> bool {
+        struct Finder<'a> {
+            address: &'a Expression,
+            is_found: bool,
+        }
+
+        impl Visitor for Finder<'_> {
+            fn visit_function_call(&mut self, call: &FunctionCall) {
+                if matches!(
+                    call.name.name.as_str(),
+                    "call" | "staticcall" | "delegatecall" | "callcode"
+                ) && call
+                    .arguments
+                    .get(1)
+                    .is_some_and(|callee| is_same(callee, self.address))
+                {
+                    self.is_found = true;
+                }
+                visit::walk_function_call(self, call);
+            }
+        }
+
+        let mut finder = Finder {
+            address,
+            is_found: false,
+        };
+        for statement in statements.iter() {
+            finder.visit_statement(statement);
+        }
+        finder.is_found
+    }
+
+    /// Checks whether the call gas argument is the stipend.
+    fn is_stipend_gas(&self, gas: &Expression) -> bool {
+        match gas {
+            Expression::Literal(literal) => is_stipend(literal),
+            Expression::Identifier(identifier) => {
+                self.stipend_variables.contains(identifier.name.as_str())
+            }
+            Expression::FunctionCall(call) => call
+                .arguments
+                .iter()
+                .any(|argument| self.is_stipend_gas(argument)),
+        }
+    }
+}
+
+impl Visitor for Linter {
+    fn visit_block(&mut self, block: &Block) {
+        for (index, statement) in block.statements.iter().enumerate() {
+            if let Some((check, address)) = Self::checked_address(statement)
+                && Self::is_called(&block.statements[index + 1..], address)
+            {
+                self.generated_checks.insert(check.range);
+            }
+        }
+        visit::walk_block(self, block);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        let (variables, value) = match statement {
+            Statement::VariableDeclaration(declaration) => (
+                declaration
+                    .variables
+                    .iter()
+                    .map(|variable| &variable.identifier)
+                    .collect::<Vec<_>>(),
+                declaration.value.as_ref(),
+            ),
+            Statement::Assignment(assignment) => (
+                assignment.variables.iter().collect(),
+                Some(&assignment.value),
+            ),
+            _ => (Vec::new(), None),
+        };
+        if let Some(Expression::Literal(literal)) = value
+            && is_stipend(literal)
+        {
+            self.stipend_variables
+                .extend(variables.into_iter().map(|variable| variable.name.clone()));
+        }
+        visit::walk_statement(self, statement);
+    }
+
+    fn visit_function_call(&mut self, call: &FunctionCall) {
+        match call.name.name.as_str() {
+            "extcodesize" if !self.generated_checks.contains(&call.range) => {
+                self.report(Lint::ExtCodeSize, call.range)
+            }
+            "msize" => self.report(Lint::MSize, call.range),
+            "selfdestruct" => self.report(Lint::SelfDestruct, call.range),
+            "gas" if !self.forwarded_gas.contains(&call.range) => {
+                self.report(Lint::Gas, call.range)
+            }
+            name @ ("call" | "callcode" | "staticcall" | "delegatecall") => {
+                match call.arguments.first() {
+                    Some(Expression::FunctionCall(gas))
+                        if gas.name.name == "gas" && gas.arguments.is_empty() =>
+                    {
+                        self.forwarded_gas.insert(gas.range);
+                    }
+                    Some(gas)
+                        if matches!(name, "call" | "callcode") && self.is_stipend_gas(gas) =>
+                    {
+                        self.report(Lint::SendTransfer, call.range)
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        visit::walk_function_call(self, call);
+    }
+}
+
+/// Returns the call if the expression calls the function `name`.
+fn call<'a>(expression: &'a Expression, name: &str) -> Option<&'a FunctionCall> {
+    match expression {
+        Expression::FunctionCall(call) if call.name.name == name => Some(call),
+        _ => None,
+    }
+}
+
+/// Checks whether the expressions are the same, regardless of their location.
+fn is_same(first: &Expression, second: &Expression) -> bool {
+    match (first, second) {
+        (Expression::FunctionCall(first), Expression::FunctionCall(second)) => {
+            first.name.name == second.name.name
+                && first.arguments.len() == second.arguments.len()
+                && first
+                    .arguments
+                    .iter()
+                    .zip(second.arguments.iter())
+                    .all(|(first, second)| is_same(first, second))
+        }
+        (Expression::Identifier(first), Expression::Identifier(second)) => {
+            first.name == second.name
+        }
+        (Expression::Literal(first), Expression::Literal(second)) => first.value == second.value,
+        _ => false,
+    }
+}
+
+/// Checks whether the literal is the gas stipend.
+fn is_stipend(literal: &Literal) -> bool {
+    let value = match literal.kind {
+        LiteralKind::Decimal => literal.value.parse::<u64>().ok(),
+        LiteralKind::Hexadecimal => literal
+            .value
+            .strip_prefix("0x")
+            .and_then(|digits| u64::from_str_radix(digits, 16).ok()),
+        _ => None,
+    };
+    value == Some(STIPEND)
+}
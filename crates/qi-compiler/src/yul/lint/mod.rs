@@ -0,0 +1,191 @@
+//! The QiVM compatibility lints over the Yul code.
+//! They flag the constructs which compile, but whose semantics on QiVM differ from the EVM ones.
+
+pub mod linter;
+
+use std::str::FromStr;
+
+use revive_solc_json_interface::ResolcWarning;
+use rowan::TextRange;
+
+use super::ast::Object;
+
+use self::linter::Linter;
+
+/// The QiVM compatibility lint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Lint {
+    /// The `extcodesize`-based contract checks.
+    ExtCodeSize,
+    /// The reliance on the 2300 gas stipend of `transfer` and `send`.
+    SendTransfer,
+    /// The `msize` usage.
+    MSize,
+    /// The logic depending on the remaining gas.
+    Gas,
+    /// The `selfdestruct` usage.
+    SelfDestruct,
+}
+
+impl Lint {
+    /// All lints.
+    pub const ALL: [Self; 5] = [
+        Self::ExtCodeSize,
+        Self::SendTransfer,
+        Self::MSize,
+        Self::Gas,
+        Self::SelfDestruct,
+    ];
+
+    /// Returns the diagnostic code, described by `qic --explain`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ExtCodeSize => "Q0101",
+            Self::SendTransfer => "Q0102",
+            Self::MSize => "Q0103",
+            Self::Gas => "Q0104",
+            Self::SelfDestruct => "Q0105",
+        }
+    }
+
+    /// Returns the diagnostic message.
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::ExtCodeSize => {
+                "The `extcodesize` check cannot tell contracts from accounts reliably on QiVM."
+            }
+            Self::SendTransfer => {
+                "The 2300 gas stipend of `transfer` and `send` does not apply on QiVM. Use `call` instead."
+            }
+            Self::MSize => "The value of `msize` on QiVM differs from the EVM one.",
+            Self::Gas => "The gas costs on QiVM differ from the EVM ones, so does the value of `gas`.",
+            Self::SelfDestruct => {
+                "`selfdestruct` only transfers the balance on QiVM, the contract is not deleted."
+            }
+        }
+    }
+
+    /// Returns the lint corresponding to the `solc` AST warning, which is suppressed by the same name.
+    pub fn from_resolc_warning(warning: &ResolcWarning) -> Option<Self> {
+        match warning {
+            ResolcWarning::ExtCodeSize => Some(Self::ExtCodeSize),
+            ResolcWarning::SendTransfer => Some(Self::SendTransfer),
+            _ => None,
+        }
+    }
+
+    /// Splits the `--suppress-warnings` names into the `solc` AST warnings and the lints.
+    /// The names shared by both, such as `extcodesize`, suppress both.
+    pub fn split_suppressed(names: &[String]) -> anyhow::Result<(Vec<ResolcWarning>, Vec<Self>)> {
+        let mut warnings = Vec::new();
+        let mut lints = Vec::new();
+        for name in names.iter() {
+            let warning = ResolcWarning::from_str(name.as_str()).ok();
+            let lint = Self::from_str(name.as_str()).ok();
+            if warning.is_none() && lint.is_none() {
+                anyhow::bail!("Invalid warning `{name}`");
+            }
+            warnings.extend(warning);
+            lints.extend(lint);
+        }
+        Ok((warnings, lints))
+    }
+}
+
+impl FromStr for Lint {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "extcodesize" => Ok(Self::ExtCodeSize),
+            "sendtransfer" => Ok(Self::SendTransfer),
+            "msize" => Ok(Self::MSize),
+            "gas" => Ok(Self::Gas),
+            "selfdestruct" => Ok(Self::SelfDestruct),
+            string => anyhow::bail!("Invalid lint `{string}`"),
+        }
+    }
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ExtCodeSize => write!(f, "extcodesize"),
+            Self::SendTransfer => write!(f, "sendtransfer"),
+            Self::MSize => write!(f, "msize"),
+            Self::Gas => write!(f, "gas"),
+            Self::SelfDestruct => write!(f, "selfdestruct"),
+        }
+    }
+}
+
+/// The lint found in the Yul code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Finding {
+    /// The lint.
+    pub lint: Lint,
+    /// The Yul code range.
+    pub range: TextRange,
+}
+
+/// Checks the object and its inner objects, skipping the `suppressed` lints.
+/// The findings are returned in the code order.
+pub fn check(object: &Object, suppressed: &[Lint]) -> Vec<Finding> {
+    let mut findings = Linter::check(object);
+    findings.retain(|finding| !suppressed.contains(&finding.lint));
+    findings.sort_by_key(|finding| finding.range.start());
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lint;
+    use crate::yul::ast::Object;
+
+    #[test]
+    fn finds_lints_but_not_generated_checks() {
+        let yul = r#"object "A_7" {
+    code {
+        function f(addr, amount) {
+            if iszero(extcodesize(addr)) { revert(0, 0) }
+            let _1 := call(gas(), addr, 0, 0, 0, 0, 0)
+            let _2 := 0
+            if iszero(amount) { _2 := 2300 }
+            pop(call(_2, addr, amount, 0, 0, 0, 0))
+            if gt(gas(), 5000) { mstore(msize(), extcodesize(caller())) }
+            selfdestruct(addr)
+        }
+    }
+}
+"#;
+        let object = Object::parse(yul).expect("Always valid");
+
+        let lints = super::check(&object, &[])
+            .into_iter()
+            .map(|finding| (finding.lint, &yul[finding.range]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lints,
+            vec![
+                (Lint::SendTransfer, "call(_2, addr, amount, 0, 0, 0, 0)"),
+                (Lint::Gas, "gas()"),
+                (Lint::MSize, "msize()"),
+                (Lint::ExtCodeSize, "extcodesize(caller())"),
+                (Lint::SelfDestruct, "selfdestruct(addr)"),
+            ]
+        );
+
+        assert_eq!(
+            super::check(&object, &[Lint::SendTransfer, Lint::Gas, Lint::MSize]).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn every_lint_is_explained() {
+        for lint in Lint::ALL {
+            assert!(crate::error::explanation::explain(lint.code()).is_some());
+            assert_eq!(lint.to_string().parse::<Lint>().ok(), Some(lint));
+        }
+    }
+}
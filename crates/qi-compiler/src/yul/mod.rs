@@ -0,0 +1,38 @@
+//! The Yul frontend.
+
+pub mod ast;
+pub mod formatter;
+pub mod ir;
+pub mod lexer;
+pub mod lint;
+pub mod parser;
+pub mod semantic;
+pub mod source_map;
+pub mod u256;
+
+pub use self::parser::syntax_kind::SyntaxKind;
+
+/// The Yul language of the `rowan` syntax trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum YulLanguage {}
+
+impl rowan::Language for YulLanguage {
+    type Kind = SyntaxKind;
+
+    fn kind_from_raw(raw: rowan::SyntaxKind) -> Self::Kind {
+        SyntaxKind::from(raw.0)
+    }
+
+    fn kind_to_raw(kind: Self::Kind) -> rowan::SyntaxKind {
+        rowan::SyntaxKind(kind.into())
+    }
+}
+
+/// The Yul syntax tree node.
+pub type SyntaxNode = rowan::SyntaxNode<YulLanguage>;
+
+/// The Yul syntax tree token.
+pub type SyntaxToken = rowan::SyntaxToken<YulLanguage>;
+
+/// The Yul syntax tree node or token.
+pub type SyntaxElement = rowan::SyntaxElement<YulLanguage>;
@@ -0,0 +1,292 @@
+//! The Yul grammar rules, as described in `doc/yul.ungram`.
+
+use super::parser::Parser;
+use super::syntax_kind::SyntaxKind;
+use super::syntax_kind::SyntaxKind::*;
+
+/// Parses the source: an object, or a bare block of code.
+pub fn source(p: &mut Parser) {
+    if p.at(OBJECT_KW) {
+        p.start_root(OBJECT);
+        object_contents(p);
+    } else {
+        p.start_root(BLOCK);
+        block_contents(p);
+    }
+    while !p.at_end() {
+        p.error_and_bump("Expected end of file");
+    }
+    p.finish_root();
+}
+
+/// `Object = 'object' '@string_literal' '{' Code ObjectProperty* '}'`
+fn object(p: &mut Parser) {
+    p.start_node(OBJECT);
+    object_contents(p);
+    p.finish_node();
+}
+
+/// The object, without the node.
+fn object_contents(p: &mut Parser) {
+    p.expect(OBJECT_KW, "`object`");
+    p.expect(STRING_LITERAL, "the object name");
+    if !p.expect(BRACKET_CURLY_LEFT, "`{`") {
+        return;
+    }
+    if p.at(CODE_KW) {
+        code(p);
+    } else {
+        p.error("Expected `code`".to_owned());
+    }
+    loop {
+        match p.current() {
+            OBJECT_KW => object(p),
+            IDENT if p.at_contextual_keyword(DATA_KW) => data(p),
+            BRACKET_CURLY_RIGHT | EOF => break,
+            _ => p.error_and_bump("Expected `object`, `data` or `}`"),
+        }
+    }
+    p.expect(BRACKET_CURLY_RIGHT, "`}`");
+}
+
+/// `Code = 'code' Block`
+fn code(p: &mut Parser) {
+    p.start_node(CODE);
+    p.bump();
+    block(p);
+    p.finish_node();
+}
+
+/// `Data = 'data' '@string_literal' ('@hex_literal' | '@string_literal')`
+fn data(p: &mut Parser) {
+    p.start_node(DATA);
+    p.bump_as(DATA_KW);
+    p.expect(STRING_LITERAL, "the data name");
+    match p.current() {
+        HEX_LITERAL | STRING_LITERAL => p.bump(),
+        _ => p.error("Expected the data value".to_owned()),
+    }
+    p.finish_node();
+}
+
+/// `Block = '{' Statement* '}'`
+fn block(p: &mut Parser) {
+    p.start_node(BLOCK);
+    block_contents(p);
+    p.finish_node();
+}
+
+/// The block, without the node.
+fn block_contents(p: &mut Parser) {
+    if !p.expect(BRACKET_CURLY_LEFT, "`{`") {
+        return;
+    }
+    while !p.at(BRACKET_CURLY_RIGHT) && !p.at_end() {
+        statement(p);
+    }
+    p.expect(BRACKET_CURLY_RIGHT, "`}`");
+}
+
+/// `Statement = Block | FunctionDefinition | VariableDeclaration | Assignment | If | Expression | Switch | ForLoop | BreakContinue | Leave`
+fn statement(p: &mut Parser) {
+    match p.current() {
+        BRACKET_CURLY_LEFT => block(p),
+        FUNCTION_KW => function_definition(p),
+        LET_KW => variable_declaration(p),
+        IF_KW => if_statement(p),
+        SWITCH_KW => switch(p),
+        FOR_KW => for_loop(p),
+        BREAK_KW | CONTINUE_KW => {
+            p.start_node(BREAK_CONTINUE);
+            p.bump();
+            p.finish_node();
+        }
+        LEAVE_KW => {
+            p.start_node(LEAVE);
+            p.bump();
+            p.finish_node();
+        }
+        IDENT if matches!(p.nth(1), COMMA | WALRUS) => assignment(p),
+        IDENT => expression(p),
+        kind if is_literal_start(kind) => expression(p),
+        _ => p.error_and_bump("Expected a statement"),
+    }
+}
+
+/// `FunctionDefinition = 'function' '#ident' '(' TypedIdentifierList? ')' ('->' TypedIdentifierList)? Block`
+fn function_definition(p: &mut Parser) {
+    p.start_node(FUNCTION_DEFINITION);
+    p.bump();
+    p.expect(IDENT, "the function name");
+    p.expect(PARENTHESIS_LEFT, "`(`");
+    if p.at(IDENT) {
+        typed_identifier_list(p);
+    }
+    p.expect(PARENTHESIS_RIGHT, "`)`");
+    if p.at(ARROW) {
+        p.bump();
+        typed_identifier_list(p);
+    }
+    block(p);
+    p.finish_node();
+}
+
+/// `VariableDeclaration = 'let' TypedIdentifierList (':=' Expression)?`
+fn variable_declaration(p: &mut Parser) {
+    p.start_node(VARIABLE_DECLARATION);
+    p.bump();
+    typed_identifier_list(p);
+    if p.at(WALRUS) {
+        p.bump();
+        expression(p);
+    }
+    p.finish_node();
+}
+
+/// `Assignment = IdentifierList ':=' Expression`
+fn assignment(p: &mut Parser) {
+    p.start_node(ASSIGNMENT);
+    identifier_list(p);
+    p.expect(WALRUS, "`:=`");
+    expression(p);
+    p.finish_node();
+}
+
+/// `If = 'if' Expression Block`
+fn if_statement(p: &mut Parser) {
+    p.start_node(IF);
+    p.bump();
+    expression(p);
+    block(p);
+    p.finish_node();
+}
+
+/// `Switch = 'switch' Expression (Case Case* Default? | Default)`
+fn switch(p: &mut Parser) {
+    p.start_node(SWITCH);
+    p.bump();
+    expression(p);
+    if !p.at(CASE_KW) && !p.at(DEFAULT_KW) {
+        p.error("Expected `case` or `default`".to_owned());
+    }
+    while p.at(CASE_KW) {
+        p.start_node(CASE);
+        p.bump();
+        if is_literal_start(p.current()) {
+            literal(p);
+        } else {
+            p.error("Expected a literal".to_owned());
+        }
+        block(p);
+        p.finish_node();
+    }
+    if p.at(DEFAULT_KW) {
+        p.start_node(DEFAULT);
+        p.bump();
+        block(p);
+        p.finish_node();
+    }
+    p.finish_node();
+}
+
+/// `ForLoop = 'for' Block Expression Block Block`
+fn for_loop(p: &mut Parser) {
+    p.start_node(FOR_LOOP);
+    p.bump();
+    block(p);
+    expression(p);
+    block(p);
+    block(p);
+    p.finish_node();
+}
+
+/// `Expression = FunctionCall | '#ident' | Literal`
+fn expression(p: &mut Parser) {
+    p.start_node(EXPRESSION);
+    match p.current() {
+        IDENT if p.nth(1) == PARENTHESIS_LEFT => function_call(p),
+        IDENT => p.bump(),
+        kind if is_literal_start(kind) => literal(p),
+        BRACKET_CURLY_LEFT | BRACKET_CURLY_RIGHT | EOF => {
+            p.error("Expected an expression".to_owned())
+        }
+        _ => p.error_and_bump("Expected an expression"),
+    }
+    p.finish_node();
+}
+
+/// `FunctionCall = '#ident' '(' (Expression (',' Expression)*)? ')'`
+fn function_call(p: &mut Parser) {
+    p.start_node(FUNCTION_CALL);
+    p.bump();
+    p.bump();
+    if !p.at(PARENTHESIS_RIGHT) {
+        expression(p);
+        while p.at(COMMA) {
+            p.bump();
+            expression(p);
+        }
+    }
+    p.expect(PARENTHESIS_RIGHT, "`,` or `)`");
+    p.finish_node();
+}
+
+/// `IdentifierList = '#ident' (',' '#ident')*`
+fn identifier_list(p: &mut Parser) {
+    p.start_node(IDENTIFIER_LIST);
+    p.expect(IDENT, "an identifier");
+    while p.at(COMMA) {
+        p.bump();
+        p.expect(IDENT, "an identifier");
+    }
+    p.finish_node();
+}
+
+/// `TypedIdentifierList = TypedIdentifier (',' TypedIdentifier)*`
+fn typed_identifier_list(p: &mut Parser) {
+    p.start_node(TYPED_IDENTIFIER_LIST);
+    typed_identifier(p);
+    while p.at(COMMA) {
+        p.bump();
+        typed_identifier(p);
+    }
+    p.finish_node();
+}
+
+/// `TypedIdentifier = '#ident' (':' TypeName)?`
+fn typed_identifier(p: &mut Parser) {
+    p.start_node(TYPED_IDENTIFIER);
+    p.expect(IDENT, "an identifier");
+    if p.at(COLON) {
+        p.bump();
+        type_name(p);
+    }
+    p.finish_node();
+}
+
+/// `Literal = ('@hex_number' | '@decimal_number' | '@string_literal' | 'true' | 'false') (':' TypeName)?`
+/// The `hex"..."` literals are accepted as well, as `solc` does.
+fn literal(p: &mut Parser) {
+    p.start_node(LITERAL);
+    p.bump();
+    if p.at(COLON) {
+        p.bump();
+        type_name(p);
+    }
+    p.finish_node();
+}
+
+/// `TypeName = '#ident'`
+fn type_name(p: &mut Parser) {
+    p.start_node(TYPE_NAME);
+    p.expect(IDENT, "a type name");
+    p.finish_node();
+}
+
+/// Checks whether the token starts a literal.
+fn is_literal_start(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        DECIMAL_NUMBER | HEX_NUMBER | STRING_LITERAL | HEX_LITERAL | TRUE_KW | FALSE_KW
+    )
+}
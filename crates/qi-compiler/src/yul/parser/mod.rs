@@ -1,9 +1,129 @@
-// pub mod parse;
-// pub mod parser;
+//! The lossless Yul parser, building the `rowan` syntax tree.
+
+pub mod grammar;
+pub mod parse;
+#[allow(clippy::module_inception)]
+pub mod parser;
 pub mod syntax_kind;
 
-// use super::SyntaxNode;
-// use super::{Lexer, NodeKind, SyntaxKind, TokenKind, YulLanguage};
-// pub use parse::Parse;
-// pub use parser::Parser;
-pub fn parse() {}
+pub use self::parse::Parse;
+pub use self::parse::ParseError;
+pub use self::parser::Parser;
+
+/// Parses the Yul object or block.
+pub fn parse(text: &str) -> Parse {
+    let mut parser = Parser::new(text);
+    grammar::source(&mut parser);
+    parser.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use rowan::TextRange;
+    use rowan::TextSize;
+
+    use crate::yul::SyntaxKind::*;
+
+    #[test]
+    fn is_lossless_and_recovers() {
+        let text = r#"/// @use-src 0:"A.sol"
+object "A_7" {
+    code {
+        function f(a, b:u256) -> c { c := add(a, b) }
+        let x, y := f(1, 0x02)
+        switch x case 0 { leave } default { for {} lt(x, 10) {} { break } }
+        if ) { }
+    }
+    data ".metadata" hex"ff"
+}
+"#;
+        let parse = super::parse(text);
+
+        assert_eq!(parse.syntax().to_string(), text);
+        assert_eq!(parse.errors().len(), 1);
+        assert_eq!(
+            parse.errors()[0].message,
+            "Expected an expression, found `)`."
+        );
+    }
+
+    #[test]
+    fn builds_the_grammar_nodes() {
+        let parse = super::parse("{ for { let i := 0 } lt(i, 2) { i := add(i, 1) } { continue } }");
+
+        assert!(parse.errors().is_empty());
+        let kinds = parse
+            .syntax()
+            .descendants()
+            .map(|node| node.kind())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                BLOCK,
+                FOR_LOOP,
+                BLOCK,
+                VARIABLE_DECLARATION,
+                TYPED_IDENTIFIER_LIST,
+                TYPED_IDENTIFIER,
+                EXPRESSION,
+                LITERAL,
+                EXPRESSION,
+                FUNCTION_CALL,
+                EXPRESSION,
+                EXPRESSION,
+                LITERAL,
+                BLOCK,
+                ASSIGNMENT,
+                IDENTIFIER_LIST,
+                EXPRESSION,
+                FUNCTION_CALL,
+                EXPRESSION,
+                EXPRESSION,
+                LITERAL,
+                BLOCK,
+                BREAK_CONTINUE,
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_the_end_of_file() {
+        let text = "{ let x := ";
+        let parse = super::parse(text);
+
+        assert_eq!(parse.syntax().to_string(), text);
+        let messages = parse
+            .errors()
+            .iter()
+            .map(|error| (error.message.as_str(), error.range))
+            .collect::<Vec<_>>();
+        let end = TextRange::empty(TextSize::of(text));
+        assert_eq!(
+            messages,
+            [
+                ("Expected an expression, found end of file.", end),
+                ("Expected `}`, found end of file.", end),
+            ]
+        );
+    }
+
+    #[test]
+    fn wraps_the_unexpected_tokens() {
+        let text = "{ pop(1) ) }";
+        let parse = super::parse(text);
+
+        assert_eq!(parse.syntax().to_string(), text);
+        assert_eq!(parse.errors().len(), 1);
+        assert_eq!(
+            parse.errors()[0].range,
+            TextRange::new(TextSize::new(9), TextSize::new(10))
+        );
+        let error = parse
+            .syntax()
+            .descendants()
+            .find(|node| node.kind() == ERROR)
+            .expect("Always valid");
+        assert_eq!(error.text().to_string(), ")");
+    }
+}
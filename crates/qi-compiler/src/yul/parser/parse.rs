@@ -0,0 +1,48 @@
+//! The parsing result.
+
+use rowan::GreenNode;
+use rowan::TextRange;
+
+use crate::yul::SyntaxNode;
+
+/// The syntax error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The error message.
+    pub message: String,
+    /// The range of the offending token, or the empty range at the end of the code.
+    pub range: TextRange,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The lossless syntax tree with the syntax errors.
+/// The tree is built even if there are errors, with the unexpected tokens wrapped into `ERROR` nodes.
+#[derive(Debug, Clone)]
+pub struct Parse {
+    /// The syntax tree.
+    green: GreenNode,
+    /// The syntax errors.
+    errors: Vec<ParseError>,
+}
+
+impl Parse {
+    /// A shortcut constructor.
+    pub fn new(green: GreenNode, errors: Vec<ParseError>) -> Self {
+        Self { green, errors }
+    }
+
+    /// Returns the root node.
+    pub fn syntax(&self) -> SyntaxNode {
+        SyntaxNode::new_root(self.green.clone())
+    }
+
+    /// Returns the syntax errors.
+    pub fn errors(&self) -> &[ParseError] {
+        self.errors.as_slice()
+    }
+}
@@ -0,0 +1,200 @@
+//! The Yul parser state.
+
+use rowan::Checkpoint;
+use rowan::GreenNodeBuilder;
+use rowan::TextRange;
+use rowan::TextSize;
+
+use crate::yul::lexer::Lexer;
+
+use super::parse::Parse;
+use super::parse::ParseError;
+use super::syntax_kind::SyntaxKind;
+
+/// The token with its kind in the syntax tree.
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    /// The syntax kind.
+    kind: SyntaxKind,
+    /// The token text.
+    text: &'a str,
+    /// The token range.
+    range: TextRange,
+}
+
+/// The Yul parser state.
+/// The trivia are attached to the innermost node open when the next significant token is bumped,
+/// so the nodes start and end with significant tokens, except for the root one.
+pub struct Parser<'a> {
+    /// The tokens, including the trivia.
+    tokens: Vec<Token<'a>>,
+    /// The index of the next token to add to the tree.
+    position: usize,
+    /// The end of the code.
+    end: TextSize,
+    /// The syntax tree builder.
+    builder: GreenNodeBuilder<'static>,
+    /// The syntax errors.
+    errors: Vec<ParseError>,
+}
+
+impl<'a> Parser<'a> {
+    /// A shortcut constructor.
+    pub fn new(text: &'a str) -> Self {
+        let tokens = Lexer::new(text)
+            .map(|token| Token {
+                kind: token
+                    .kind
+                    .map(SyntaxKind::from)
+                    .unwrap_or(SyntaxKind::ERROR),
+                text: token.text,
+                range: token.range,
+            })
+            .collect();
+        Self {
+            tokens,
+            position: 0,
+            end: TextSize::of(text),
+            builder: GreenNodeBuilder::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Returns the kind of the `n`-th significant token ahead, or `EOF`.
+    pub fn nth(&self, n: usize) -> SyntaxKind {
+        self.significant(n)
+            .map(|token| token.kind)
+            .unwrap_or(SyntaxKind::EOF)
+    }
+
+    /// Returns the kind of the next significant token, or `EOF`.
+    pub fn current(&self) -> SyntaxKind {
+        self.nth(0)
+    }
+
+    /// Checks whether the next significant token is of the kind.
+    pub fn at(&self, kind: SyntaxKind) -> bool {
+        self.current() == kind
+    }
+
+    /// Checks whether the next significant token is the identifier used as a contextual keyword.
+    pub fn at_contextual_keyword(&self, keyword: SyntaxKind) -> bool {
+        self.significant(0).is_some_and(|token| {
+            token.kind == SyntaxKind::IDENT
+                && SyntaxKind::from_contextual_keyword(token.text) == Some(keyword)
+        })
+    }
+
+    /// Checks whether all significant tokens have been consumed.
+    pub fn at_end(&self) -> bool {
+        self.at(SyntaxKind::EOF)
+    }
+
+    /// Starts the root node, which also holds the leading trivia.
+    pub fn start_root(&mut self, kind: SyntaxKind) {
+        self.builder.start_node(rowan::SyntaxKind(kind.into()));
+    }
+
+    /// Finishes the root node with the trailing trivia.
+    pub fn finish_root(&mut self) {
+        self.bump_trivia();
+        self.builder.finish_node();
+    }
+
+    /// Starts the node at the next significant token.
+    pub fn start_node(&mut self, kind: SyntaxKind) {
+        self.bump_trivia();
+        self.builder.start_node(rowan::SyntaxKind(kind.into()));
+    }
+
+    /// Finishes the innermost node.
+    pub fn finish_node(&mut self) {
+        self.builder.finish_node();
+    }
+
+    /// Returns the checkpoint at the next significant token, to wrap the following tokens into a node later.
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        self.bump_trivia();
+        self.builder.checkpoint()
+    }
+
+    /// Starts the node at the checkpoint.
+    pub fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        self.builder
+            .start_node_at(checkpoint, rowan::SyntaxKind(kind.into()));
+    }
+
+    /// Adds the next significant token to the tree.
+    pub fn bump(&mut self) {
+        let kind = self.current();
+        self.bump_as(kind);
+    }
+
+    /// Adds the next significant token to the tree as the kind, e.g. an identifier as a contextual keyword.
+    pub fn bump_as(&mut self, kind: SyntaxKind) {
+        self.bump_trivia();
+        if let Some(token) = self.tokens.get(self.position) {
+            self.builder
+                .token(rowan::SyntaxKind(kind.into()), token.text);
+            self.position += 1;
+        }
+    }
+
+    /// Bumps the next significant token if it is of the kind, or reports the error otherwise.
+    pub fn expect(&mut self, kind: SyntaxKind, description: &str) -> bool {
+        if self.at(kind) {
+            self.bump();
+            true
+        } else {
+            self.error(format!("Expected {description}"));
+            false
+        }
+    }
+
+    /// Reports the error at the next significant token.
+    pub fn error(&mut self, message: String) {
+        let (found, range) = match self.significant(0) {
+            Some(token) => (format!("`{}`", token.text), token.range),
+            None => ("end of file".to_owned(), TextRange::empty(self.end)),
+        };
+        self.errors.push(ParseError {
+            message: format!("{message}, found {found}."),
+            range,
+        });
+    }
+
+    /// Reports the error at the next significant token and wraps it into an `ERROR` node.
+    pub fn error_and_bump(&mut self, message: &str) {
+        self.error(message.to_owned());
+        if !self.at_end() {
+            self.start_node(SyntaxKind::ERROR);
+            self.bump();
+            self.finish_node();
+        }
+    }
+
+    /// Builds the syntax tree.
+    pub fn finish(self) -> Parse {
+        Parse::new(self.builder.finish(), self.errors)
+    }
+
+    /// Returns the `n`-th significant token ahead.
+    fn significant(&self, n: usize) -> Option<&Token<'a>> {
+        self.tokens[self.position..]
+            .iter()
+            .filter(|token| !token.kind.is_trivia())
+            .nth(n)
+    }
+
+    /// Adds the trivia preceding the next significant token to the tree.
+    fn bump_trivia(&mut self) {
+        while let Some(token) = self.tokens.get(self.position) {
+            if !token.kind.is_trivia() {
+                break;
+            }
+            self.builder
+                .token(rowan::SyntaxKind(token.kind.into()), token.text);
+            self.position += 1;
+        }
+    }
+}
@@ -3,3 +3,65 @@ mod generated;
 
 #[allow(unreachable_pub)]
 pub use self::generated::SyntaxKind;
+
+use crate::yul::lexer::TokenKind;
+
+impl SyntaxKind {
+    /// Checks whether the kind is a whitespace or a comment.
+    pub fn is_trivia(self) -> bool {
+        matches!(self, SyntaxKind::WHITESPACE | SyntaxKind::COMMENT)
+    }
+}
+
+impl From<u16> for SyntaxKind {
+    fn from(raw: u16) -> Self {
+        assert!(raw < SyntaxKind::__LAST as u16, "invalid syntax kind {raw}");
+        // SAFETY: the kind is `repr(u16)` and the value is checked to be in range.
+        unsafe { std::mem::transmute::<u16, SyntaxKind>(raw) }
+    }
+}
+
+impl From<SyntaxKind> for u16 {
+    fn from(kind: SyntaxKind) -> Self {
+        kind as u16
+    }
+}
+
+impl From<TokenKind> for SyntaxKind {
+    /// The type keywords are lexed separately, but they are identifiers in the grammar.
+    /// The `data` contextual keyword is lexed as an identifier and remapped by the parser.
+    fn from(kind: TokenKind) -> Self {
+        match kind {
+            TokenKind::Object => SyntaxKind::OBJECT_KW,
+            TokenKind::Code => SyntaxKind::CODE_KW,
+            TokenKind::Function => SyntaxKind::FUNCTION_KW,
+            TokenKind::Let => SyntaxKind::LET_KW,
+            TokenKind::If => SyntaxKind::IF_KW,
+            TokenKind::Switch => SyntaxKind::SWITCH_KW,
+            TokenKind::Case => SyntaxKind::CASE_KW,
+            TokenKind::Default => SyntaxKind::DEFAULT_KW,
+            TokenKind::For => SyntaxKind::FOR_KW,
+            TokenKind::Break => SyntaxKind::BREAK_KW,
+            TokenKind::Continue => SyntaxKind::CONTINUE_KW,
+            TokenKind::Leave => SyntaxKind::LEAVE_KW,
+            TokenKind::Bool | TokenKind::Int | TokenKind::Uint => SyntaxKind::IDENT,
+            TokenKind::Walrus => SyntaxKind::WALRUS,
+            TokenKind::Arrow => SyntaxKind::ARROW,
+            TokenKind::BracketCurlyLeft => SyntaxKind::BRACKET_CURLY_LEFT,
+            TokenKind::BracketCurlyRight => SyntaxKind::BRACKET_CURLY_RIGHT,
+            TokenKind::ParenthesisLeft => SyntaxKind::PARENTHESIS_LEFT,
+            TokenKind::ParenthesisRight => SyntaxKind::PARENTHESIS_RIGHT,
+            TokenKind::Comma => SyntaxKind::COMMA,
+            TokenKind::Colon => SyntaxKind::COLON,
+            TokenKind::True => SyntaxKind::TRUE_KW,
+            TokenKind::False => SyntaxKind::FALSE_KW,
+            TokenKind::Decimal => SyntaxKind::DECIMAL_NUMBER,
+            TokenKind::Hexadecimal => SyntaxKind::HEX_NUMBER,
+            TokenKind::String => SyntaxKind::STRING_LITERAL,
+            TokenKind::StringHex => SyntaxKind::HEX_LITERAL,
+            TokenKind::Identifier => SyntaxKind::IDENT,
+            TokenKind::Comment => SyntaxKind::COMMENT,
+            TokenKind::Whitespace => SyntaxKind::WHITESPACE,
+        }
+    }
+}
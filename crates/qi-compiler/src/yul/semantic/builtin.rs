@@ -0,0 +1,139 @@
+//! The EVM dialect builtin functions.
+
+/// The builtin function signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Builtin {
+    /// The name.
+    pub name: &'static str,
+    /// The number of arguments.
+    pub parameters: usize,
+    /// The number of returned values.
+    pub returns: usize,
+}
+
+impl Builtin {
+    /// A shortcut constructor.
+    const fn new(name: &'static str, parameters: usize, returns: usize) -> Self {
+        Self {
+            name,
+            parameters,
+            returns,
+        }
+    }
+
+    /// Returns the builtin by its name.
+    pub fn get(name: &str) -> Option<&'static Self> {
+        BUILTINS.iter().find(|builtin| builtin.name == name)
+    }
+
+    /// Whether the builtin ends the execution, so that the code after it is unreachable.
+    pub fn is_terminating(&self) -> bool {
+        matches!(
+            self.name,
+            "stop" | "return" | "revert" | "selfdestruct" | "invalid"
+        )
+    }
+
+    /// Returns the index of the parameter which is the literal object, immutable or library name,
+    /// rather than a value. `datacopy` takes the offsets only, which `dataoffset` and `datasize` give.
+    pub fn name_parameter(&self) -> Option<usize> {
+        match self.name {
+            "datasize" | "dataoffset" | "loadimmutable" | "linkersymbol" => Some(0),
+            "setimmutable" => Some(1),
+            _ => None,
+        }
+    }
+}
+
+/// The EVM dialect builtins, as of the Cancun upgrade, and the object access builtins.
+pub static BUILTINS: &[Builtin] = &[
+    Builtin::new("stop", 0, 0),
+    Builtin::new("add", 2, 1),
+    Builtin::new("sub", 2, 1),
+    Builtin::new("mul", 2, 1),
+    Builtin::new("div", 2, 1),
+    Builtin::new("sdiv", 2, 1),
+    Builtin::new("mod", 2, 1),
+    Builtin::new("smod", 2, 1),
+    Builtin::new("exp", 2, 1),
+    Builtin::new("not", 1, 1),
+    Builtin::new("lt", 2, 1),
+    Builtin::new("gt", 2, 1),
+    Builtin::new("slt", 2, 1),
+    Builtin::new("sgt", 2, 1),
+    Builtin::new("eq", 2, 1),
+    Builtin::new("iszero", 1, 1),
+    Builtin::new("and", 2, 1),
+    Builtin::new("or", 2, 1),
+    Builtin::new("xor", 2, 1),
+    Builtin::new("byte", 2, 1),
+    Builtin::new("shl", 2, 1),
+    Builtin::new("shr", 2, 1),
+    Builtin::new("sar", 2, 1),
+    Builtin::new("addmod", 3, 1),
+    Builtin::new("mulmod", 3, 1),
+    Builtin::new("signextend", 2, 1),
+    Builtin::new("keccak256", 2, 1),
+    Builtin::new("pc", 0, 1),
+    Builtin::new("pop", 1, 0),
+    Builtin::new("mload", 1, 1),
+    Builtin::new("mstore", 2, 0),
+    Builtin::new("mstore8", 2, 0),
+    Builtin::new("mcopy", 3, 0),
+    Builtin::new("sload", 1, 1),
+    Builtin::new("sstore", 2, 0),
+    Builtin::new("tload", 1, 1),
+    Builtin::new("tstore", 2, 0),
+    Builtin::new("msize", 0, 1),
+    Builtin::new("gas", 0, 1),
+    Builtin::new("address", 0, 1),
+    Builtin::new("balance", 1, 1),
+    Builtin::new("selfbalance", 0, 1),
+    Builtin::new("caller", 0, 1),
+    Builtin::new("callvalue", 0, 1),
+    Builtin::new("calldataload", 1, 1),
+    Builtin::new("calldatasize", 0, 1),
+    Builtin::new("calldatacopy", 3, 0),
+    Builtin::new("codesize", 0, 1),
+    Builtin::new("codecopy", 3, 0),
+    Builtin::new("extcodesize", 1, 1),
+    Builtin::new("extcodecopy", 4, 0),
+    Builtin::new("returndatasize", 0, 1),
+    Builtin::new("returndatacopy", 3, 0),
+    Builtin::new("extcodehash", 1, 1),
+    Builtin::new("create", 3, 1),
+    Builtin::new("create2", 4, 1),
+    Builtin::new("call", 7, 1),
+    Builtin::new("callcode", 7, 1),
+    Builtin::new("delegatecall", 6, 1),
+    Builtin::new("staticcall", 6, 1),
+    Builtin::new("return", 2, 0),
+    Builtin::new("revert", 2, 0),
+    Builtin::new("selfdestruct", 1, 0),
+    Builtin::new("invalid", 0, 0),
+    Builtin::new("log0", 2, 0),
+    Builtin::new("log1", 3, 0),
+    Builtin::new("log2", 4, 0),
+    Builtin::new("log3", 5, 0),
+    Builtin::new("log4", 6, 0),
+    Builtin::new("chainid", 0, 1),
+    Builtin::new("basefee", 0, 1),
+    Builtin::new("blobbasefee", 0, 1),
+    Builtin::new("origin", 0, 1),
+    Builtin::new("gasprice", 0, 1),
+    Builtin::new("blockhash", 1, 1),
+    Builtin::new("blobhash", 1, 1),
+    Builtin::new("coinbase", 0, 1),
+    Builtin::new("timestamp", 0, 1),
+    Builtin::new("number", 0, 1),
+    Builtin::new("difficulty", 0, 1),
+    Builtin::new("prevrandao", 0, 1),
+    Builtin::new("gaslimit", 0, 1),
+    Builtin::new("datasize", 1, 1),
+    Builtin::new("dataoffset", 1, 1),
+    Builtin::new("datacopy", 3, 0),
+    Builtin::new("setimmutable", 3, 0),
+    Builtin::new("loadimmutable", 1, 1),
+    Builtin::new("linkersymbol", 1, 1),
+    Builtin::new("memoryguard", 1, 1),
+];
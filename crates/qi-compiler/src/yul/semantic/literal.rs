@@ -0,0 +1,320 @@
+//! The Yul literal evaluation.
+//! The errors point at the offending part of the literal, such as an escape sequence,
+//! the excess digits of a number or the bytes of a string beyond the 32-byte limit.
+
+use rowan::TextRange;
+use rowan::TextSize;
+
+use crate::yul::ast::Literal;
+use crate::yul::ast::LiteralKind;
+use crate::yul::u256::U256;
+
+use super::SemanticError;
+
+/// The maximum length of the string literals, in bytes.
+pub const MAX_STRING_LENGTH: usize = 32;
+
+/// The number of hexadecimal digits of the maximum value, whose excess is reported.
+const U256_HEX_DIGITS: usize = 64;
+
+/// The literal value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// The number or boolean value.
+    Number(U256),
+    /// The string or hex string bytes.
+    Bytes(Vec<u8>),
+}
+
+impl Value {
+    /// Returns the value as a stack word, where the bytes are left-aligned like in the EVM memory.
+    pub fn to_u256(&self) -> U256 {
+        match self {
+            Self::Number(value) => *value,
+            Self::Bytes(bytes) => {
+                let mut word = [0; 32];
+                word[..bytes.len()].copy_from_slice(bytes.as_slice());
+                U256::from_be_bytes(word)
+            }
+        }
+    }
+}
+
+/// Evaluates the literal.
+pub fn evaluate(literal: &Literal) -> Result<Value, SemanticError> {
+    let evaluator = Evaluator {
+        text: literal.value.as_str(),
+        start: literal.range.start(),
+    };
+    match literal.kind {
+        LiteralKind::Decimal => evaluator.number(0, 10).map(Value::Number),
+        LiteralKind::Hexadecimal => evaluator.number(2, 16).map(Value::Number),
+        LiteralKind::String => evaluator.string(MAX_STRING_LENGTH).map(Value::Bytes),
+        LiteralKind::HexString => evaluator.hex_string().map(Value::Bytes),
+        LiteralKind::Boolean => Ok(Value::Number(U256::from(literal.value == "true"))),
+    }
+}
+
+/// Evaluates the string literal naming an object, an immutable or a library, whose length is not limited.
+pub fn name(literal: &Literal) -> Result<String, SemanticError> {
+    let evaluator = Evaluator {
+        text: literal.value.as_str(),
+        start: literal.range.start(),
+    };
+    if literal.kind != LiteralKind::String {
+        return Err(evaluator.error(
+            "Expected a string literal name.".to_owned(),
+            0,
+            literal.value.len(),
+        ));
+    }
+    let bytes = evaluator.string(usize::MAX)?;
+    String::from_utf8(bytes).map_err(|_| {
+        evaluator.error(
+            "Names must be valid UTF-8.".to_owned(),
+            0,
+            literal.value.len(),
+        )
+    })
+}
+
+/// The literal evaluator.
+struct Evaluator<'a> {
+    /// The literal text, as it is written.
+    text: &'a str,
+    /// The literal offset in the code.
+    start: TextSize,
+}
+
+impl Evaluator<'_> {
+    /// Evaluates the number, whose digits start at `prefix`.
+    fn number(&self, prefix: usize, radix: u32) -> Result<U256, SemanticError> {
+        let digits = &self.text[prefix..];
+        let significant = digits.trim_start_matches('0');
+        let start = prefix + digits.len() - significant.len();
+
+        let mut value = U256::ZERO;
+        for digit in significant.chars() {
+            let digit = digit.to_digit(radix).expect("Always valid") as u64;
+            value = match value.checked_mul_add(radix as u64, digit) {
+                Some(value) => value,
+                None => {
+                    let end = match radix {
+                        16 => start + significant.len() - U256_HEX_DIGITS,
+                        _ => start + significant.len(),
+                    };
+                    return Err(self.error(
+                        "Number literal exceeds the maximum value 2^256-1.".to_owned(),
+                        start,
+                        end,
+                    ));
+                }
+            };
+        }
+        Ok(value)
+    }
+
+    /// Evaluates the string, decoding the escape sequences, which must be at most `max_length` bytes long.
+    fn string(&self, max_length: usize) -> Result<Vec<u8>, SemanticError> {
+        let end = self.text.len() - 1;
+        let mut bytes = Vec::new();
+        let mut overflow_start = None;
+        let mut chars = self.text[1..end]
+            .char_indices()
+            .map(|(index, char)| (index + 1, char))
+            .peekable();
+        while let Some((index, char)) = chars.next() {
+            let length = bytes.len();
+            if char != '\\' {
+                bytes.extend_from_slice(char.encode_utf8(&mut [0; 4]).as_bytes());
+            } else {
+                let Some((_, escape)) = chars.next() else {
+                    return Err(self.error("Unterminated escape sequence.".to_owned(), index, end));
+                };
+                match escape {
+                    'n' => bytes.push(b'\n'),
+                    'r' => bytes.push(b'\r'),
+                    't' => bytes.push(b'\t'),
+                    '\\' | '"' | '\'' => bytes.push(escape as u8),
+                    'x' | 'u' => {
+                        let expected = if escape == 'x' { 2 } else { 4 };
+                        let mut digits = String::new();
+                        while digits.len() < expected
+                            && let Some((_, digit)) =
+                                chars.next_if(|(_, char)| char.is_ascii_hexdigit())
+                        {
+                            digits.push(digit);
+                        }
+                        let escape_end = index + 2 + digits.len();
+                        if digits.len() < expected {
+                            return Err(self.error(
+                                format!(
+                                    "Invalid escape sequence `{}`, expected {expected} hexadecimal digits.",
+                                    &self.text[index..escape_end]
+                                ),
+                                index,
+                                escape_end,
+                            ));
+                        }
+                        let code = u32::from_str_radix(digits.as_str(), 16).expect("Always valid");
+                        if escape == 'x' {
+                            bytes.push(code as u8);
+                        } else {
+                            let Some(char) = char::from_u32(code) else {
+                                return Err(self.error(
+                                    format!(
+                                        "Invalid escape sequence `{}`, surrogates are not valid code points.",
+                                        &self.text[index..escape_end]
+                                    ),
+                                    index,
+                                    escape_end,
+                                ));
+                            };
+                            bytes.extend_from_slice(char.encode_utf8(&mut [0; 4]).as_bytes());
+                        }
+                    }
+                    escape => {
+                        return Err(self.error(
+                            format!("Invalid escape sequence `\\{escape}`."),
+                            index,
+                            index + 1 + escape.len_utf8(),
+                        ));
+                    }
+                }
+            }
+            if length <= max_length && bytes.len() > max_length {
+                overflow_start = Some(index);
+            }
+        }
+
+        match overflow_start {
+            Some(start) => Err(self.error(
+                format!(
+                    "String literal is {} bytes long, but at most {max_length} bytes are allowed.",
+                    bytes.len()
+                ),
+                start,
+                end,
+            )),
+            None => Ok(bytes),
+        }
+    }
+
+    /// Evaluates the hex string, such as `hex"c0fe"`.
+    fn hex_string(&self) -> Result<Vec<u8>, SemanticError> {
+        let start = "hex\"".len();
+        let end = self.text.len() - 1;
+        let bytes = self.text.as_bytes()[start..end]
+            .chunks_exact(2)
+            .map(|pair| {
+                u8::from_str_radix(std::str::from_utf8(pair).expect("Always valid"), 16)
+                    .expect("Always valid")
+            })
+            .collect::<Vec<_>>();
+        if bytes.len() > MAX_STRING_LENGTH {
+            return Err(self.error(
+                format!(
+                    "Hex string literal is {} bytes long, but at most {MAX_STRING_LENGTH} bytes are allowed.",
+                    bytes.len()
+                ),
+                start + MAX_STRING_LENGTH * 2,
+                end,
+            ));
+        }
+        Ok(bytes)
+    }
+
+    /// Creates the error for the part of the literal between the offsets.
+    fn error(&self, message: String, start: usize, end: usize) -> SemanticError {
+        SemanticError {
+            message,
+            range: TextRange::new(
+                self.start + TextSize::from(start as u32),
+                self.start + TextSize::from(end as u32),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::ast::Block;
+    use crate::yul::ast::Expression;
+    use crate::yul::ast::Statement;
+    use crate::yul::u256::U256;
+
+    use super::Value;
+
+    #[test]
+    fn evaluates_literals_and_reports_exact_ranges() {
+        let yul = r#"{
+    pop(115792089237316195423570985008687907853269984665640564039457584007913129639935)
+    pop(0x00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff)
+    pop("a\n\x41\u00e9\"")
+    pop(hex"c0fe")
+    pop(true)
+    pop(115792089237316195423570985008687907853269984665640564039457584007913129639936)
+    pop(0x1230000000000000000000000000000000000000000000000000000000000000000)
+    pop("\q")
+    pop("\x4")
+    pop("0123456789abcdef0123456789abcdefé!")
+    pop(hex"000000000000000000000000000000000000000000000000000000000000000000c0fe")
+}
+"#;
+        let block = Block::parse(yul).expect("Always valid");
+        let results = block
+            .statements
+            .iter()
+            .map(|statement| {
+                let Statement::Expression(Expression::FunctionCall(call)) = statement else {
+                    panic!("Always a call");
+                };
+                let Expression::Literal(literal) = &call.arguments[0] else {
+                    panic!("Always a literal");
+                };
+                super::evaluate(literal).map_err(|error| (error.message, &yul[error.range]))
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            vec![
+                Ok(Value::Number(U256::MAX)),
+                Ok(Value::Number(U256::MAX)),
+                Ok(Value::Bytes(b"a\nA\xc3\xa9\"".to_vec())),
+                Ok(Value::Bytes(vec![0xc0, 0xfe])),
+                Ok(Value::Number(U256::ONE)),
+                Err((
+                    "Number literal exceeds the maximum value 2^256-1.".to_owned(),
+                    "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+                )),
+                Err((
+                    "Number literal exceeds the maximum value 2^256-1.".to_owned(),
+                    "123"
+                )),
+                Err(("Invalid escape sequence `\\q`.".to_owned(), "\\q")),
+                Err((
+                    "Invalid escape sequence `\\x4`, expected 2 hexadecimal digits.".to_owned(),
+                    "\\x4"
+                )),
+                Err((
+                    "String literal is 35 bytes long, but at most 32 bytes are allowed.".to_owned(),
+                    "é!"
+                )),
+                Err((
+                    "Hex string literal is 35 bytes long, but at most 32 bytes are allowed.".to_owned(),
+                    "00c0fe"
+                )),
+            ]
+        );
+
+        assert_eq!(
+            U256::MAX.to_string(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        assert_eq!(
+            format!("{:#x}", Value::Bytes(b"a".to_vec()).to_u256()),
+            format!("0x61{}", "0".repeat(62))
+        );
+    }
+}
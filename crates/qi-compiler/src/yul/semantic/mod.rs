@@ -0,0 +1,595 @@
+//! The Yul scope resolution and semantic checks.
+//! Every identifier is resolved to a declaration or a builtin, and the violations of the Yul rules
+//! are reported: unknown, shadowed or repeated names, misplaced `break`, `continue` and `leave`, the calls
+//! which do not match the number of arguments or returned values, the duplicate switch cases, and the
+//! invalid literals.
+//! The types of the opt-in typed dialect are checked separately, on the resolved symbols.
+
+pub mod builtin;
+pub mod literal;
+pub mod symbol_table;
+pub mod typing;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use rowan::TextRange;
+
+use super::ast::Block;
+use super::ast::Expression;
+use super::ast::FunctionDefinition;
+use super::ast::Identifier;
+use super::ast::Literal;
+use super::ast::Object;
+use super::ast::Statement;
+use super::ast::TypedIdentifier;
+
+use self::builtin::Builtin;
+use self::symbol_table::Declaration;
+use self::symbol_table::DeclarationId;
+use self::symbol_table::DeclarationKind;
+use self::symbol_table::Resolution;
+use self::symbol_table::SymbolTable;
+
+/// The semantic error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticError {
+    /// The error message.
+    pub message: String,
+    /// The range of the offending identifier or statement.
+    pub range: TextRange,
+}
+
+impl std::fmt::Display for SemanticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The semantic analysis result.
+#[derive(Debug, Default, Clone)]
+pub struct Analysis {
+    /// The resolved symbols.
+    pub symbols: SymbolTable,
+    /// The semantic errors.
+    pub errors: Vec<SemanticError>,
+}
+
+/// Analyzes the object code and the inner objects, each of which has its own scopes.
+pub fn analyze(object: &Object) -> Analysis {
+    let mut analyzer = Analyzer::default();
+    analyzer.object(object);
+    analyzer.analysis
+}
+
+/// Analyzes the bare block.
+pub fn analyze_block(block: &Block) -> Analysis {
+    let mut analyzer = Analyzer::default();
+    analyzer.block(block);
+    analyzer.analysis
+}
+
+/// The scope of a block or a function signature.
+#[derive(Debug, Default)]
+struct Scope {
+    /// The names declared in the scope.
+    names: HashMap<String, DeclarationId>,
+    /// Whether the scope is the function signature, beyond which the variables cannot be accessed.
+    is_function: bool,
+}
+
+/// Where the statements are.
+#[derive(Debug, Default, Clone, Copy)]
+struct Context {
+    /// Inside a function body.
+    is_in_function: bool,
+    /// Inside a `for` loop body.
+    is_in_loop_body: bool,
+    /// Inside a `for` loop initialization block.
+    is_in_loop_initializer: bool,
+}
+
+/// The semantic analyzer.
+#[derive(Debug, Default)]
+struct Analyzer {
+    /// The analysis result.
+    analysis: Analysis,
+    /// The scopes, from the outermost one.
+    scopes: Vec<Scope>,
+    /// Where the statements are.
+    context: Context,
+}
+
+impl Analyzer {
+    /// Analyzes the object.
+    fn object(&mut self, object: &Object) {
+        self.block(&object.code.block);
+        for object in object.objects.iter() {
+            self.object(object);
+        }
+    }
+
+    /// Analyzes the block in its own scope.
+    fn block(&mut self, block: &Block) {
+        self.scopes.push(Scope::default());
+        self.statements(block.statements.as_slice());
+        self.scopes.pop();
+    }
+
+    /// Analyzes the statements in the current scope.
+    /// The functions are declared first, since they are visible in the whole block.
+    fn statements(&mut self, statements: &[Statement]) {
+        for statement in statements.iter() {
+            if let Statement::FunctionDefinition(definition) = statement {
+                self.declare(
+                    &definition.name,
+                    DeclarationKind::Function {
+                        parameters: definition.parameters.len(),
+                        returns: definition.returns.len(),
+                    },
+                );
+            }
+        }
+        for statement in statements.iter() {
+            self.statement(statement);
+        }
+    }
+
+    /// Analyzes the statement.
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block) => self.block(block),
+            Statement::FunctionDefinition(definition) => self.function_definition(definition),
+            Statement::VariableDeclaration(declaration) => {
+                if let Some(value) = declaration.value.as_ref() {
+                    self.expression(value, declaration.variables.len());
+                }
+                for variable in declaration.variables.iter() {
+                    self.declare(&variable.identifier, DeclarationKind::Variable);
+                }
+            }
+            Statement::Assignment(assignment) => {
+                self.expression(&assignment.value, assignment.variables.len());
+                let mut names = HashSet::with_capacity(assignment.variables.len());
+                for variable in assignment.variables.iter() {
+                    if !names.insert(variable.name.as_str()) {
+                        self.error(
+                            format!(
+                                "Variable `{}` occurs multiple times on the left-hand side of the assignment.",
+                                variable.name
+                            ),
+                            variable.range,
+                        );
+                    }
+                    self.variable(variable);
+                }
+            }
+            Statement::If(r#if) => {
+                self.expression(&r#if.condition, 1);
+                self.block(&r#if.body);
+            }
+            Statement::Expression(expression) => self.expression(expression, 0),
+            Statement::Switch(switch) => {
+                self.expression(&switch.expression, 1);
+                let mut values = HashSet::with_capacity(switch.cases.len());
+                for case in switch.cases.iter() {
+                    if let Some(value) = self.literal(&case.value)
+                        && !values.insert(value.to_u256())
+                    {
+                        self.error(
+                            format!("Duplicate case `{}` defined.", case.value.value),
+                            case.value.range,
+                        );
+                    }
+                    self.block(&case.body);
+                }
+                if let Some(default) = switch.default.as_ref() {
+                    self.block(default);
+                }
+            }
+            Statement::ForLoop(for_loop) => {
+                let context = self.context;
+                self.scopes.push(Scope::default());
+                self.context.is_in_loop_initializer = true;
+                self.context.is_in_loop_body = false;
+                self.statements(for_loop.initializer.statements.as_slice());
+                self.context.is_in_loop_initializer = false;
+                self.expression(&for_loop.condition, 1);
+                self.block(&for_loop.finalizer);
+                self.context.is_in_loop_body = true;
+                self.block(&for_loop.body);
+                self.scopes.pop();
+                self.context = context;
+            }
+            Statement::Break(range) | Statement::Continue(range) => {
+                if !self.context.is_in_loop_body {
+                    let keyword = match statement {
+                        Statement::Break(_) => "break",
+                        _ => "continue",
+                    };
+                    self.error(
+                        format!("Keyword `{keyword}` needs to be inside a `for` loop body."),
+                        *range,
+                    );
+                }
+            }
+            Statement::Leave(range) => {
+                if !self.context.is_in_function {
+                    self.error(
+                        "Keyword `leave` can only be used inside a function.".to_owned(),
+                        *range,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Analyzes the function body, where only the parameters, the return variables and the functions are accessible.
+    fn function_definition(&mut self, definition: &FunctionDefinition) {
+        if self.context.is_in_loop_initializer {
+            self.error(
+                "Functions cannot be defined inside a `for` loop initialization block.".to_owned(),
+                definition.name.range,
+            );
+        }
+
+        let context = self.context;
+        self.context = Context {
+            is_in_function: true,
+            ..Context::default()
+        };
+        self.scopes.push(Scope {
+            is_function: true,
+            ..Scope::default()
+        });
+        for variable in definition
+            .parameters
+            .iter()
+            .chain(definition.returns.iter())
+            .map(|variable: &TypedIdentifier| &variable.identifier)
+        {
+            self.declare(variable, DeclarationKind::Variable);
+        }
+        self.block(&definition.body);
+        self.scopes.pop();
+        self.context = context;
+    }
+
+    /// Analyzes the expression, which must return the `expected` number of values.
+    fn expression(&mut self, expression: &Expression, expected: usize) {
+        let returns = match expression {
+            Expression::FunctionCall(call) => {
+                let name_parameter =
+                    Builtin::get(call.name.name.as_str()).and_then(Builtin::name_parameter);
+                for (index, argument) in call.arguments.iter().enumerate() {
+                    if Some(index) == name_parameter {
+                        self.name(argument);
+                    } else {
+                        self.expression(argument, 1);
+                    }
+                }
+                let Some((parameters, returns)) = self.function(&call.name) else {
+                    return;
+                };
+                if call.arguments.len() != parameters {
+                    self.error(
+                        format!(
+                            "Function `{}` expects {}, but {} given.",
+                            call.name.name,
+                            count(parameters, "argument"),
+                            count(call.arguments.len(), "argument"),
+                        ),
+                        call.range,
+                    );
+                }
+                returns
+            }
+            Expression::Identifier(identifier) => {
+                if self.variable(identifier).is_none() {
+                    return;
+                }
+                1
+            }
+            Expression::Literal(literal) => {
+                self.literal(literal);
+                1
+            }
+        };
+
+        if returns == expected {
+            return;
+        }
+        let message = if expected == 0 {
+            format!(
+                "Top-level expressions must not return values, but this one returns {}. Use `pop` or assign them.",
+                count(returns, "value")
+            )
+        } else {
+            format!(
+                "Expected {}, but the expression returns {}.",
+                count(expected, "value"),
+                count(returns, "value")
+            )
+        };
+        self.error(message, expression.range());
+    }
+
+    /// Evaluates the literal to report the overflows, invalid escapes and too long strings.
+    fn literal(&mut self, literal: &Literal) -> Option<literal::Value> {
+        literal::evaluate(literal)
+            .map_err(|error| self.analysis.errors.push(error))
+            .ok()
+    }
+
+    /// Checks the name passed to an object access builtin, which is a string literal of any length.
+    fn name(&mut self, argument: &Expression) {
+        let Expression::Literal(literal) = argument else {
+            self.error(
+                "Expected a string literal name.".to_owned(),
+                argument.range(),
+            );
+            return;
+        };
+        if let Err(error) = literal::name(literal) {
+            self.analysis.errors.push(error);
+        }
+    }
+
+    /// Resolves the called function and returns its number of parameters and returned values.
+    fn function(&mut self, name: &Identifier) -> Option<(usize, usize)> {
+        if let Some(builtin) = Builtin::get(name.name.as_str()) {
+            self.analysis
+                .symbols
+                .resolutions
+                .insert(name.range, Resolution::Builtin(builtin));
+            return Some((builtin.parameters, builtin.returns));
+        }
+
+        let Some((id, _)) = self.lookup(name.name.as_str()) else {
+            self.error(format!("Function `{}` not found.", name.name), name.range);
+            return None;
+        };
+        let DeclarationKind::Function {
+            parameters,
+            returns,
+        } = self.analysis.symbols.declaration(id).kind
+        else {
+            self.error(
+                format!("Variable `{}` cannot be called.", name.name),
+                name.range,
+            );
+            return None;
+        };
+        self.analysis
+            .symbols
+            .resolutions
+            .insert(name.range, Resolution::Declaration(id));
+        Some((parameters, returns))
+    }
+
+    /// Resolves the used or assigned variable.
+    fn variable(&mut self, name: &Identifier) -> Option<DeclarationId> {
+        if Builtin::get(name.name.as_str()).is_some() {
+            self.error(
+                format!("Builtin function `{}` must be called.", name.name),
+                name.range,
+            );
+            return None;
+        }
+
+        let Some((id, is_accessible)) = self.lookup(name.name.as_str()) else {
+            self.error(format!("Variable `{}` not found.", name.name), name.range);
+            return None;
+        };
+        if !matches!(
+            self.analysis.symbols.declaration(id).kind,
+            DeclarationKind::Variable
+        ) {
+            self.error(
+                format!("Function `{}` must be called.", name.name),
+                name.range,
+            );
+            return None;
+        }
+        if !is_accessible {
+            self.error(
+                format!(
+                    "Variable `{}` is declared outside of the function and cannot be accessed.",
+                    name.name
+                ),
+                name.range,
+            );
+            return None;
+        }
+        self.analysis
+            .symbols
+            .resolutions
+            .insert(name.range, Resolution::Declaration(id));
+        Some(id)
+    }
+
+    /// Declares the name in the current scope, unless it is a builtin or already visible.
+    fn declare(&mut self, name: &Identifier, kind: DeclarationKind) {
+        if Builtin::get(name.name.as_str()).is_some() {
+            self.error(
+                format!("Builtin function name `{}` cannot be declared.", name.name),
+                name.range,
+            );
+            return;
+        }
+        if self.lookup(name.name.as_str()).is_some() {
+            self.error(
+                format!(
+                    "Name `{}` is already taken, and cannot be shadowed.",
+                    name.name
+                ),
+                name.range,
+            );
+            return;
+        }
+
+        let id = self.analysis.symbols.declare(Declaration {
+            name: name.name.clone(),
+            kind,
+            range: name.range,
+        });
+        self.scopes
+            .last_mut()
+            .expect("Always exists")
+            .names
+            .insert(name.name.clone(), id);
+    }
+
+    /// Looks the name up in the visible scopes.
+    /// Also returns whether the declaration is accessible, which is not the case
+    /// for the variables declared outside of the current function.
+    fn lookup(&self, name: &str) -> Option<(DeclarationId, bool)> {
+        let mut is_outside_function = false;
+        for scope in self.scopes.iter().rev() {
+            if let Some(id) = scope.names.get(name) {
+                let is_variable = matches!(
+                    self.analysis.symbols.declaration(*id).kind,
+                    DeclarationKind::Variable
+                );
+                return Some((*id, !(is_variable && is_outside_function)));
+            }
+            if scope.is_function {
+                is_outside_function = true;
+            }
+        }
+        None
+    }
+
+    /// Adds the error.
+    fn error(&mut self, message: String, range: TextRange) {
+        self.analysis.errors.push(SemanticError { message, range });
+    }
+}
+
+/// Formats the number of things, e.g. `1 value` or `2 values`.
+fn count(number: usize, thing: &str) -> String {
+    match number {
+        1 => format!("1 {thing}"),
+        number => format!("{number} {thing}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::ast::Object;
+
+    use super::symbol_table::DeclarationKind;
+    use super::symbol_table::Resolution;
+
+    #[test]
+    fn resolves_and_reports_violations() {
+        let yul = r#"object "A" {
+    code {
+        let x := f(1)
+        function f(a) -> b {
+            b := add(a, x)
+            leave
+        }
+        let x := g()
+        function g() -> c, d { }
+        pop(f)
+        for { let i := 0 } lt(i, 10) { break } {
+            continue
+        }
+        leave
+        mstore(0)
+        f(2)
+    }
+}
+"#;
+        let object = Object::parse(yul).expect("Always valid");
+        let analysis = super::analyze(&object);
+
+        let errors = analysis
+            .errors
+            .iter()
+            .map(|error| (error.message.as_str(), &yul[error.range]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    "Variable `x` is declared outside of the function and cannot be accessed.",
+                    "x"
+                ),
+                ("Expected 1 value, but the expression returns 2 values.", "g()"),
+                ("Name `x` is already taken, and cannot be shadowed.", "x"),
+                ("Function `f` must be called.", "f"),
+                (
+                    "Keyword `break` needs to be inside a `for` loop body.",
+                    "break"
+                ),
+                (
+                    "Keyword `leave` can only be used inside a function.",
+                    "leave"
+                ),
+                ("Function `mstore` expects 2 arguments, but 1 argument given.", "mstore(0)"),
+                (
+                    "Top-level expressions must not return values, but this one returns 1 value. Use `pop` or assign them.",
+                    "f(2)"
+                ),
+            ]
+        );
+
+        let call = yul.find("f(1)").unwrap() as u32;
+        let Some(Resolution::Declaration(id)) = analysis
+            .symbols
+            .resolve(rowan::TextRange::at(call.into(), 1.into()))
+        else {
+            panic!("`f` is not resolved");
+        };
+        assert_eq!(
+            analysis.symbols.declaration(id).kind,
+            DeclarationKind::Function {
+                parameters: 1,
+                returns: 1
+            }
+        );
+    }
+
+    #[test]
+    fn reports_the_duplicates() {
+        let yul = r#"{
+    function f() -> a, b { }
+    let x, x := f()
+    let y, z
+    y, y := f()
+    y, z := f()
+    switch y
+    case 1 { }
+    case 0x01 { }
+    case "a" { }
+    case 0x6100000000000000000000000000000000000000000000000000000000000000 { }
+    case 2 { }
+}
+"#;
+        let block = crate::yul::ast::Block::parse(yul).expect("Always valid");
+        let analysis = super::analyze_block(&block);
+
+        let errors = analysis
+            .errors
+            .iter()
+            .map(|error| (error.message.as_str(), &yul[error.range]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                ("Name `x` is already taken, and cannot be shadowed.", "x"),
+                (
+                    "Variable `y` occurs multiple times on the left-hand side of the assignment.",
+                    "y"
+                ),
+                ("Duplicate case `0x01` defined.", "0x01"),
+                (
+                    "Duplicate case `0x6100000000000000000000000000000000000000000000000000000000000000` defined.",
+                    "0x6100000000000000000000000000000000000000000000000000000000000000"
+                ),
+            ]
+        );
+    }
+}
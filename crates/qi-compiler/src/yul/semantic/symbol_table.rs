@@ -0,0 +1,76 @@
+//! The resolved Yul symbols.
+
+use std::collections::HashMap;
+
+use rowan::TextRange;
+
+use super::builtin::Builtin;
+
+/// The declaration index in the symbol table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeclarationId(pub usize);
+
+/// The declaration kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclarationKind {
+    /// The variable, including the function parameters and return variables.
+    Variable,
+    /// The function.
+    Function {
+        /// The number of parameters.
+        parameters: usize,
+        /// The number of return variables.
+        returns: usize,
+    },
+}
+
+/// The variable or function declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declaration {
+    /// The name.
+    pub name: String,
+    /// The declaration kind.
+    pub kind: DeclarationKind,
+    /// The range of the declared name.
+    pub range: TextRange,
+}
+
+/// What the identifier refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// The user-defined variable or function.
+    Declaration(DeclarationId),
+    /// The builtin function.
+    Builtin(&'static Builtin),
+}
+
+/// The resolved Yul symbols.
+/// Both the declared names and their uses are resolved, keyed by their ranges.
+#[derive(Debug, Default, Clone)]
+pub struct SymbolTable {
+    /// The declarations, in the order they are found.
+    pub declarations: Vec<Declaration>,
+    /// The resolved identifiers.
+    pub resolutions: HashMap<TextRange, Resolution>,
+}
+
+impl SymbolTable {
+    /// Adds the declaration and resolves its name to it.
+    pub fn declare(&mut self, declaration: Declaration) -> DeclarationId {
+        let id = DeclarationId(self.declarations.len());
+        self.resolutions
+            .insert(declaration.range, Resolution::Declaration(id));
+        self.declarations.push(declaration);
+        id
+    }
+
+    /// Returns the declaration.
+    pub fn declaration(&self, id: DeclarationId) -> &Declaration {
+        &self.declarations[id.0]
+    }
+
+    /// Returns what the identifier at the range refers to.
+    pub fn resolve(&self, range: TextRange) -> Option<Resolution> {
+        self.resolutions.get(&range).copied()
+    }
+}
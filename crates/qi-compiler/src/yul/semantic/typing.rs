@@ -0,0 +1,613 @@
+//! The typed Yul dialect checks.
+//! The opt-in dialect annotates the variables, parameters and literals with `u256`, `bool`,
+//! `uintN` or `intN`. The unannotated names are `u256`, except the variables declared with a value,
+//! which take its type, and the number literals, which take the type expected by their context.
+//! The checks run on the resolved symbols, so the code must be free of the semantic errors first.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use rowan::TextRange;
+
+use crate::yul::ast::Block;
+use crate::yul::ast::Expression;
+use crate::yul::ast::FunctionCall;
+use crate::yul::ast::Identifier;
+use crate::yul::ast::Literal;
+use crate::yul::ast::LiteralKind;
+use crate::yul::ast::Object;
+use crate::yul::ast::Statement;
+use crate::yul::ast::TypeName;
+
+use super::builtin::Builtin;
+use super::literal;
+use super::symbol_table::DeclarationId;
+use super::symbol_table::DeclarationKind;
+use super::symbol_table::Resolution;
+use super::symbol_table::SymbolTable;
+use super::SemanticError;
+
+/// The typed Yul value type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Type {
+    /// The `bool` type.
+    Bool,
+    /// The unsigned integer type of the given bit length, `u256` being the default one.
+    Uint(u16),
+    /// The signed integer type of the given bit length.
+    Int(u16),
+}
+
+impl Type {
+    /// The default type of the untyped names and literals.
+    pub const U256: Self = Self::Uint(256);
+
+    /// Whether the type is an integer one.
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, Self::Bool)
+    }
+
+    /// Returns the integer bit length, or `None` for `bool`.
+    pub fn bit_length(&self) -> Option<u16> {
+        match self {
+            Self::Bool => None,
+            Self::Uint(bit_length) | Self::Int(bit_length) => Some(*bit_length),
+        }
+    }
+}
+
+impl FromStr for Type {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let integer = |digits: &str, constructor: fn(u16) -> Self| {
+            digits
+                .parse::<u16>()
+                .ok()
+                .filter(|bit_length| (8..=256).contains(bit_length) && bit_length % 8 == 0)
+                .map(constructor)
+        };
+        let r#type = match string {
+            "bool" => Some(Self::Bool),
+            "u256" => Some(Self::U256),
+            string => match string.strip_prefix("uint") {
+                Some(digits) => integer(digits, Self::Uint),
+                None => string
+                    .strip_prefix("int")
+                    .and_then(|digits| integer(digits, Self::Int)),
+            },
+        };
+        r#type.ok_or_else(|| anyhow::anyhow!("Invalid type `{string}`"))
+    }
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool => write!(f, "bool"),
+            Self::Uint(256) => write!(f, "u256"),
+            Self::Uint(bit_length) => write!(f, "uint{bit_length}"),
+            Self::Int(bit_length) => write!(f, "int{bit_length}"),
+        }
+    }
+}
+
+/// The builtin signature in the typed dialect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Signature {
+    /// `(T, ..) -> T` for the integer `T`.
+    Arithmetic,
+    /// `(T, ..) -> T` for any `T`.
+    Bitwise,
+    /// `(integer, T) -> T` for the integer `T`.
+    Shift,
+    /// `(T, T) -> bool` for the integer `T`.
+    Comparison,
+    /// `(T, ..) -> bool` for any `T`.
+    Equality,
+    /// `(T) -> ()` for any `T`.
+    Pop,
+    /// The EVM signature, where every argument and returned value is `u256`.
+    Evm,
+}
+
+impl Signature {
+    /// Returns the signature of the builtin.
+    fn of(builtin: &Builtin) -> Self {
+        match builtin.name {
+            "add" | "sub" | "mul" | "div" | "sdiv" | "mod" | "smod" | "exp" | "addmod"
+            | "mulmod" => Self::Arithmetic,
+            "and" | "or" | "xor" | "not" => Self::Bitwise,
+            "shl" | "shr" | "sar" => Self::Shift,
+            "lt" | "gt" | "slt" | "sgt" => Self::Comparison,
+            "eq" | "iszero" => Self::Equality,
+            "pop" => Self::Pop,
+            _ => Self::Evm,
+        }
+    }
+
+    /// Whether the operands may be `bool`.
+    fn allows_bool(&self) -> bool {
+        matches!(self, Self::Bitwise | Self::Equality | Self::Pop)
+    }
+}
+
+/// The type checking result.
+#[derive(Debug, Default, Clone)]
+pub struct Typing {
+    /// The variable types.
+    pub types: HashMap<DeclarationId, Type>,
+    /// The type errors.
+    pub errors: Vec<SemanticError>,
+}
+
+/// Checks the types of the object code and the inner objects.
+pub fn check(object: &Object, symbols: &SymbolTable) -> Typing {
+    let mut checker = Checker::new(symbols);
+    checker.object(object);
+    checker.typing
+}
+
+/// Checks the types of the bare block.
+pub fn check_block(block: &Block, symbols: &SymbolTable) -> Typing {
+    let mut checker = Checker::new(symbols);
+    checker.signatures(block.statements.as_slice());
+    checker.statements(block.statements.as_slice());
+    checker.typing
+}
+
+/// The type checker.
+#[derive(Debug)]
+struct Checker<'a> {
+    /// The resolved symbols.
+    symbols: &'a SymbolTable,
+    /// The type checking result.
+    typing: Typing,
+    /// The function parameter and return types.
+    functions: HashMap<DeclarationId, (Vec<Type>, Vec<Type>)>,
+}
+
+impl<'a> Checker<'a> {
+    /// A shortcut constructor.
+    fn new(symbols: &'a SymbolTable) -> Self {
+        Self {
+            symbols,
+            typing: Typing::default(),
+            functions: HashMap::new(),
+        }
+    }
+
+    /// Checks the object.
+    fn object(&mut self, object: &Object) {
+        self.signatures(object.code.block.statements.as_slice());
+        self.statements(object.code.block.statements.as_slice());
+        for object in object.objects.iter() {
+            self.object(object);
+        }
+    }
+
+    /// Collects the signatures of the functions defined in the statements, at any depth,
+    /// since the functions can be called before their definitions.
+    fn signatures(&mut self, statements: &[Statement]) {
+        for statement in statements.iter() {
+            match statement {
+                Statement::Block(block) => self.signatures(block.statements.as_slice()),
+                Statement::FunctionDefinition(definition) => {
+                    let parameters = definition
+                        .parameters
+                        .iter()
+                        .map(|parameter| self.annotation(parameter.r#type.as_ref()))
+                        .collect();
+                    let returns = definition
+                        .returns
+                        .iter()
+                        .map(|variable| self.annotation(variable.r#type.as_ref()))
+                        .collect();
+                    if let Some(id) = self.declaration(&definition.name) {
+                        self.functions.insert(id, (parameters, returns));
+                    }
+                    self.signatures(definition.body.statements.as_slice());
+                }
+                Statement::If(r#if) => self.signatures(r#if.body.statements.as_slice()),
+                Statement::Switch(switch) => {
+                    for block in switch
+                        .cases
+                        .iter()
+                        .map(|case| &case.body)
+                        .chain(switch.default.iter())
+                    {
+                        self.signatures(block.statements.as_slice());
+                    }
+                }
+                Statement::ForLoop(for_loop) => {
+                    for block in [&for_loop.initializer, &for_loop.finalizer, &for_loop.body] {
+                        self.signatures(block.statements.as_slice());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Checks the statements.
+    fn statements(&mut self, statements: &[Statement]) {
+        for statement in statements.iter() {
+            self.statement(statement);
+        }
+    }
+
+    /// Checks the statement.
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block) => self.statements(block.statements.as_slice()),
+            Statement::FunctionDefinition(definition) => {
+                if let Some((parameters, returns)) = self
+                    .declaration(&definition.name)
+                    .and_then(|id| self.functions.get(&id))
+                    .cloned()
+                {
+                    for (variable, r#type) in definition
+                        .parameters
+                        .iter()
+                        .chain(definition.returns.iter())
+                        .zip(parameters.into_iter().chain(returns))
+                    {
+                        self.define(&variable.identifier, r#type);
+                    }
+                }
+                self.statements(definition.body.statements.as_slice());
+            }
+            Statement::VariableDeclaration(declaration) => {
+                let annotations = declaration
+                    .variables
+                    .iter()
+                    .map(|variable| {
+                        variable
+                            .r#type
+                            .as_ref()
+                            .map(|r#type| self.type_name(r#type))
+                    })
+                    .collect::<Vec<_>>();
+                let values = declaration.value.as_ref().map(|value| {
+                    let expected = match annotations.as_slice() {
+                        [annotation] => *annotation,
+                        _ => None,
+                    };
+                    (self.expression(value, expected), value.range())
+                });
+                for (index, variable) in declaration.variables.iter().enumerate() {
+                    let value = values
+                        .as_ref()
+                        .and_then(|(types, range)| Some((*types.get(index)?, *range)));
+                    let r#type = match (annotations[index], value) {
+                        (Some(expected), Some((found, range))) => {
+                            self.expect(expected, found, range);
+                            expected
+                        }
+                        (Some(expected), None) => expected,
+                        (None, Some((found, _))) => found,
+                        (None, None) => Type::U256,
+                    };
+                    self.define(&variable.identifier, r#type);
+                }
+            }
+            Statement::Assignment(assignment) => {
+                let expected = assignment
+                    .variables
+                    .iter()
+                    .map(|variable| self.variable(variable))
+                    .collect::<Vec<_>>();
+                let found = self.expression(
+                    &assignment.value,
+                    match expected.as_slice() {
+                        [expected] => Some(*expected),
+                        _ => None,
+                    },
+                );
+                for (expected, found) in expected.into_iter().zip(found) {
+                    self.expect(expected, found, assignment.value.range());
+                }
+            }
+            Statement::If(r#if) => {
+                self.condition(&r#if.condition);
+                self.statements(r#if.body.statements.as_slice());
+            }
+            Statement::Expression(expression) => {
+                self.expression(expression, None);
+            }
+            Statement::Switch(switch) => {
+                let r#type = self.value(&switch.expression, None);
+                for case in switch.cases.iter() {
+                    let found = self.literal(&case.value, Some(r#type));
+                    self.expect(r#type, found, case.value.range);
+                    self.statements(case.body.statements.as_slice());
+                }
+                if let Some(default) = switch.default.as_ref() {
+                    self.statements(default.statements.as_slice());
+                }
+            }
+            Statement::ForLoop(for_loop) => {
+                self.statements(for_loop.initializer.statements.as_slice());
+                self.condition(&for_loop.condition);
+                self.statements(for_loop.finalizer.statements.as_slice());
+                self.statements(for_loop.body.statements.as_slice());
+            }
+            Statement::Break(_) | Statement::Continue(_) | Statement::Leave(_) => {}
+        }
+    }
+
+    /// Checks the `if` or `for` loop condition, which must be `bool`.
+    fn condition(&mut self, condition: &Expression) {
+        let found = self.value(condition, Some(Type::Bool));
+        self.expect(Type::Bool, found, condition.range());
+    }
+
+    /// Checks the expression and returns the types of its values.
+    /// The `expected` type is only a hint for the untyped literals, the caller checks the values.
+    fn expression(&mut self, expression: &Expression, expected: Option<Type>) -> Vec<Type> {
+        match expression {
+            Expression::FunctionCall(call) => self.call(call, expected),
+            Expression::Identifier(identifier) => vec![self.variable(identifier)],
+            Expression::Literal(literal) => vec![self.literal(literal, expected)],
+        }
+    }
+
+    /// Checks the single-valued expression and returns its type.
+    fn value(&mut self, expression: &Expression, expected: Option<Type>) -> Type {
+        self.expression(expression, expected)
+            .first()
+            .copied()
+            .unwrap_or(Type::U256)
+    }
+
+    /// Checks the call and returns the types of its returned values.
+    fn call(&mut self, call: &FunctionCall, expected: Option<Type>) -> Vec<Type> {
+        match self.symbols.resolve(call.name.range) {
+            Some(Resolution::Builtin(builtin)) => self.builtin(call, builtin, expected),
+            Some(Resolution::Declaration(id)) => match self.functions.get(&id).cloned() {
+                Some((parameters, returns)) => {
+                    for (argument, expected) in call.arguments.iter().zip(parameters) {
+                        self.argument(argument, expected);
+                    }
+                    returns
+                }
+                None => {
+                    let returns = match self.symbols.declaration(id).kind {
+                        DeclarationKind::Function { returns, .. } => returns,
+                        DeclarationKind::Variable => 0,
+                    };
+                    vec![Type::U256; returns]
+                }
+            },
+            None => Vec::new(),
+        }
+    }
+
+    /// Checks the builtin call and returns the types of its returned values.
+    /// The operand type of the generic builtins is the expected result type if it fits,
+    /// otherwise the type of the first operand which is not an untyped literal.
+    fn builtin(
+        &mut self,
+        call: &FunctionCall,
+        builtin: &Builtin,
+        expected: Option<Type>,
+    ) -> Vec<Type> {
+        let signature = Signature::of(builtin);
+        let operands = match signature {
+            Signature::Evm => {
+                for argument in call.arguments.iter() {
+                    self.argument(argument, Type::U256);
+                }
+                return vec![Type::U256; builtin.returns];
+            }
+            Signature::Shift => {
+                if let Some(shift) = call.arguments.first() {
+                    let found = self.value(shift, None);
+                    if !found.is_integer() {
+                        self.expect(Type::U256, found, shift.range());
+                    }
+                }
+                call.arguments.get(1..).unwrap_or_default()
+            }
+            _ => call.arguments.as_slice(),
+        };
+
+        let mut r#type = match signature {
+            Signature::Arithmetic | Signature::Shift => expected.filter(Type::is_integer),
+            Signature::Bitwise => expected,
+            _ => None,
+        };
+        for operand in operands.iter() {
+            let found = self.value(operand, r#type);
+            match r#type {
+                Some(expected) => self.expect(expected, found, operand.range()),
+                None if !is_untyped_literal(operand) => r#type = Some(found),
+                None => {}
+            }
+        }
+        let r#type = r#type.unwrap_or(Type::U256);
+        if !r#type.is_integer() && !signature.allows_bool() {
+            self.error(
+                format!(
+                    "Builtin function `{}` is not defined for `{type}`.",
+                    builtin.name
+                ),
+                call.range,
+            );
+        }
+
+        match signature {
+            Signature::Comparison | Signature::Equality => vec![Type::Bool],
+            Signature::Pop => Vec::new(),
+            _ => vec![r#type],
+        }
+    }
+
+    /// Checks the call argument.
+    fn argument(&mut self, argument: &Expression, expected: Type) {
+        let found = self.value(argument, Some(expected));
+        self.expect(expected, found, argument.range());
+    }
+
+    /// Returns the type of the literal, checking that the numbers fit in it.
+    fn literal(&mut self, literal: &Literal, expected: Option<Type>) -> Type {
+        let is_boolean = literal.kind == LiteralKind::Boolean;
+        let r#type = match literal.r#type.as_ref() {
+            Some(r#type) => {
+                let r#type = self.type_name(r#type);
+                if is_boolean == r#type.is_integer() {
+                    self.error(
+                        format!("Literal `{}` cannot be of type `{type}`.", literal.value),
+                        literal.range,
+                    );
+                    return r#type;
+                }
+                r#type
+            }
+            None if is_boolean => Type::Bool,
+            None => expected.filter(Type::is_integer).unwrap_or(Type::U256),
+        };
+
+        let value_bits = match r#type {
+            Type::Bool => return r#type,
+            Type::Uint(bit_length) => bit_length as u32,
+            Type::Int(bit_length) => bit_length as u32 - 1,
+        };
+        if matches!(
+            literal.kind,
+            LiteralKind::Decimal | LiteralKind::Hexadecimal
+        ) && let Ok(value) = literal::evaluate(literal)
+            && value.to_u256().bits() > value_bits
+        {
+            self.error(
+                format!("Literal `{}` does not fit in type `{type}`.", literal.value),
+                literal.range,
+            );
+        }
+        r#type
+    }
+
+    /// Returns the type of the used or assigned variable.
+    fn variable(&self, identifier: &Identifier) -> Type {
+        self.declaration(identifier)
+            .and_then(|id| self.typing.types.get(&id))
+            .copied()
+            .unwrap_or(Type::U256)
+    }
+
+    /// Sets the type of the declared variable.
+    fn define(&mut self, identifier: &Identifier, r#type: Type) {
+        if let Some(id) = self.declaration(identifier) {
+            self.typing.types.insert(id, r#type);
+        }
+    }
+
+    /// Returns the declaration the identifier is resolved to.
+    fn declaration(&self, identifier: &Identifier) -> Option<DeclarationId> {
+        match self.symbols.resolve(identifier.range) {
+            Some(Resolution::Declaration(id)) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Returns the annotated type, or `u256` if there is no annotation.
+    fn annotation(&mut self, r#type: Option<&TypeName>) -> Type {
+        r#type
+            .map(|r#type| self.type_name(r#type))
+            .unwrap_or(Type::U256)
+    }
+
+    /// Returns the named type, reporting the unknown ones as `u256`.
+    fn type_name(&mut self, r#type: &TypeName) -> Type {
+        match Type::from_str(r#type.name.as_str()) {
+            Ok(r#type) => r#type,
+            Err(_) => {
+                self.error(format!("Type `{}` not found.", r#type.name), r#type.range);
+                Type::U256
+            }
+        }
+    }
+
+    /// Reports the type mismatch, if any.
+    fn expect(&mut self, expected: Type, found: Type, range: TextRange) {
+        if expected != found {
+            self.error(
+                format!("Expected a value of type `{expected}`, but got `{found}`."),
+                range,
+            );
+        }
+    }
+
+    /// Adds the error.
+    fn error(&mut self, message: String, range: TextRange) {
+        self.typing.errors.push(SemanticError { message, range });
+    }
+}
+
+/// Checks whether the expression is a number or string literal without a type,
+/// which takes the type expected by its context.
+fn is_untyped_literal(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Literal(literal) if literal.r#type.is_none() && literal.kind != LiteralKind::Boolean
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::ast::Block;
+
+    use super::Type;
+
+    #[test]
+    fn infers_and_checks_types() {
+        let yul = r#"{
+    function narrow(a:uint8, b) -> c:uint8 {
+        c := add(a, 1)
+        if lt(b, 10) { c := b }
+    }
+    let x := narrow(200, 3)
+    let y:bool := x
+    let flag := and(iszero(x), true)
+    if flag { sstore(0, x) }
+    if x { }
+    switch x case 2:uint8 { } case true { }
+    let z := add(false, flag)
+    let w:int512 := 0:bool
+    let v:int8 := add(128, 0:int8)
+}
+"#;
+        let block = Block::parse(yul).expect("Always valid");
+        let analysis = crate::yul::semantic::analyze_block(&block);
+        assert!(analysis.errors.is_empty());
+        let typing = super::check_block(&block, &analysis.symbols);
+
+        let errors = typing
+            .errors
+            .iter()
+            .map(|error| (error.message.as_str(), &yul[error.range]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                ("Expected a value of type `uint8`, but got `u256`.", "b"),
+                ("Expected a value of type `bool`, but got `uint8`.", "x"),
+                ("Expected a value of type `u256`, but got `uint8`.", "x"),
+                ("Expected a value of type `bool`, but got `uint8`.", "x"),
+                ("Expected a value of type `uint8`, but got `bool`.", "true"),
+                (
+                    "Builtin function `add` is not defined for `bool`.",
+                    "add(false, flag)"
+                ),
+                ("Type `int512` not found.", "int512"),
+                ("Literal `0` cannot be of type `bool`.", "0:bool"),
+                ("Expected a value of type `u256`, but got `bool`.", "0:bool"),
+                ("Literal `128` does not fit in type `int8`.", "128"),
+            ]
+        );
+
+        assert!(typing.types.values().any(|r#type| *r#type == Type::Uint(8)));
+        assert_eq!("int64".parse::<Type>().ok(), Some(Type::Int(64)));
+        assert!("uint7".parse::<Type>().is_err());
+    }
+}
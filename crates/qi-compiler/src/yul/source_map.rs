@@ -0,0 +1,153 @@
+//! The `@src` and `@use-src` annotations emitted by `solc` into the Yul code.
+//!
+//! ```text
+//! /// @use-src 0:"contracts/Token.sol"
+//! object "Token_42" {
+//!     code {
+//!         /// @src 0:63:210  "contract Token {..."
+//!         mstore(64, memoryguard(128))
+//! ```
+
+use std::collections::BTreeMap;
+
+use rowan::TextSize;
+
+use super::lexer::Lexer;
+use super::lexer::TokenKind;
+
+/// The Solidity source location of the Yul code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// The source unit name.
+    pub file: String,
+    /// The start byte offset.
+    pub start: usize,
+    /// The end byte offset, exclusive.
+    pub end: usize,
+}
+
+/// The `@src` annotation, where the `None` location stands for `@src -1:-1:-1`.
+type Annotation = (TextSize, Option<(usize, usize, usize)>);
+
+/// The Yul source map, built from the annotations in the comments.
+#[derive(Debug, Default, Clone)]
+pub struct SourceMap {
+    /// The source unit names by the source indexes, from the `@use-src` annotations in the order of appearance.
+    sources: Vec<(TextSize, BTreeMap<usize, String>)>,
+    /// The `@src` annotations in the order of appearance.
+    annotations: Vec<Annotation>,
+}
+
+impl SourceMap {
+    /// Collects the annotations from the Yul code.
+    pub fn new(yul: &str) -> Self {
+        let mut source_map = Self::default();
+        for token in Lexer::new(yul).filter(|token| token.kind == Ok(TokenKind::Comment)) {
+            let offset = token.range.start();
+            if let Some(sources) = tag_value(token.text, "@use-src") {
+                source_map.sources.push((offset, parse_use_src(sources)));
+            }
+            if let Some(location) = tag_value(token.text, "@src") {
+                let location = location.split_whitespace().next().unwrap_or_default();
+                source_map.annotations.push((offset, parse_src(location)));
+            }
+        }
+        source_map
+    }
+
+    /// Returns the Solidity location of the Yul code at the offset, from the closest preceding `@src` annotation.
+    /// The annotations without a location, such as `@src -1:-1:-1`, yield `None`.
+    pub fn locate(&self, offset: TextSize) -> Option<SourceLocation> {
+        let (_, location) = self
+            .annotations
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= offset)?;
+        let (source_index, start, end) = (*location)?;
+        let (_, sources) = self
+            .sources
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= offset)?;
+        Some(SourceLocation {
+            file: sources.get(&source_index)?.to_owned(),
+            start,
+            end,
+        })
+    }
+}
+
+/// Returns the text following the tag in the comment, e.g. `0:1:2` for `@src 0:1:2`.
+fn tag_value<'a>(comment: &'a str, tag: &str) -> Option<&'a str> {
+    comment
+        .match_indices(tag)
+        .map(|(offset, _)| &comment[offset + tag.len()..])
+        .find(|rest| rest.starts_with(char::is_whitespace))
+        .map(|rest| rest.trim_start().trim_end_matches("*/"))
+}
+
+/// Parses the `@src` location, e.g. `0:63:210`.
+fn parse_src(location: &str) -> Option<(usize, usize, usize)> {
+    let mut parts = location
+        .splitn(3, ':')
+        .map(|part| part.parse::<usize>().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+/// Parses the `@use-src` sources, e.g. `0:"contracts/Token.sol", 1:"#utility.yul"`.
+fn parse_use_src(sources: &str) -> BTreeMap<usize, String> {
+    let mut result = BTreeMap::new();
+    let mut rest = sources;
+    while let Some((index, tail)) = rest.split_once(":\"") {
+        let Some(end) = tail.find('"') else {
+            break;
+        };
+        if let Ok(index) = index.trim().trim_start_matches(',').trim().parse::<usize>() {
+            result.insert(index, tail[..end].to_owned());
+        }
+        rest = &tail[end + 1..];
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use rowan::TextSize;
+
+    use super::SourceLocation;
+    use super::SourceMap;
+
+    #[test]
+    fn locates_the_closest_annotation() {
+        let yul = r##"/// @use-src 0:"contracts/A.sol", 1:"#utility.yul"
+object "A_7" {
+    code {
+        /// @src 0:25:60  "contract A {..."
+        mstore(64, memoryguard(128))
+        /** @src -1:-1:-1 */
+        let x := 1
+        /// @ast-id 6 @src 0:40:58  "function f() {}"
+        sstore(0, x)
+    }
+}"##;
+        let source_map = SourceMap::new(yul);
+        let at = |pattern: &str| TextSize::new(yul.find(pattern).unwrap() as u32);
+
+        assert_eq!(source_map.locate(at("object")), None);
+        assert_eq!(
+            source_map.locate(at("mstore")),
+            Some(SourceLocation {
+                file: "contracts/A.sol".to_owned(),
+                start: 25,
+                end: 60,
+            })
+        );
+        assert_eq!(source_map.locate(at("let x")), None);
+        assert_eq!(
+            source_map
+                .locate(at("sstore"))
+                .map(|location| location.start),
+            Some(40)
+        );
+    }
+}
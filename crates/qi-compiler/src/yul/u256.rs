@@ -0,0 +1,330 @@
+//! The 256-bit unsigned integer of the Yul values.
+
+/// The 256-bit unsigned integer, stored as little-endian 64-bit limbs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct U256(pub [u64; 4]);
+
+impl U256 {
+    /// The zero value.
+    pub const ZERO: Self = Self([0; 4]);
+    /// The one value.
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+    /// The maximum value, `2^256-1`.
+    pub const MAX: Self = Self([u64::MAX; 4]);
+
+    /// Creates the value from the big-endian bytes.
+    pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        let mut limbs = [0; 4];
+        for (index, chunk) in bytes.chunks_exact(8).enumerate() {
+            limbs[3 - index] = u64::from_be_bytes(chunk.try_into().expect("Always valid"));
+        }
+        Self(limbs)
+    }
+
+    /// Returns the big-endian bytes.
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        for (index, limb) in self.0.iter().rev().enumerate() {
+            bytes[index * 8..(index + 1) * 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Whether the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    /// Returns the value if it fits in `u64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.0 {
+            [value, 0, 0, 0] => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of significant bits.
+    pub fn bits(&self) -> u32 {
+        self.0
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, limb)| **limb != 0)
+            .map(|(index, limb)| index as u32 * 64 + 64 - limb.leading_zeros())
+            .unwrap_or_default()
+    }
+
+    /// Computes `self * multiplier + addend`, or returns `None` on overflow.
+    pub fn checked_mul_add(&self, multiplier: u64, addend: u64) -> Option<Self> {
+        let mut limbs = [0; 4];
+        let mut carry = addend as u128;
+        for (result, limb) in limbs.iter_mut().zip(self.0.iter()) {
+            let value = *limb as u128 * multiplier as u128 + carry;
+            *result = value as u64;
+            carry = value >> 64;
+        }
+        (carry == 0).then_some(Self(limbs))
+    }
+
+    /// Computes `self + other`, returning whether it overflows.
+    pub fn overflowing_add(&self, other: &Self) -> (Self, bool) {
+        let mut limbs = [0; 4];
+        let mut carry = false;
+        for (index, result) in limbs.iter_mut().enumerate() {
+            let (value, overflow) = self.0[index].overflowing_add(other.0[index]);
+            let (value, carry_overflow) = value.overflowing_add(carry as u64);
+            *result = value;
+            carry = overflow || carry_overflow;
+        }
+        (Self(limbs), carry)
+    }
+
+    /// Computes `self + other` modulo `2^256`.
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        self.overflowing_add(other).0
+    }
+
+    /// Computes `self - other` modulo `2^256`.
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        self.wrapping_add(&other.wrapping_neg())
+    }
+
+    /// Computes `self * other` modulo `2^256`.
+    pub fn wrapping_mul(&self, other: &Self) -> Self {
+        let mut limbs = [0; 4];
+        for (index, limb) in self.0.iter().enumerate() {
+            let mut carry = 0u128;
+            for other_index in 0..4 - index {
+                let value = *limb as u128 * other.0[other_index] as u128
+                    + limbs[index + other_index] as u128
+                    + carry;
+                limbs[index + other_index] = value as u64;
+                carry = value >> 64;
+            }
+        }
+        Self(limbs)
+    }
+
+    /// Computes `-self` modulo `2^256`, the two's complement.
+    pub fn wrapping_neg(&self) -> Self {
+        (!*self).wrapping_add(&Self::ONE)
+    }
+
+    /// Whether the value is negative as a two's complement signed integer.
+    pub fn is_negative(&self) -> bool {
+        self.0[3] >> 63 == 1
+    }
+
+    /// Divides by the `divisor`, returning the quotient and the remainder, or `None` if the `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let mut quotient = Self::ZERO;
+        let mut remainder = Self::ZERO;
+        for bit in (0..self.bits()).rev() {
+            let is_overflow = remainder.bit(255);
+            remainder = (remainder << 1) | Self::from(self.bit(bit));
+            if is_overflow || remainder >= *divisor {
+                remainder = remainder.wrapping_sub(divisor);
+                quotient.0[bit as usize / 64] |= 1 << (bit % 64);
+            }
+        }
+        Some((quotient, remainder))
+    }
+
+    /// Returns the bit at the index, counting from the least significant one.
+    pub fn bit(&self, index: u32) -> bool {
+        index < 256 && (self.0[index as usize / 64] >> (index % 64)) & 1 == 1
+    }
+
+    /// Divides by the `divisor`, returning the quotient and the remainder.
+    ///
+    /// # Panics
+    /// If the `divisor` is zero.
+    pub fn div_rem_u64(&self, divisor: u64) -> (Self, u64) {
+        let mut limbs = [0; 4];
+        let mut remainder = 0u128;
+        for (result, limb) in limbs.iter_mut().zip(self.0.iter()).rev() {
+            let value = (remainder << 64) | *limb as u128;
+            *result = (value / divisor as u128) as u64;
+            remainder = value % divisor as u128;
+        }
+        (Self(limbs), remainder as u64)
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::ops::Not for U256 {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(self.0.map(|limb| !limb))
+    }
+}
+
+impl std::ops::BitAnd for U256 {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        Self(std::array::from_fn(|index| self.0[index] & other.0[index]))
+    }
+}
+
+impl std::ops::BitOr for U256 {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        Self(std::array::from_fn(|index| self.0[index] | other.0[index]))
+    }
+}
+
+impl std::ops::BitXor for U256 {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self::Output {
+        Self(std::array::from_fn(|index| self.0[index] ^ other.0[index]))
+    }
+}
+
+/// The left shift, where the shifts by 256 bits or more give zero.
+impl std::ops::Shl<u32> for U256 {
+    type Output = Self;
+
+    fn shl(self, shift: u32) -> Self::Output {
+        let mut limbs = [0; 4];
+        if shift >= 256 {
+            return Self(limbs);
+        }
+        let (limb_shift, bit_shift) = ((shift / 64) as usize, shift % 64);
+        for (index, limb) in limbs.iter_mut().enumerate().skip(limb_shift) {
+            *limb = self.0[index - limb_shift] << bit_shift;
+            if bit_shift > 0 && index > limb_shift {
+                *limb |= self.0[index - limb_shift - 1] >> (64 - bit_shift);
+            }
+        }
+        Self(limbs)
+    }
+}
+
+/// The logical right shift, where the shifts by 256 bits or more give zero.
+impl std::ops::Shr<u32> for U256 {
+    type Output = Self;
+
+    fn shr(self, shift: u32) -> Self::Output {
+        let mut limbs = [0; 4];
+        if shift >= 256 {
+            return Self(limbs);
+        }
+        let (limb_shift, bit_shift) = ((shift / 64) as usize, shift % 64);
+        for (index, limb) in limbs.iter_mut().enumerate().take(4 - limb_shift) {
+            *limb = self.0[index + limb_shift] >> bit_shift;
+            if bit_shift > 0 && index + limb_shift < 3 {
+                *limb |= self.0[index + limb_shift + 1] << (64 - bit_shift);
+            }
+        }
+        Self(limbs)
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+}
+
+impl From<bool> for U256 {
+    fn from(value: bool) -> Self {
+        Self::from(value as u64)
+    }
+}
+
+impl std::fmt::Display for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut digits = Vec::new();
+        let mut value = *self;
+        loop {
+            let (quotient, remainder) = value.div_rem_u64(10);
+            digits.push(b'0' + remainder as u8);
+            value = quotient;
+            if value.is_zero() {
+                break;
+            }
+        }
+        digits.reverse();
+        f.pad_integral(
+            true,
+            "",
+            std::str::from_utf8(digits.as_slice()).expect("Always valid"),
+        )
+    }
+}
+
+impl std::fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut digits = String::new();
+        for limb in self.0.iter().rev() {
+            if digits.is_empty() {
+                if *limb != 0 {
+                    digits = format!("{limb:x}");
+                }
+            } else {
+                digits.push_str(format!("{limb:016x}").as_str());
+            }
+        }
+        if digits.is_empty() {
+            digits.push('0');
+        }
+        f.pad_integral(true, "0x", digits.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::U256;
+
+    /// Returns the word with the hexadecimal value.
+    fn word(hex: &str) -> U256 {
+        let bytes = hex::decode(format!("{hex:0>64}")).expect("Always valid");
+        U256::from_be_bytes(bytes.try_into().expect("Always 32 bytes"))
+    }
+
+    #[test]
+    fn carries_across_the_limbs() {
+        assert_eq!(U256::MAX.wrapping_add(&U256::ONE), U256::ZERO);
+        assert_eq!(U256::ZERO.wrapping_sub(&U256::ONE), U256::MAX);
+        assert_eq!(
+            word("ffffffffffffffffffffffffffffffff")
+                .wrapping_mul(&word("fedcba9876543210fedcba9876543210")),
+            word("fedcba9876543210fedcba987654320f0123456789abcdef0123456789abcdf0")
+        );
+        assert_eq!(
+            U256::MAX.div_rem(&word("1234567890abcdef1234567890abcdef")),
+            Some((
+                word("e10000007c6b900ca0dd3adf4dc87f9d3"),
+                word("3f1bb8aaa0ecd0203f1bb8aaa0ecd02")
+            ))
+        );
+        assert_eq!(U256::MAX.div_rem(&U256::ZERO), None);
+        assert_eq!(U256::ONE << 64, word("10000000000000000"));
+        assert_eq!(U256::MAX >> 192, word("ffffffffffffffff"));
+        assert_eq!((U256::MAX << 255) >> 255, U256::ONE);
+        assert_eq!(U256::MAX.checked_mul_add(1, 1), None);
+        assert_eq!(
+            U256::MAX.to_string(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        assert_eq!(format!("{:#x}", U256::ZERO), "0x0");
+    }
+}
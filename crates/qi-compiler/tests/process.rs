@@ -0,0 +1,86 @@
+//! The recursive process tests, compiling the contracts in the `qic` subprocesses.
+
+use std::path::Path;
+
+use qi_compiler::process::input::Input;
+use qi_compiler::solc::version::Version as SolcVersion;
+use qi_compiler::{DebugConfig, OptimizerSettings, Project};
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+/// The Yul IR of the Foundry `Counter` contract, written in the form `solc` optimizes it to.
+const COUNTER_YUL: &str = include_str!("fixtures/Counter.yul");
+
+/// Returns the project of the `Counter` contract, along with the contract whose Yul IR cannot be parsed.
+fn project() -> Project {
+    let contract = |ir: &str| {
+        serde_json::json!({
+            "Counter": {
+                "abi": [],
+                "metadata": "{}",
+                "evm": { "methodIdentifiers": { "increment()": "d09de08a" } },
+                "irOptimized": ir,
+            },
+        })
+    };
+    let output: SolcStandardJsonOutput = serde_json::from_value(serde_json::json!({
+        "contracts": {
+            "src/Counter.sol": contract(COUNTER_YUL),
+            "src/Broken.sol": contract("object \"Broken\" { code { return(0, } }"),
+        },
+    }))
+    .expect("Always valid");
+    Project::try_from_standard_json_output(
+        &output,
+        SolcVersion::new_simple(semver::Version::new(0, 8, 28)),
+    )
+    .expect("Always valid")
+}
+
+#[test]
+fn compiles_the_contracts_in_subprocesses() {
+    let executable = Path::new(env!("CARGO_BIN_EXE_qic"));
+    let mut project = project();
+    let contract = project
+        .contracts
+        .remove("src/Counter.sol:Counter")
+        .expect("Always valid");
+
+    let expected = contract
+        .clone()
+        .compile(
+            &project.solc_version,
+            OptimizerSettings::cycles(),
+            &DebugConfig::new(None, false),
+        )
+        .expect("Always valid");
+    let input = Input::new(
+        contract,
+        project.solc_version.clone(),
+        OptimizerSettings::cycles(),
+        DebugConfig::new(None, false),
+    );
+    let build = qi_compiler::process::call(executable, &input).expect("Always valid");
+    assert_eq!(
+        serde_json::to_value(build).expect("Always valid"),
+        serde_json::to_value(expected).expect("Always valid")
+    );
+
+    qi_compiler::process::EXECUTABLE
+        .set(executable.to_path_buf())
+        .expect("Always empty");
+    let build = self::project().compile(
+        OptimizerSettings::cycles(),
+        DebugConfig::new(None, false),
+        None,
+    );
+    assert!(build.results["src/Counter.sol:Counter"].is_ok());
+    let error = build.results["src/Broken.sol:Counter"]
+        .as_ref()
+        .expect_err("Always invalid");
+    assert!(
+        error
+            .to_string()
+            .starts_with("The compiler subprocess failed"),
+        "{error:#}"
+    );
+}